@@ -0,0 +1,56 @@
+"""
+Заглушка сброса пароля для локальной разработки, пока сервер не умеет отправлять письма.
+
+Включается переменной окружения BITUM_DEV_PASSWORD_RESET=1. Код не отдаётся в ответе, а пишется
+в журнал сервера, откуда его передаёт пользователю администратор. Коды живут в памяти процесса.
+"""
+from secrets import token_urlsafe
+from time import time
+from http import HTTPStatus
+from models.interfaces import ApiError
+from models.postgres.models import User
+
+RESET_CODE_TTL_SECONDS = 60 * 60
+# Не чаще одного запроса в минуту на пользователя
+REQUEST_INTERVAL_SECONDS = 60
+
+reset_codes: dict[str, tuple[int, float]] = {}
+last_requests: dict[int, float] = {}
+
+
+def _drop_expired(now: float):
+    for code, (_, expires_at) in list(reset_codes.items()):
+        if expires_at < now:
+            del reset_codes[code]
+
+
+def request_reset(user: User) -> str:
+    if user.is_bot():
+        raise ApiError(HTTPStatus.FORBIDDEN, 'Bots can not use this method.', None)
+
+    now = time()
+    _drop_expired(now)
+
+    if now - last_requests.get(user.id(), 0) < REQUEST_INTERVAL_SECONDS:
+        raise ApiError(HTTPStatus.TOO_MANY_REQUESTS, 'Reset password was requested too recently', None)
+
+    # У пользователя действует только последний код
+    for code, (user_id, _) in list(reset_codes.items()):
+        if user_id == user.id():
+            del reset_codes[code]
+
+    code = token_urlsafe(32)
+    reset_codes[code] = (user.id(), now + RESET_CODE_TTL_SECONDS)
+    last_requests[user.id()] = now
+
+    return code
+
+
+def reset(code: str, password: str):
+    _drop_expired(time())
+    user_id, _ = reset_codes.pop(code, (None, 0))
+
+    if user_id is None:
+        raise ApiError(HTTPStatus.NOT_FOUND, 'Reset code is invalid or expired', None)
+
+    User.get_by_id(user_id).set_password(password)
@@ -3,15 +3,15 @@ from hashlib import sha1
 from http import HTTPStatus
 from models.interfaces import ApiError
 from models.postgres.models import User, Chat, ChatMember, ChatMessage, ChatMemberPermissions, Event
-from . import validation_schemas
+from . import validation_schemas, dev_password_reset
 from .jwt import get_user_from_jwt, generate_jwt, JWT_TOKEN_COOKIE_NAME
 from .util import ok, only_user
 from .error_handlers import bind as bind_errors
-from config import API_FILES_SAVE_PATH, MAX_FILE_SIZE_IN_BYTES
+from config import API_FILES_SAVE_PATH, MAX_FILE_SIZE_IN_BYTES, DEV_PASSWORD_RESET
 
 from flask_cors import CORS
 from flask_expects_json import expects_json
-from flask import Blueprint, request, send_from_directory
+from flask import Blueprint, current_app, request, send_from_directory
 
 
 api = Blueprint('api', __name__)
@@ -52,17 +52,34 @@ def get_user_by_username(username: str):
     return ok(user.to_dict())
 
 
+def check_password_reset_available():
+    if not DEV_PASSWORD_RESET:
+        raise ApiError(HTTPStatus.NOT_IMPLEMENTED, 'Password reset is not available on this server', None)
+
+
+@api.route('/user/request_reset_password', methods=['GET'], strict_slashes=False)
+def get_password_reset_info():
+    return ok({'available': DEV_PASSWORD_RESET})
+
+
 @api.route('/user/request_reset_password/<string:username>', methods=['POST'], strict_slashes=False)
-@only_user
 def request_reset_password(username: str):
-    raise NotImplementedError()
+    check_password_reset_available()
+    user = User.get_by_username(username)
+    code = dev_password_reset.request_reset(user)
+
+    # Письма сервер не отправляет: код видит только тот, у кого есть доступ к журналу сервера
+    current_app.logger.warning('Password reset code for user %s: %s', user.username(), code)
+
+    return ok()
 
 
 @api.route('/user/reset_password/<string:code>', methods=['POST'], strict_slashes=False)
 @expects_json(validation_schemas.RESET_PASSWORD)
-@only_user
 def reset_password(code: str):
-    raise NotImplementedError()
+    check_password_reset_available()
+    dev_password_reset.reset(code, request.json['password'])
+    return ok()
 
 
 @api.route('/user/token', methods=['POST'], strict_slashes=False)
@@ -130,6 +147,20 @@ def delete_bot(bot_id: int, user: User):
     return ok()
 
 
+@api.route('/bot/<int:bot_id>', methods=['PATCH'], strict_slashes=False)
+@expects_json(validation_schemas.PATCH_BOT)
+@get_user_from_jwt
+@only_user
+@ApiError.wrap_exception(AssertionError, HTTPStatus.NOT_FOUND, 'This user does not own this bot')
+def patch_bot(bot_id: int, user: User):
+    bot = User.get_by_id(bot_id)
+    assert bot.is_bot()
+    assert bot.creator().id() == user.id()
+
+    bot.set_icon(request.json.get('icon_file', bot.icon()))
+    return ok(bot.to_dict())
+
+
 @api.route('/bots', methods=['GET'], strict_slashes=False)
 @get_user_from_jwt
 @only_user
@@ -197,6 +228,7 @@ def new_chat(user: User):
 @ api.route('/chat/<int:chat_id>', methods=['PATCH'], strict_slashes=False)
 @ get_user_from_jwt
 @ expects_json(validation_schemas.PATCH_CHAT)
+@ ApiError.wrap_exception(AssertionError, HTTPStatus.FORBIDDEN, f'You can not edit this chat')
 def patch_chat(chat_id: int, user: User):
     json_request = request.json
     chat = Chat.get_by_id(chat_id)
@@ -205,6 +237,11 @@ def patch_chat(chat_id: int, user: User):
 
     new_owner = User.get_by_id(json_request.get('owner_id', chat_owner.id()))
 
+    if new_owner.id() != chat_owner.id():
+        # Свои права владелец менять не может, поэтому новый владелец сразу получает все, как в Chat.new
+        new_owner_member = ChatMember.get_by_chat_and_user(chat, new_owner)
+        new_owner_member.set_permissions(ChatMemberPermissions(True, True, True))
+
     chat.set_name(json_request.get('name', chat.name()))
     chat.set_owner(new_owner)
     chat.set_icon(json_request.get('icon_file', chat.icon()))
@@ -239,6 +276,20 @@ def get_chats(user: User):
     return ok([el.to_dict() for el in chats])
 
 
+@ api.route('/chats/shared/<int:user_id>', methods=['GET'], strict_slashes=False)
+@ get_user_from_jwt
+def get_shared_chats(user_id: int, user: User):
+    chats = user.shared_chats(User.get_by_id(user_id))
+    return ok([el.to_dict() for el in chats])
+
+
+@ api.route('/chats/last_messages', methods=['GET'], strict_slashes=False)
+@ get_user_from_jwt
+def get_last_messages(user: User):
+    messages = user.last_messages()
+    return ok([el.to_dict() for el in messages])
+
+
 # /chat/ /member
 @ api.route('/chat/<int:chat_id>/member', methods=["POST"], strict_slashes=False)
 @ get_user_from_jwt
@@ -262,13 +313,17 @@ def add_chat_member(chat_id: int, user: User):
 def delete_member(chat_id: int, user_id: int, user: User):
     chat = Chat.get_by_id(chat_id)
 
-    assert ChatMember.get_by_chat_and_user(
-        chat, user).permissions().can_kick_members
+    # Покинуть чат может любой участник, кроме владельца
+    if user_id != user.id():
+        assert ChatMember.get_by_chat_and_user(
+            chat, user).permissions().can_kick_members
 
     asked_user = User.get_by_id(user_id)
+    assert asked_user.id() != chat.owner().id()
+
     chat_member = ChatMember.get_by_chat_and_user(chat, asked_user)
 
-    chat_member.delete()
+    chat_member.delete(notify_member=user_id != user.id())
 
     return ok()
 
@@ -17,6 +17,7 @@ JWT_EXP_HOURS = 31 * 24
 JWT_HS256_SECRET = environ['JWT_SECRET']
 
 JWT_TOKEN_COOKIE_NAME = 'api_token'
+JWT_TOKEN_HEADER_PREFIX = 'Bearer '
 
 
 def generate_jwt(user: User) -> str:
@@ -36,11 +37,21 @@ def validate_and_parse_jwt(token: str) -> Dict[str, Any]:
     return payload
 
 
+def get_request_token() -> str:
+    # Из браузера нельзя подставить чужую куку, поэтому токен можно передать и в заголовке
+    authorization = request.headers.get('Authorization', '')
+
+    if authorization.startswith(JWT_TOKEN_HEADER_PREFIX):
+        return authorization[len(JWT_TOKEN_HEADER_PREFIX):]
+
+    return request.cookies.get(JWT_TOKEN_COOKIE_NAME, '')
+
+
 def get_user_from_jwt(function):
     @wraps(function)
     def wrapper(*args, **kwargs):
         try:
-            token = request.cookies.get(JWT_TOKEN_COOKIE_NAME, '')
+            token = get_request_token()
             parsed = validate_and_parse_jwt(token)
 
             user = User.get_by_id(parsed['user_id'])
@@ -48,7 +59,7 @@ def get_user_from_jwt(function):
 
             return function(*args, **kwargs, user=user)
 
-        except (AssertionError, jwt.exceptions.DecodeError) as e:
+        except (AssertionError, jwt.exceptions.InvalidTokenError) as e:
             raise ApiError(HTTPStatus.UNAUTHORIZED, 'Unauthorized', e)
 
         except Exception as another_e:
@@ -29,6 +29,7 @@ PATCH_USER = {
     'type': 'object',
     'properties': {
         'username': {'type': 'string', 'minLength': 1, 'maxLength': 100},
+        'password': {'type': 'string', 'minLength': 4, 'maxLength': 100},
         'email': {'type': 'string', 'format': 'email', "minLength": 6, "maxLength": 127, "pattern": "^\\S+@\\S+\\.\\S+$"},
         'icon_file': {'type': ["string", "null"], 'minLength': 4, 'maxLength': 400, 'default': None}
     }
@@ -55,6 +56,13 @@ CREATE_BOT = {
     ]
 }
 
+PATCH_BOT = {
+    'type': 'object',
+    'properties': {
+        'icon_file': {'type': ["string", "null"], 'minLength': 4, 'maxLength': 400}
+    }
+}
+
 CREATE_CHAT = {
     'type': 'object',
     'properties': {
@@ -1,2 +1,7 @@
+from os import environ
+
 API_FILES_SAVE_PATH = "files/"
 MAX_FILE_SIZE_IN_BYTES = 1024 * 1024 * 10  # 10mb
+
+# Сброс пароля без отправки писем, только для разработки: см. api/dev_password_reset.py
+DEV_PASSWORD_RESET = environ.get('BITUM_DEV_PASSWORD_RESET') == '1'
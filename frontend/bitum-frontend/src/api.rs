@@ -1,5 +1,8 @@
+pub mod bot_client;
+pub mod explorer;
 pub mod methods;
-pub mod structs;
 
+pub use bitum_types as structs;
+pub use bot_client::*;
 pub use methods::*;
 pub use structs::*;
@@ -0,0 +1,125 @@
+use gloo_net::http::Request;
+
+use super::*;
+
+/// Запросы к API от имени бота: токен передаётся в заголовке, куки владельца не отправляются
+#[derive(Clone, PartialEq, Debug)]
+pub struct BotClient {
+    token: String,
+}
+
+impl BotClient {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+
+    pub fn authorize(&self, request: Request) -> Request {
+        request
+            .credentials(web_sys::RequestCredentials::Omit)
+            .header("Authorization", &format!("Bearer {}", self.token))
+    }
+
+    pub async fn get_current_user_info(&self) -> Result<Response<User>, ApiCallError> {
+        let response: Response<User> = self
+            .authorize(Request::get(&endpoint("/user")))
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+        Ok(response)
+    }
+
+    pub async fn get_chats(&self) -> Result<Response<Vec<Chat>>, ApiCallError> {
+        let response: Response<Vec<Chat>> = self
+            .authorize(Request::get(&endpoint("/chats")))
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+        Ok(response)
+    }
+
+    /// События без разбора в `Event`, чтобы можно было показать их как есть
+    pub async fn get_raw_events(&self) -> Result<Response<Vec<serde_json::Value>>, ApiCallError> {
+        let response: Response<Vec<serde_json::Value>> = self
+            .authorize(Request::get(&endpoint("/events")))
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+        Ok(response)
+    }
+
+    pub async fn read_events(
+        &self,
+        read_events_request: ReadEventsRequest,
+    ) -> Result<Response<ReadEventsResponseData>, ApiCallError> {
+        let response: Response<ReadEventsResponseData> = self
+            .authorize(Request::delete(&endpoint("/events")))
+            .json(&read_events_request)
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+        Ok(response)
+    }
+
+    pub async fn send_message(
+        &self,
+        chat_id: i32,
+        send_message_request: SendMessageRequest,
+    ) -> Result<Response<ChatMessage>, ApiCallError> {
+        let response: Response<ChatMessage> = self
+            .authorize(Request::post(&endpoint(&format!(
+                "/chat/{}/message",
+                chat_id
+            ))))
+            .json(&send_message_request)
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+        Ok(response)
+    }
+}
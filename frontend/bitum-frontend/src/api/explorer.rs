@@ -0,0 +1,825 @@
+//! Описание методов API для `/api-explorer`: по нему строятся формы, а ответы разбираются теми же типами, что и в `methods`
+
+use std::{collections::HashMap, fmt::Debug};
+
+use gloo_net::http::{Method, Request};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use web_sys::{Blob, File, FormData};
+
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Patch,
+    Delete,
+}
+
+impl HttpMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
+
+    /// Класс Bootstrap для значка метода
+    pub fn color_class(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "text-bg-primary",
+            HttpMethod::Post => "text-bg-success",
+            HttpMethod::Patch => "text-bg-warning",
+            HttpMethod::Delete => "text-bg-danger",
+        }
+    }
+
+    fn request_method(&self) -> Method {
+        match self {
+            HttpMethod::Get => Method::GET,
+            HttpMethod::Post => Method::POST,
+            HttpMethod::Patch => Method::PATCH,
+            HttpMethod::Delete => Method::DELETE,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FieldKind {
+    Text,
+    Integer,
+    Boolean,
+    /// Значения через запятую
+    TextList,
+    IntegerList,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FieldSpec {
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub optional: bool,
+    pub description: &'static str,
+}
+
+const fn field(name: &'static str, kind: FieldKind, description: &'static str) -> FieldSpec {
+    FieldSpec {
+        name,
+        kind,
+        optional: false,
+        description,
+    }
+}
+
+const fn optional(name: &'static str, kind: FieldKind, description: &'static str) -> FieldSpec {
+    FieldSpec {
+        name,
+        kind,
+        optional: true,
+        description,
+    }
+}
+
+/// Тело запроса, для которого можно построить форму
+pub trait ExplorerRequest: Serialize + DeserializeOwned {
+    const NAME: &'static str;
+    const FIELDS: &'static [FieldSpec];
+}
+
+impl ExplorerRequest for NewUserRequest {
+    const NAME: &'static str = "NewUserRequest";
+    const FIELDS: &'static [FieldSpec] = &[
+        field("username", FieldKind::Text, "Имя пользователя"),
+        field("password", FieldKind::Text, "Пароль"),
+        field("email", FieldKind::Text, "Почта"),
+    ];
+}
+
+impl ExplorerRequest for GetTokenRequest {
+    const NAME: &'static str = "GetTokenRequest";
+    const FIELDS: &'static [FieldSpec] = &[
+        field("username", FieldKind::Text, "Имя пользователя"),
+        field("password", FieldKind::Text, "Пароль"),
+    ];
+}
+
+impl ExplorerRequest for PatchUserRequest {
+    const NAME: &'static str = "PatchUserRequest";
+    const FIELDS: &'static [FieldSpec] = &[
+        optional("email", FieldKind::Text, "Новая почта"),
+        optional(
+            "icon_file",
+            FieldKind::Text,
+            "Имя загруженного файла аватара",
+        ),
+        optional("password", FieldKind::Text, "Новый пароль"),
+    ];
+}
+
+impl ExplorerRequest for ResetPasswordRequest {
+    const NAME: &'static str = "ResetPasswordRequest";
+    const FIELDS: &'static [FieldSpec] = &[field("password", FieldKind::Text, "Новый пароль")];
+}
+
+impl ExplorerRequest for NewChatRequest {
+    const NAME: &'static str = "NewChatRequest";
+    const FIELDS: &'static [FieldSpec] = &[
+        field("name", FieldKind::Text, "Имя чата"),
+        optional(
+            "icon_file",
+            FieldKind::Text,
+            "Имя загруженного файла иконки",
+        ),
+    ];
+}
+
+impl ExplorerRequest for PatchChatRequest {
+    const NAME: &'static str = "PatchChatRequest";
+    const FIELDS: &'static [FieldSpec] = &[
+        optional("name", FieldKind::Text, "Новое имя чата"),
+        optional(
+            "icon_file",
+            FieldKind::Text,
+            "Имя загруженного файла иконки",
+        ),
+        optional("owner_id", FieldKind::Integer, "ID нового владельца"),
+    ];
+}
+
+impl ExplorerRequest for AddChatMemberRequest {
+    const NAME: &'static str = "AddChatMemberRequest";
+    const FIELDS: &'static [FieldSpec] = &[field("user_id", FieldKind::Integer, "ID пользователя")];
+}
+
+impl ExplorerRequest for ChatMemberPermissions {
+    const NAME: &'static str = "ChatMemberPermissions";
+    const FIELDS: &'static [FieldSpec] = &[
+        field("can_write", FieldKind::Boolean, "Может писать"),
+        field(
+            "can_add_members",
+            FieldKind::Boolean,
+            "Может добавлять участников",
+        ),
+        field(
+            "can_kick_members",
+            FieldKind::Boolean,
+            "Может удалять участников",
+        ),
+    ];
+}
+
+impl ExplorerRequest for SendMessageRequest {
+    const NAME: &'static str = "SendMessageRequest";
+    const FIELDS: &'static [FieldSpec] = &[
+        field("content", FieldKind::Text, "Текст сообщения в Markdown"),
+        field("files", FieldKind::TextList, "Имена загруженных файлов"),
+    ];
+}
+
+impl ExplorerRequest for NewBotRequest {
+    const NAME: &'static str = "NewBotRequest";
+    const FIELDS: &'static [FieldSpec] = &[
+        field("username", FieldKind::Text, "Имя бота"),
+        optional(
+            "icon_file",
+            FieldKind::Text,
+            "Имя загруженного файла иконки",
+        ),
+    ];
+}
+
+impl ExplorerRequest for PatchBotRequest {
+    const NAME: &'static str = "PatchBotRequest";
+    const FIELDS: &'static [FieldSpec] = &[optional(
+        "icon_file",
+        FieldKind::Text,
+        "Имя загруженного файла иконки",
+    )];
+}
+
+impl ExplorerRequest for ReadEventsRequest {
+    const NAME: &'static str = "ReadEventsRequest";
+    const FIELDS: &'static [FieldSpec] = &[field(
+        "ids",
+        FieldKind::IntegerList,
+        "ID событий, которые нужно прочитать",
+    )];
+}
+
+pub enum RequestBody {
+    None,
+    Json {
+        struct_name: &'static str,
+        fields: &'static [FieldSpec],
+        /// Пропускает JSON через тип запроса, чтобы отправлять только то, что отправил бы клиент
+        check: fn(Value) -> Result<Value, String>,
+    },
+    /// `multipart/form-data` с одним файлом
+    File,
+}
+
+fn check_body<T: ExplorerRequest>(value: Value) -> Result<Value, String> {
+    let request = serde_json::from_value::<T>(value)
+        .map_err(|e| format!("Не подходит под {}: {}", T::NAME, e))?;
+
+    serde_json::to_value(request).map_err(|e| e.to_string())
+}
+
+fn json_body<T: ExplorerRequest>() -> RequestBody {
+    RequestBody::Json {
+        struct_name: T::NAME,
+        fields: T::FIELDS,
+        check: check_body::<T>,
+    }
+}
+
+fn decode<T: DeserializeOwned + Debug>(text: &str) -> Result<String, String> {
+    serde_json::from_str::<Response<T>>(text)
+        .map(|response| format!("{:#?}", response))
+        .map_err(|e| e.to_string())
+}
+
+const PAGE_QUERY: &[FieldSpec] = &[
+    optional("limit", FieldKind::Integer, "Сколько вернуть, не больше 50"),
+    optional("offset", FieldKind::Integer, "Сколько пропустить"),
+];
+
+const SEARCH_QUERY: &[FieldSpec] = &[
+    field("username", FieldKind::Text, "Начало имени"),
+    PAGE_QUERY[0],
+    PAGE_QUERY[1],
+];
+
+pub struct EndpointSpec {
+    /// Функция из `api::methods`, которая делает этот запрос
+    pub function: &'static str,
+    pub method: HttpMethod,
+    /// Параметры пути записываются как `{chat_id}`
+    pub path: &'static str,
+    pub description: &'static str,
+    pub query: &'static [FieldSpec],
+    pub body: RequestBody,
+    pub response: &'static str,
+    pub decode: fn(&str) -> Result<String, String>,
+}
+
+impl EndpointSpec {
+    pub fn path_params(&self) -> Vec<&'static str> {
+        self.path
+            .split('/')
+            .filter_map(|part| part.strip_prefix('{')?.strip_suffix('}'))
+            .collect()
+    }
+
+    pub fn url(&self, path_values: &HashMap<String, String>) -> Result<String, String> {
+        let mut path = self.path.to_string();
+
+        for param in self.path_params() {
+            let value = path_values
+                .get(param)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| format!("Заполните параметр пути {}", param))?;
+
+            path = path.replace(&format!("{{{}}}", param), &path_segment(value));
+        }
+
+        Ok(endpoint(&path))
+    }
+}
+
+/// Все методы из `api::methods`, методы участников чата собраны вместе
+pub fn endpoints() -> Vec<EndpointSpec> {
+    vec![
+        EndpointSpec {
+            function: "get_current_user_info",
+            method: HttpMethod::Get,
+            path: "/user",
+            description: "Текущий пользователь или бот",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<User>",
+            decode: decode::<User>,
+        },
+        EndpointSpec {
+            function: "get_user",
+            method: HttpMethod::Get,
+            path: "/user/{user_id}",
+            description: "Пользователь по ID",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<User>",
+            decode: decode::<User>,
+        },
+        EndpointSpec {
+            function: "get_user_by_username",
+            method: HttpMethod::Get,
+            path: "/user/{username}",
+            description: "Пользователь по имени",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<User>",
+            decode: decode::<User>,
+        },
+        EndpointSpec {
+            function: "new_user",
+            method: HttpMethod::Post,
+            path: "/user",
+            description: "Регистрация",
+            query: &[],
+            body: json_body::<NewUserRequest>(),
+            response: "Response<User>",
+            decode: decode::<User>,
+        },
+        EndpointSpec {
+            function: "patch_user",
+            method: HttpMethod::Patch,
+            path: "/user",
+            description: "Изменить текущего пользователя",
+            query: &[],
+            body: json_body::<PatchUserRequest>(),
+            response: "Response<User>",
+            decode: decode::<User>,
+        },
+        EndpointSpec {
+            function: "get_user_token",
+            method: HttpMethod::Post,
+            path: "/user/token",
+            description: "Войти и получить токен",
+            query: &[],
+            body: json_body::<GetTokenRequest>(),
+            response: "Response<GetTokenResponseData>",
+            decode: decode::<GetTokenResponseData>,
+        },
+        EndpointSpec {
+            function: "get_password_reset_info",
+            method: HttpMethod::Get,
+            path: "/user/request_reset_password",
+            description: "Можно ли на сервере сбросить пароль",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<PasswordResetInfo>",
+            decode: decode::<PasswordResetInfo>,
+        },
+        EndpointSpec {
+            function: "request_reset_password",
+            method: HttpMethod::Post,
+            path: "/user/request_reset_password/{username}",
+            description: "Запросить код для сброса пароля",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<()>",
+            decode: decode::<()>,
+        },
+        EndpointSpec {
+            function: "reset_password",
+            method: HttpMethod::Post,
+            path: "/user/reset_password/{code}",
+            description: "Сменить пароль по коду из ссылки",
+            query: &[],
+            body: json_body::<ResetPasswordRequest>(),
+            response: "Response<()>",
+            decode: decode::<()>,
+        },
+        EndpointSpec {
+            function: "new_chat",
+            method: HttpMethod::Post,
+            path: "/chat",
+            description: "Создать чат",
+            query: &[],
+            body: json_body::<NewChatRequest>(),
+            response: "Response<Chat>",
+            decode: decode::<Chat>,
+        },
+        EndpointSpec {
+            function: "get_chats",
+            method: HttpMethod::Get,
+            path: "/chats",
+            description: "Чаты, в которых состоит пользователь",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<Vec<Chat>>",
+            decode: decode::<Vec<Chat>>,
+        },
+        EndpointSpec {
+            function: "get_last_messages",
+            method: HttpMethod::Get,
+            path: "/chats/last_messages",
+            description: "Последнее сообщение из каждого чата пользователя",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<Vec<ChatMessage>>",
+            decode: decode::<Vec<ChatMessage>>,
+        },
+        EndpointSpec {
+            function: "get_shared_chats",
+            method: HttpMethod::Get,
+            path: "/chats/shared/{user_id}",
+            description: "Общие чаты с другим пользователем",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<Vec<Chat>>",
+            decode: decode::<Vec<Chat>>,
+        },
+        EndpointSpec {
+            function: "get_chat",
+            method: HttpMethod::Get,
+            path: "/chat/{chat_id}",
+            description: "Чат по ID",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<Chat>",
+            decode: decode::<Chat>,
+        },
+        EndpointSpec {
+            function: "patch_chat",
+            method: HttpMethod::Patch,
+            path: "/chat/{chat_id}",
+            description: "Изменить чат или передать его другому участнику",
+            query: &[],
+            body: json_body::<PatchChatRequest>(),
+            response: "Response<Chat>",
+            decode: decode::<Chat>,
+        },
+        EndpointSpec {
+            function: "delete_chat",
+            method: HttpMethod::Delete,
+            path: "/chat/{chat_id}",
+            description: "Удалить чат",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<()>",
+            decode: decode::<()>,
+        },
+        EndpointSpec {
+            function: "upload_file",
+            method: HttpMethod::Post,
+            path: "/files/",
+            description: "Загрузить файл",
+            query: &[],
+            body: RequestBody::File,
+            response: "Response<Vec<String>>",
+            decode: decode::<Vec<String>>,
+        },
+        EndpointSpec {
+            function: "get_messages",
+            method: HttpMethod::Get,
+            path: "/chat/{chat_id}/messages",
+            description: "Сообщения чата, новые первыми",
+            query: PAGE_QUERY,
+            body: RequestBody::None,
+            response: "Response<Vec<ChatMessage>>",
+            decode: decode::<Vec<ChatMessage>>,
+        },
+        EndpointSpec {
+            function: "send_message",
+            method: HttpMethod::Post,
+            path: "/chat/{chat_id}/message",
+            description: "Отправить сообщение",
+            query: &[],
+            body: json_body::<SendMessageRequest>(),
+            response: "Response<ChatMessage>",
+            decode: decode::<ChatMessage>,
+        },
+        EndpointSpec {
+            function: "get_chat_members",
+            method: HttpMethod::Get,
+            path: "/chat/{chat_id}/members",
+            description: "Участники чата",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<Vec<ChatMember>>",
+            decode: decode::<Vec<ChatMember>>,
+        },
+        EndpointSpec {
+            function: "get_chat_member",
+            method: HttpMethod::Get,
+            path: "/chat/{chat_id}/member/{user_id}",
+            description: "Участник чата",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<ChatMember>",
+            decode: decode::<ChatMember>,
+        },
+        EndpointSpec {
+            function: "add_chat_member",
+            method: HttpMethod::Post,
+            path: "/chat/{chat_id}/member",
+            description: "Добавить участника",
+            query: &[],
+            body: json_body::<AddChatMemberRequest>(),
+            response: "Response<ChatMember>",
+            decode: decode::<ChatMember>,
+        },
+        EndpointSpec {
+            function: "patch_chat_member_permissions",
+            method: HttpMethod::Patch,
+            path: "/chat/{chat_id}/member/{user_id}",
+            description: "Изменить права участника",
+            query: &[],
+            body: json_body::<ChatMemberPermissions>(),
+            response: "Response<ChatMember>",
+            decode: decode::<ChatMember>,
+        },
+        EndpointSpec {
+            function: "delete_chat_member",
+            method: HttpMethod::Delete,
+            path: "/chat/{chat_id}/member/{user_id}",
+            description: "Удалить участника или выйти из чата",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<()>",
+            decode: decode::<()>,
+        },
+        EndpointSpec {
+            function: "search_users",
+            method: HttpMethod::Get,
+            path: "/user/search",
+            description: "Поиск пользователей по имени",
+            query: SEARCH_QUERY,
+            body: RequestBody::None,
+            response: "Response<Vec<User>>",
+            decode: decode::<Vec<User>>,
+        },
+        EndpointSpec {
+            function: "search_bots",
+            method: HttpMethod::Get,
+            path: "/bot/search",
+            description: "Поиск ботов по имени",
+            query: SEARCH_QUERY,
+            body: RequestBody::None,
+            response: "Response<Vec<User>>",
+            decode: decode::<Vec<User>>,
+        },
+        EndpointSpec {
+            function: "get_bots",
+            method: HttpMethod::Get,
+            path: "/bots",
+            description: "Боты текущего пользователя",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<Vec<User>>",
+            decode: decode::<Vec<User>>,
+        },
+        EndpointSpec {
+            function: "new_bot",
+            method: HttpMethod::Post,
+            path: "/bot",
+            description: "Создать бота",
+            query: &[],
+            body: json_body::<NewBotRequest>(),
+            response: "Response<User>",
+            decode: decode::<User>,
+        },
+        EndpointSpec {
+            function: "patch_bot",
+            method: HttpMethod::Patch,
+            path: "/bot/{bot_id}",
+            description: "Изменить бота",
+            query: &[],
+            body: json_body::<PatchBotRequest>(),
+            response: "Response<User>",
+            decode: decode::<User>,
+        },
+        EndpointSpec {
+            function: "get_bot_token",
+            method: HttpMethod::Post,
+            path: "/bot/{bot_id}/token",
+            description: "Выпустить новый токен бота. Старый перестанет работать",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<GetTokenResponseData>",
+            decode: decode::<GetTokenResponseData>,
+        },
+        EndpointSpec {
+            function: "delete_bot",
+            method: HttpMethod::Delete,
+            path: "/bot/{bot_id}",
+            description: "Удалить бота",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<()>",
+            decode: decode::<()>,
+        },
+        EndpointSpec {
+            function: "get_events",
+            method: HttpMethod::Get,
+            path: "/events",
+            description: "Непрочитанные события",
+            query: &[],
+            body: RequestBody::None,
+            response: "Response<Vec<Event>>",
+            decode: decode::<Vec<Event>>,
+        },
+        EndpointSpec {
+            function: "read_events",
+            method: HttpMethod::Delete,
+            path: "/events",
+            description: "Отметить события прочитанными",
+            query: &[],
+            body: json_body::<ReadEventsRequest>(),
+            response: "Response<ReadEventsResponseData>",
+            decode: decode::<ReadEventsResponseData>,
+        },
+    ]
+}
+
+fn parse_integer(field: &FieldSpec, value: &str) -> Result<i64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{}: нужно целое число", field.name))
+}
+
+fn list_items(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// JSON-объект из значений формы. Пустые необязательные поля не отправляются
+pub fn build_json(fields: &[FieldSpec], values: &HashMap<String, String>) -> Result<Value, String> {
+    let mut object = Map::new();
+
+    for field in fields {
+        let raw = values
+            .get(field.name)
+            .map(|value| value.trim())
+            .unwrap_or_default();
+
+        if raw.is_empty() && field.optional {
+            continue;
+        }
+
+        let value = match field.kind {
+            FieldKind::Text => Value::from(raw),
+            FieldKind::Integer => Value::from(parse_integer(field, raw)?),
+            FieldKind::Boolean => Value::from(raw == "true"),
+            FieldKind::TextList => Value::from(list_items(raw).collect::<Vec<_>>()),
+            FieldKind::IntegerList => Value::from(
+                list_items(raw)
+                    .map(|item| parse_integer(field, item))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        };
+
+        object.insert(field.name.to_string(), value);
+    }
+
+    Ok(Value::Object(object))
+}
+
+pub fn build_query(
+    fields: &[FieldSpec],
+    values: &HashMap<String, String>,
+) -> Result<Vec<(&'static str, String)>, String> {
+    let mut query = Vec::new();
+
+    for field in fields {
+        let raw = values
+            .get(field.name)
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default();
+
+        if raw.is_empty() && field.optional {
+            continue;
+        }
+
+        if field.kind == FieldKind::Integer {
+            parse_integer(field, &raw)?;
+        }
+
+        query.push((field.name, raw));
+    }
+
+    Ok(query)
+}
+
+pub enum ExplorerBody {
+    None,
+    Json(Value),
+    File(File),
+}
+
+/// Ответ как есть: разбирать его будет `EndpointSpec::decode`
+pub struct ExplorerResponse {
+    pub status: u16,
+    pub text: String,
+}
+
+/// Запрос от имени бота, если передан клиент, иначе от имени вошедшего пользователя
+pub async fn call_endpoint(
+    method: HttpMethod,
+    url: &str,
+    query: Vec<(&'static str, String)>,
+    body: ExplorerBody,
+    client: Option<&BotClient>,
+) -> Result<ExplorerResponse, ApiCallError> {
+    let request = Request::new(url)
+        .method(method.request_method())
+        .query(query);
+    let request = match client {
+        Some(client) => client.authorize(request),
+        None => request.credentials(web_sys::RequestCredentials::Include),
+    };
+    let request = match body {
+        ExplorerBody::None => request,
+        ExplorerBody::Json(value) => request.json(&value).map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?,
+        ExplorerBody::File(file) => {
+            let form_data = FormData::new().unwrap();
+
+            form_data
+                .append_with_blob("file", &Blob::from(file))
+                .map_err(|e| ApiCallError {
+                    message: format!("{:?}", e),
+                })?;
+
+            request.body(form_data)
+        }
+    };
+
+    let response = request.send().await.map_err(|e| ApiCallError {
+        message: e.to_string(),
+    })?;
+    let text = response.text().await.map_err(|e| ApiCallError {
+        message: e.to_string(),
+    })?;
+
+    Ok(ExplorerResponse {
+        status: response.status(),
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_value(kind: FieldKind) -> &'static str {
+        match kind {
+            FieldKind::Text => "text",
+            FieldKind::Integer => "1",
+            FieldKind::Boolean => "true",
+            FieldKind::TextList => "a, b",
+            FieldKind::IntegerList => "1, 2",
+        }
+    }
+
+    fn sample_values<'a>(fields: impl Iterator<Item = &'a FieldSpec>) -> HashMap<String, String> {
+        fields
+            .map(|field| (field.name.to_string(), sample_value(field.kind).to_string()))
+            .collect()
+    }
+
+    /// Форма из `FIELDS` проходит через тип запроса без потерь: имена и типы полей совпадают со структурой
+    fn assert_fields_match<T: ExplorerRequest>() {
+        let full = build_json(T::FIELDS, &sample_values(T::FIELDS.iter())).unwrap();
+
+        assert_eq!(check_body::<T>(full.clone()), Ok(full), "{}", T::NAME);
+
+        // Без необязательных полей запрос тоже собирается
+        let required_values = sample_values(T::FIELDS.iter().filter(|field| !field.optional));
+        let required = build_json(T::FIELDS, &required_values).unwrap();
+
+        assert!(check_body::<T>(required).is_ok(), "{}", T::NAME);
+    }
+
+    #[test]
+    fn request_fields_match_structs() {
+        assert_fields_match::<NewUserRequest>();
+        assert_fields_match::<GetTokenRequest>();
+        assert_fields_match::<PatchUserRequest>();
+        assert_fields_match::<ResetPasswordRequest>();
+        assert_fields_match::<NewChatRequest>();
+        assert_fields_match::<PatchChatRequest>();
+        assert_fields_match::<AddChatMemberRequest>();
+        assert_fields_match::<ChatMemberPermissions>();
+        assert_fields_match::<SendMessageRequest>();
+        assert_fields_match::<NewBotRequest>();
+        assert_fields_match::<PatchBotRequest>();
+        assert_fields_match::<ReadEventsRequest>();
+    }
+
+    #[test]
+    fn endpoints_cover_all_methods() {
+        let mut methods: Vec<&str> = include_str!("methods.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub async fn "))
+            .filter_map(|line| line.split(['(', '<']).next())
+            .collect();
+        let mut functions: Vec<&str> = endpoints()
+            .iter()
+            .map(|endpoint| endpoint.function)
+            .collect();
+
+        methods.sort_unstable();
+        functions.sort_unstable();
+
+        assert_eq!(functions, methods);
+    }
+}
@@ -1,20 +1,30 @@
 use gloo_net::http::Request;
-use serde_json::json;
 use web_sys::{Blob, File, FormData};
 
 use super::*;
 
 const API_PREFIX: &str = "/api";
 
-fn endpoint(method: &str) -> String {
+pub fn endpoint(method: &str) -> String {
     format!("{}{}", API_PREFIX, method)
 }
 
+/// Экранирует значение для подстановки в путь: `/`, `?`, `#` и пробелы не ломают url
+pub fn path_segment(value: &str) -> String {
+    String::from(js_sys::encode_uri_component(value))
+}
+
 #[derive(Debug)]
 pub struct ApiCallError {
     pub message: String,
 }
 
+impl std::fmt::Display for ApiCallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
 pub async fn get_current_user_info() -> Result<Response<User>, ApiCallError> {
     let response: Response<User> = Request::get(&endpoint("/user"))
         .credentials(web_sys::RequestCredentials::Include)
@@ -32,6 +42,41 @@ pub async fn get_current_user_info() -> Result<Response<User>, ApiCallError> {
     Ok(response)
 }
 
+pub async fn get_user(user_id: i32) -> Result<Response<User>, ApiCallError> {
+    let response: Response<User> = Request::get(&endpoint(&format!("/user/{}", user_id)))
+        .credentials(web_sys::RequestCredentials::Include)
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
+
+pub async fn get_user_by_username(username: &str) -> Result<Response<User>, ApiCallError> {
+    let response: Response<User> =
+        Request::get(&endpoint(&format!("/user/{}", path_segment(username))))
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+    Ok(response)
+}
+
 pub async fn new_user(new_user_request: NewUserRequest) -> Result<Response<User>, ApiCallError> {
     let response: Response<User> = Request::post(&endpoint("/user"))
         .credentials(web_sys::RequestCredentials::Include)
@@ -53,6 +98,29 @@ pub async fn new_user(new_user_request: NewUserRequest) -> Result<Response<User>
     Ok(response)
 }
 
+pub async fn patch_user(
+    patch_user_request: PatchUserRequest,
+) -> Result<Response<User>, ApiCallError> {
+    let response: Response<User> = Request::patch(&endpoint("/user"))
+        .credentials(web_sys::RequestCredentials::Include)
+        .json(&patch_user_request)
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
+
 pub async fn get_user_token(
     token_request: GetTokenRequest,
 ) -> Result<Response<GetTokenResponseData>, ApiCallError> {
@@ -76,6 +144,71 @@ pub async fn get_user_token(
     Ok(response)
 }
 
+pub async fn get_password_reset_info() -> Result<Response<PasswordResetInfo>, ApiCallError> {
+    let response: Response<PasswordResetInfo> =
+        Request::get(&endpoint("/user/request_reset_password"))
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+    Ok(response)
+}
+
+pub async fn request_reset_password(username: &str) -> Result<Response<()>, ApiCallError> {
+    let response = Request::post(&endpoint(&format!(
+        "/user/request_reset_password/{}",
+        path_segment(username)
+    )))
+    .credentials(web_sys::RequestCredentials::Include)
+    .send()
+    .await
+    .map_err(|e| ApiCallError {
+        message: e.to_string(),
+    })?
+    .json()
+    .await
+    .map_err(|e| ApiCallError {
+        message: e.to_string(),
+    })?;
+
+    Ok(response)
+}
+
+pub async fn reset_password(
+    code: &str,
+    reset_password_request: ResetPasswordRequest,
+) -> Result<Response<()>, ApiCallError> {
+    let response = Request::post(&endpoint(&format!(
+        "/user/reset_password/{}",
+        path_segment(code)
+    )))
+    .credentials(web_sys::RequestCredentials::Include)
+    .json(&reset_password_request)
+    .map_err(|e| ApiCallError {
+        message: e.to_string(),
+    })?
+    .send()
+    .await
+    .map_err(|e| ApiCallError {
+        message: e.to_string(),
+    })?
+    .json()
+    .await
+    .map_err(|e| ApiCallError {
+        message: e.to_string(),
+    })?;
+
+    Ok(response)
+}
+
 pub async fn new_chat(new_chat_requests: NewChatRequest) -> Result<Response<Chat>, ApiCallError> {
     let response: Response<Chat> = Request::post(&endpoint("/chat"))
         .credentials(web_sys::RequestCredentials::Include)
@@ -114,6 +247,41 @@ pub async fn get_chats() -> Result<Response<Vec<Chat>>, ApiCallError> {
     Ok(response)
 }
 
+pub async fn get_last_messages() -> Result<Response<Vec<ChatMessage>>, ApiCallError> {
+    let response: Response<Vec<ChatMessage>> = Request::get(&endpoint("/chats/last_messages"))
+        .credentials(web_sys::RequestCredentials::Include)
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
+
+pub async fn get_shared_chats(user_id: i32) -> Result<Response<Vec<Chat>>, ApiCallError> {
+    let response: Response<Vec<Chat>> =
+        Request::get(&endpoint(&format!("/chats/shared/{}", user_id)))
+            .credentials(web_sys::RequestCredentials::Include)
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+    Ok(response)
+}
+
 pub async fn get_chat(chat_id: i32) -> Result<Response<Chat>, ApiCallError> {
     let response: Response<Chat> = Request::get(&endpoint(&format!("/chat/{}", chat_id)))
         .credentials(web_sys::RequestCredentials::Include)
@@ -131,6 +299,47 @@ pub async fn get_chat(chat_id: i32) -> Result<Response<Chat>, ApiCallError> {
     Ok(response)
 }
 
+pub async fn patch_chat(
+    chat_id: i32,
+    patch_chat_request: PatchChatRequest,
+) -> Result<Response<Chat>, ApiCallError> {
+    let response: Response<Chat> = Request::patch(&endpoint(&format!("/chat/{}", chat_id)))
+        .credentials(web_sys::RequestCredentials::Include)
+        .json(&patch_chat_request)
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
+
+pub async fn delete_chat(chat_id: i32) -> Result<Response<()>, ApiCallError> {
+    let response = Request::delete(&endpoint(&format!("/chat/{}", chat_id)))
+        .credentials(web_sys::RequestCredentials::Include)
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
+
 pub async fn upload_file(file: File) -> Result<Response<Vec<String>>, ApiCallError> {
     let form_data = FormData::new().unwrap();
 
@@ -254,7 +463,33 @@ pub async fn add_chat_member(
     let response: Response<ChatMember> =
         Request::post(&endpoint(&format!("/chat/{}/member", chat_id)))
             .credentials(web_sys::RequestCredentials::Include)
-            .json(&json!({ "user_id": user_id }))
+            .json(&AddChatMemberRequest { user_id })
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .send()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?
+            .json()
+            .await
+            .map_err(|e| ApiCallError {
+                message: e.to_string(),
+            })?;
+
+    Ok(response)
+}
+
+pub async fn patch_chat_member_permissions(
+    chat_id: i32,
+    user_id: i32,
+    permissions: ChatMemberPermissions,
+) -> Result<Response<ChatMember>, ApiCallError> {
+    let response: Response<ChatMember> =
+        Request::patch(&endpoint(&format!("/chat/{}/member/{}", chat_id, user_id)))
+            .credentials(web_sys::RequestCredentials::Include)
+            .json(&permissions)
             .map_err(|e| ApiCallError {
                 message: e.to_string(),
             })?
@@ -341,10 +576,34 @@ pub async fn get_bots() -> Result<Response<Vec<User>>, ApiCallError> {
     Ok(response)
 }
 
-pub async fn new_bot(username: String) -> Result<Response<User>, ApiCallError> {
+pub async fn new_bot(new_bot_request: NewBotRequest) -> Result<Response<User>, ApiCallError> {
     let response = Request::post(&endpoint("/bot"))
         .credentials(web_sys::RequestCredentials::Include)
-        .json(&json!({ "username": username }))
+        .json(&new_bot_request)
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
+
+pub async fn patch_bot(
+    bot_id: i32,
+    patch_bot_request: PatchBotRequest,
+) -> Result<Response<User>, ApiCallError> {
+    let response = Request::patch(&endpoint(&format!("/bot/{}", bot_id)))
+        .credentials(web_sys::RequestCredentials::Include)
+        .json(&patch_bot_request)
         .map_err(|e| ApiCallError {
             message: e.to_string(),
         })?
@@ -411,4 +670,44 @@ pub async fn delete_chat_member(chat_id: i32, user_id: i32) -> Result<Response<(
         })?;
 
     Ok(response)
-}
\ No newline at end of file
+}
+
+pub async fn get_events() -> Result<Response<Vec<Event>>, ApiCallError> {
+    let response: Response<Vec<Event>> = Request::get(&endpoint("/events"))
+        .credentials(web_sys::RequestCredentials::Include)
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
+
+pub async fn read_events(
+    read_events_request: ReadEventsRequest,
+) -> Result<Response<ReadEventsResponseData>, ApiCallError> {
+    let response: Response<ReadEventsResponseData> = Request::delete(&endpoint("/events"))
+        .credentials(web_sys::RequestCredentials::Include)
+        .json(&read_events_request)
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .send()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|e| ApiCallError {
+            message: e.to_string(),
+        })?;
+
+    Ok(response)
+}
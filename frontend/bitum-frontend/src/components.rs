@@ -1,29 +1,61 @@
+pub mod add_to_chat_dropdown;
+pub mod auth_provider;
+pub mod avatar_cropper;
+pub mod bot_token_modal;
 pub mod chat_member;
 pub mod chat_member_add_modal;
+pub mod chat_organization;
+pub mod copy_button;
+pub mod delete_bot_modal;
+pub mod direct_message;
 pub mod error_message;
+pub mod events_provider;
 pub mod footer;
+pub mod form_field;
 pub mod header;
 pub mod login_or_redirect;
+pub mod member_permissions_modal;
 pub mod message;
 pub mod modal;
 pub mod new_bot_modal;
 pub mod new_chat_modal;
+pub mod notifications_provider;
+pub mod password_strength_meter;
 pub mod raw_html;
+pub mod text_message;
 pub mod toast_container;
 pub mod toast_message;
 pub mod toast_wrapper;
+pub mod typed_confirm_modal;
+pub mod unread_provider;
 
+pub use add_to_chat_dropdown::*;
+pub use auth_provider::*;
+pub use avatar_cropper::*;
+pub use bot_token_modal::*;
 pub use chat_member::*;
 pub use chat_member_add_modal::*;
+pub use chat_organization::*;
+pub use copy_button::*;
+pub use delete_bot_modal::*;
+pub use direct_message::*;
 pub use error_message::*;
+pub use events_provider::*;
 pub use footer::*;
+pub use form_field::*;
 pub use header::*;
 pub use login_or_redirect::*;
+pub use member_permissions_modal::*;
 pub use message::*;
 pub use modal::*;
 pub use new_bot_modal::*;
 pub use new_chat_modal::*;
+pub use notifications_provider::*;
+pub use password_strength_meter::*;
 pub use raw_html::*;
+pub use text_message::*;
 pub use toast_container::*;
 pub use toast_message::*;
 pub use toast_wrapper::*;
+pub use typed_confirm_modal::*;
+pub use unread_provider::*;
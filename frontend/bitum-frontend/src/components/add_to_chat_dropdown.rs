@@ -0,0 +1,219 @@
+use futures::future::{join, join_all};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::{
+    api::{add_chat_member, get_chat_member, get_chats, Chat, User},
+    components::{use_current_user, ChatName, ErrorMessage, TextMessage},
+    error_messages::{ApiAction, NOT_FOUND, SERVER_UNAVAILABLE},
+};
+
+/// Чаты, где у нас есть право добавлять участников и где пользователя ещё нет
+async fn load_addable_chats(self_id: i32, user_id: i32) -> Result<Vec<Chat>, String> {
+    let chats = match get_chats().await {
+        Ok(response) if response.ok => response.data.unwrap_or_default(),
+        Ok(_) => return Err("Не удалось получить список чатов".to_string()),
+        Err(_) => return Err(SERVER_UNAVAILABLE.to_string()),
+    };
+
+    let checks = join_all(chats.iter().map(|chat| {
+        join(
+            get_chat_member(chat.id, self_id),
+            get_chat_member(chat.id, user_id),
+        )
+    }))
+    .await;
+
+    let addable = chats
+        .into_iter()
+        .zip(checks)
+        .filter(|(_, (self_member, member))| {
+            let can_add = matches!(
+                self_member,
+                Ok(response) if response.data.as_ref().is_some_and(|member| member.permissions.can_add_members)
+            );
+            // Не участник только если сервер так и ответил, а не при любой ошибке
+            let is_not_member = matches!(
+                member,
+                Ok(response) if response.error.as_ref().map(|error| error.code) == Some(NOT_FOUND)
+            );
+
+            can_add && is_not_member
+        })
+        .map(|(chat, _)| chat)
+        .collect();
+
+    Ok(addable)
+}
+
+#[derive(PartialEq, Properties)]
+pub struct AddToChatDropdownProps {
+    pub user: User,
+    /// Вызывается после того, как пользователь добавлен в чат
+    #[prop_or_default]
+    pub on_added: Callback<Chat>,
+}
+
+/// Список чатов загружается при первом открытии, чтобы не делать лишних запросов для каждой карточки
+#[function_component]
+pub fn AddToChatDropdown(props: &AddToChatDropdownProps) -> Html {
+    let AddToChatDropdownProps { user, on_added } = props;
+
+    let self_id = use_current_user().map(|user| user.id);
+    let error_message_state = use_state(|| Option::<String>::None);
+    let success_message_state = use_state(|| Option::<String>::None);
+    let chats_state = use_state(|| Option::<Vec<Chat>>::None);
+    let is_loading = use_state(|| false);
+
+    {
+        let chats_state = chats_state.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                chats_state.set(None);
+            },
+            (self_id, user.id),
+        );
+    }
+
+    let can_add = self_id.is_some_and(|self_id| self_id != user.id);
+
+    let on_toggle = {
+        let error_message_state = error_message_state.clone();
+        let chats_state = chats_state.clone();
+        let is_loading = is_loading.clone();
+        let user_id = user.id;
+
+        Callback::from(move |_: MouseEvent| {
+            let Some(self_id) = self_id.filter(|_| chats_state.is_none() && !*is_loading) else {
+                return;
+            };
+
+            let error_message_state = error_message_state.clone();
+            let chats_state = chats_state.clone();
+            let is_loading = is_loading.clone();
+
+            is_loading.set(true);
+
+            spawn_local(async move {
+                match load_addable_chats(self_id, user_id).await {
+                    Ok(chats) => chats_state.set(Some(chats)),
+                    Err(err) => error_message_state.set(Some(err)),
+                }
+
+                is_loading.set(false);
+            });
+        })
+    };
+
+    let on_add = {
+        let error_message_state = error_message_state.clone();
+        let success_message_state = success_message_state.clone();
+        let chats_state = chats_state.clone();
+        let on_added = on_added.clone();
+        let user = user.clone();
+
+        Callback::from(move |chat: Chat| {
+            let error_message_state = error_message_state.clone();
+            let success_message_state = success_message_state.clone();
+            let chats_state = chats_state.clone();
+            let on_added = on_added.clone();
+            let user = user.clone();
+
+            spawn_local(async move {
+                match add_chat_member(chat.id, user.id).await {
+                    Ok(response) if response.ok => {
+                        let mut chats = (*chats_state).clone().unwrap_or_default();
+                        chats.retain(|addable_chat| addable_chat.id != chat.id);
+                        chats_state.set(Some(chats));
+
+                        success_message_state.set(Some(format!(
+                            "{} добавлен в чат «{}»",
+                            user.username, chat.name
+                        )));
+                        on_added.emit(chat);
+                    }
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::AddMember.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <>
+        <div class="dropdown">
+            <button
+                onclick={on_toggle}
+                type="button"
+                class="btn btn-outline-dark dropdown-toggle"
+                data-bs-toggle="dropdown"
+                aria-expanded="false"
+                disabled={!can_add}
+            >
+                <i class="bi bi-person-plus"></i>
+                {" Добавить в чат"}
+            </button>
+            <ul class="dropdown-menu">
+                if *is_loading {
+                    <li><span class="dropdown-item-text text-body-secondary">{"Загрузка..."}</span></li>
+                } else if chats_state.as_ref().is_some_and(|chats| chats.is_empty()) {
+                    <li>
+                        <span class="dropdown-item-text text-body-secondary">
+                            {"Нет чатов, куда вы можете добавить участников"}
+                        </span>
+                    </li>
+                }
+                { for chats_state.iter().flatten().map(|chat| {
+                    let on_add = on_add.clone();
+                    let chat = chat.clone();
+
+                    html! {
+                        <li>
+                            <button
+                                onclick={
+                                    let chat = chat.clone();
+                                    Callback::from(move |_: MouseEvent| on_add.emit(chat.clone()))
+                                }
+                                type="button"
+                                class="dropdown-item"
+                            >
+                                <ChatName chat={chat} />
+                            </button>
+                        </li>
+                    }
+                })}
+            </ul>
+        </div>
+
+        if let Some(message) = (*success_message_state).clone() {
+            <TextMessage
+                on_close={
+                    let success_message_state = success_message_state.clone();
+
+                    Callback::from(move |_| {
+                        success_message_state.set(None);
+                    })
+                }
+                value={message}
+                toast_classes={classes!("bg-success", "text-bg-primary")}
+            />
+        }
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
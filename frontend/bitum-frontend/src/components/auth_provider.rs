@@ -0,0 +1,159 @@
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::{
+    api::{get_current_user_info, User},
+    components::{ChatOrganizationProvider, EventsProvider, NotificationsProvider, UnreadProvider},
+    error_messages::{FORBIDDEN, UNAUTHORIZED},
+    session::{api_token, clear_session, is_token_expired},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuthState {
+    /// Ещё не знаем, вошёл ли пользователь
+    Loading,
+    Anonymous {
+        /// Из аккаунта выкинуло из-за истёкшего токена
+        session_expired: bool,
+    },
+    Authenticated(User),
+}
+
+impl AuthState {
+    pub fn user(&self) -> Option<&User> {
+        match self {
+            AuthState::Authenticated(user) => Some(user),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct AuthContext {
+    pub state: AuthState,
+    /// Заново запрашивает пользователя, например после входа
+    pub refresh: Callback<()>,
+    /// Обновляет пользователя после изменений в настройках
+    pub set_user: Callback<User>,
+    /// Удаляет сессию. `true`, если токен истёк сам
+    pub logout: Callback<bool>,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct AuthProviderProps {
+    pub children: Children,
+}
+
+#[function_component]
+pub fn AuthProvider(props: &AuthProviderProps) -> Html {
+    let AuthProviderProps { children } = props;
+
+    let auth_state = use_state(|| AuthState::Loading);
+    let refresh_generation = use_state(|| 0_u32);
+
+    {
+        let auth_state = auth_state.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                if is_token_expired() {
+                    clear_session();
+                    auth_state.set(AuthState::Anonymous {
+                        session_expired: true,
+                    });
+                    return;
+                }
+
+                spawn_local(async move {
+                    let state = match get_current_user_info().await {
+                        Ok(response) if response.ok => response
+                            .data
+                            .map(AuthState::Authenticated)
+                            .unwrap_or(AuthState::Anonymous {
+                                session_expired: false,
+                            }),
+                        Ok(response)
+                            if api_token().is_some()
+                                && response.error.as_ref().is_some_and(|error| {
+                                    error.code == UNAUTHORIZED || error.code == FORBIDDEN
+                                }) =>
+                        {
+                            // Токен есть, но сервер его больше не принимает.
+                            // При других ошибках сессию не трогаем, сервер мог просто упасть
+                            clear_session();
+                            AuthState::Anonymous {
+                                session_expired: true,
+                            }
+                        }
+                        _ => AuthState::Anonymous {
+                            session_expired: false,
+                        },
+                    };
+
+                    auth_state.set(state);
+                });
+            },
+            *refresh_generation,
+        );
+    }
+
+    let context = AuthContext {
+        state: (*auth_state).clone(),
+        refresh: {
+            let auth_state = auth_state.clone();
+            let refresh_generation = refresh_generation.clone();
+
+            Callback::from(move |_: ()| {
+                auth_state.set(AuthState::Loading);
+                refresh_generation.set(*refresh_generation + 1);
+            })
+        },
+        set_user: {
+            let auth_state = auth_state.clone();
+
+            Callback::from(move |user: User| auth_state.set(AuthState::Authenticated(user)))
+        },
+        logout: {
+            let auth_state = auth_state.clone();
+
+            Callback::from(move |session_expired: bool| {
+                clear_session();
+                auth_state.set(AuthState::Anonymous { session_expired });
+            })
+        },
+    };
+
+    let user_id = context.state.user().map(|user| user.id);
+    let on_unauthorized = {
+        let logout = context.logout.clone();
+
+        Callback::from(move |_: ()| logout.emit(true))
+    };
+
+    // key пересоздаёт провайдеры при смене пользователя, чтобы не протащить чужое состояние
+    html! {
+        <ContextProvider<AuthContext> {context}>
+            <EventsProvider key={user_id.map(|id| id.to_string()).unwrap_or_default()} enabled={user_id.is_some()} {on_unauthorized}>
+                <ChatOrganizationProvider>
+                    <NotificationsProvider>
+                        <UnreadProvider>
+                            { for children.iter() }
+                        </UnreadProvider>
+                    </NotificationsProvider>
+                </ChatOrganizationProvider>
+            </EventsProvider>
+        </ContextProvider<AuthContext>>
+    }
+}
+
+/// Контекст авторизации. Есть везде внутри `AuthProvider`
+#[hook]
+pub fn use_auth() -> AuthContext {
+    use_context::<AuthContext>().expect("use_auth must be used inside AuthProvider")
+}
+
+/// Текущий пользователь, если он вошёл
+#[hook]
+pub fn use_current_user() -> Option<User> {
+    use_context::<AuthContext>().and_then(|auth| auth.state.user().cloned())
+}
@@ -0,0 +1,186 @@
+use gloo::file::{File, ObjectUrl};
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement, HtmlInputElement};
+use yew::prelude::*;
+
+use crate::constants::AVATAR_SIZE;
+
+/// Квадратная область картинки, которая станет аватаром
+#[derive(Clone, Copy, PartialEq)]
+struct Crop {
+    /// Увеличение в процентах, от 100 до 300
+    zoom: i32,
+    /// Смещение по осям в процентах, от -100 до 100
+    offset_x: i32,
+    offset_y: i32,
+}
+
+impl Default for Crop {
+    fn default() -> Self {
+        Self {
+            zoom: 100,
+            offset_x: 0,
+            offset_y: 0,
+        }
+    }
+}
+
+fn draw_crop(canvas: &HtmlCanvasElement, image: &HtmlImageElement, crop: Crop) -> Option<()> {
+    let context = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()?
+        .dyn_into::<CanvasRenderingContext2d>()
+        .ok()?;
+
+    let (width, height) = (image.natural_width() as f64, image.natural_height() as f64);
+    let side = width.min(height) * 100.0 / crop.zoom as f64;
+    let source_x = (width - side) * (crop.offset_x as f64 + 100.0) / 200.0;
+    let source_y = (height - side) * (crop.offset_y as f64 + 100.0) / 200.0;
+
+    context.clear_rect(0.0, 0.0, AVATAR_SIZE as f64, AVATAR_SIZE as f64);
+    context
+        .draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+            image,
+            source_x,
+            source_y,
+            side,
+            side,
+            0.0,
+            0.0,
+            AVATAR_SIZE as f64,
+            AVATAR_SIZE as f64,
+        )
+        .ok()
+}
+
+/// Содержимое canvas в виде png файла
+fn canvas_to_file(canvas: &HtmlCanvasElement) -> Option<web_sys::File> {
+    let data_url = canvas.to_data_url_with_type("image/png").ok()?;
+    let (_, base64) = data_url.split_once(',')?;
+    let binary = gloo_utils::window().atob(base64).ok()?;
+    let bytes: Vec<u8> = binary.chars().map(|char| char as u8).collect();
+
+    let file = File::new_with_options("avatar.png", bytes.as_slice(), Some("image/png"), None);
+    Some(AsRef::<web_sys::File>::as_ref(&file).clone())
+}
+
+#[derive(PartialEq, Properties)]
+pub struct AvatarCropperProps {
+    pub on_save: Callback<web_sys::File>,
+    #[prop_or_default]
+    pub disabled: bool,
+}
+
+#[function_component]
+pub fn AvatarCropper(props: &AvatarCropperProps) -> Html {
+    let AvatarCropperProps { on_save, disabled } = props;
+
+    let image_url = use_state(|| Option::<ObjectUrl>::None);
+    // Меняется при загрузке новой картинки, чтобы перерисовать canvas
+    let image_generation = use_state(|| 0_u32);
+    let crop_state = use_state(Crop::default);
+    let image_node = use_node_ref();
+    let canvas_node = use_node_ref();
+
+    {
+        let image_node = image_node.clone();
+        let canvas_node = canvas_node.clone();
+
+        use_effect_with_deps(
+            move |(crop, _)| {
+                if let (Some(image), Some(canvas)) = (
+                    image_node.cast::<HtmlImageElement>(),
+                    canvas_node.cast::<HtmlCanvasElement>(),
+                ) {
+                    if image.complete() && image.natural_width() > 0 {
+                        draw_crop(&canvas, &image, *crop);
+                    }
+                }
+            },
+            (*crop_state, *image_generation),
+        );
+    }
+
+    let on_file_change = {
+        let image_url = image_url.clone();
+        let crop_state = crop_state.clone();
+
+        Callback::from(move |event: Event| {
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                crop_state.set(Crop::default());
+                image_url.set(Some(ObjectUrl::from(File::from(file))));
+            }
+        })
+    };
+
+    let on_image_load = {
+        let image_generation = image_generation.clone();
+
+        Callback::from(move |_: Event| image_generation.set(*image_generation + 1))
+    };
+
+    let slider = |label: &'static str,
+                  min: i32,
+                  max: i32,
+                  get: fn(&Crop) -> i32,
+                  set: fn(&mut Crop, i32)| {
+        let crop_state = crop_state.clone();
+        let value = get(&crop_state);
+
+        let oninput = Callback::from(move |event: InputEvent| {
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+            let mut crop = *crop_state;
+            set(&mut crop, input.value().parse().unwrap_or_default());
+            crop_state.set(crop);
+        });
+
+        html! {
+            <label class="form-label w-100">
+                {label}
+                <input {oninput} type="range" class="form-range" min={min.to_string()} max={max.to_string()} value={value.to_string()} />
+            </label>
+        }
+    };
+
+    let on_save_click = {
+        let canvas_node = canvas_node.clone();
+        let on_save = on_save.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            if let Some(file) = canvas_node
+                .cast::<HtmlCanvasElement>()
+                .and_then(|canvas| canvas_to_file(&canvas))
+            {
+                on_save.emit(file);
+            }
+        })
+    };
+
+    html! {
+        <div class="avatar-cropper">
+            <input onchange={on_file_change} type="file" accept="image/*" class="form-control mb-3" />
+            if let Some(url) = (*image_url).clone() {
+                <img ref={image_node} onload={on_image_load} src={url.to_string()} class="d-none" alt="source" />
+                <div class="d-flex flex-wrap gap-4 align-items-start">
+                    <canvas
+                        ref={canvas_node}
+                        width={AVATAR_SIZE.to_string()}
+                        height={AVATAR_SIZE.to_string()}
+                        class="rounded-4 border avatar-cropper-preview"
+                    ></canvas>
+                    <div class="flex-grow-1">
+                        { slider("Масштаб", 100, 300, |crop| crop.zoom, |crop, value| crop.zoom = value) }
+                        { slider("По горизонтали", -100, 100, |crop| crop.offset_x, |crop, value| crop.offset_x = value) }
+                        { slider("По вертикали", -100, 100, |crop| crop.offset_y, |crop, value| crop.offset_y = value) }
+                        <button onclick={on_save_click} disabled={*disabled} type="button" class="btn btn-dark">
+                            {"Сохранить аватар"}
+                        </button>
+                    </div>
+                </div>
+            }
+        </div>
+    }
+}
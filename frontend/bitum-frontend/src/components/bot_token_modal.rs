@@ -0,0 +1,235 @@
+use gloo_timers::callback::Timeout;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::{
+    api::{endpoint, get_bot_token, User},
+    components::{CopyButton, ErrorMessage, Modal},
+    constants::{API_TOKEN_COOKIE_NAME, BOT_TOKEN_VISIBLE_MILLIS},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+};
+
+const TOKEN_ENV_VAR: &str = "BITUM_BOT_TOKEN";
+const MASKED_TOKEN: &str = "••••••••••••••••";
+
+#[derive(PartialEq, Properties)]
+pub struct BotTokenSnippetProps {
+    pub label: AttrValue,
+    /// Что показываем
+    pub shown: String,
+    /// Что копируем
+    pub copied: String,
+}
+
+#[function_component]
+fn BotTokenSnippet(props: &BotTokenSnippetProps) -> Html {
+    let BotTokenSnippetProps {
+        label,
+        shown,
+        copied,
+    } = props;
+
+    html! {
+        <div class="mt-3">
+            <div class="form-text mb-1">{label.clone()}</div>
+            <div class="d-flex align-items-start gap-2">
+                <pre class="flex-grow-1 bg-body-tertiary rounded p-2 mb-0 text-wrap text-break"><code>{shown.clone()}</code></pre>
+                <CopyButton text={copied.clone()} class={classes!("btn", "btn-sm", "btn-outline-secondary")} />
+            </div>
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct BotTokenModalButtonProps {
+    pub bot: User,
+    pub children: Children,
+}
+
+/// Выпускает новый токен бота после подтверждения и ненадолго показывает его в окне
+#[function_component]
+pub fn BotTokenModalButton(props: &BotTokenModalButtonProps) -> Html {
+    let BotTokenModalButtonProps { bot, children } = props;
+
+    let error_message_state = use_state(|| Option::<String>::None);
+    let confirm_modal_visible = use_state(|| false);
+    let token_state = use_state(|| Option::<String>::None);
+    let revealed = use_state(|| false);
+
+    // Токен не должен висеть на экране: через минуту забываем его и закрываем окно
+    {
+        let token = (*token_state).clone();
+        let token_state = token_state.clone();
+        let revealed = revealed.clone();
+
+        use_effect_with_deps(
+            move |token| {
+                let timeout = token.as_ref().map(|_| {
+                    Timeout::new(BOT_TOKEN_VISIBLE_MILLIS, move || {
+                        token_state.set(None);
+                        revealed.set(false);
+                    })
+                });
+
+                move || drop(timeout)
+            },
+            token,
+        );
+    }
+
+    let on_open = {
+        let confirm_modal_visible = confirm_modal_visible.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            confirm_modal_visible.set(true);
+        })
+    };
+
+    let on_confirm_close = {
+        let confirm_modal_visible = confirm_modal_visible.clone();
+
+        Callback::from(move |_: ()| {
+            confirm_modal_visible.set(false);
+        })
+    };
+
+    let on_confirm = {
+        let token_state = token_state.clone();
+        let bot_id = bot.id;
+        let error_message_state = error_message_state.clone();
+
+        Callback::from(move |_: ()| {
+            let error_message_state = error_message_state.clone();
+            let token_state = token_state.clone();
+
+            spawn_local(async move {
+                match get_bot_token(bot_id).await {
+                    Ok(response) if response.ok => {
+                        token_state.set(response.data.map(|data| data.token));
+                    }
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::LoadBotToken.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+            });
+        })
+    };
+
+    let on_token_close = {
+        let token_state = token_state.clone();
+        let revealed = revealed.clone();
+
+        Callback::from(move |_: ()| {
+            token_state.set(None);
+            revealed.set(false);
+        })
+    };
+
+    let on_reveal_click = {
+        let revealed = revealed.clone();
+
+        Callback::from(move |_: MouseEvent| revealed.set(!*revealed))
+    };
+
+    let token = (*token_state).clone().unwrap_or_default();
+    let shown_token = if *revealed {
+        token.as_str()
+    } else {
+        MASKED_TOKEN
+    };
+    let events_url = format!(
+        "{}{}",
+        gloo_utils::window().location().origin().unwrap_or_default(),
+        endpoint("/events")
+    );
+    let curl_snippet = format!(
+        "curl -H \"Cookie: {}=${}\" {}",
+        API_TOKEN_COOKIE_NAME, TOKEN_ENV_VAR, events_url
+    );
+
+    html! {
+        <>
+        <span onclick={on_open} title="Токен бота">
+            { for children.iter() }
+        </span>
+        <Modal
+            modal_id={"bot-token-confirm-modal".to_string()}
+            is_visible={*confirm_modal_visible}
+            on_ok={on_confirm}
+            on_cancel={Callback::from(|_| {})}
+            on_close={on_confirm_close}
+        >
+            <div class="modal-header">
+                <h1 class="modal-title fs-5">{"Новый токен "} {bot.username.clone()}</h1>
+            </div>
+            <div class="modal-body">
+                <p>{"Посмотреть текущий токен нельзя, можно только выпустить новый."}</p>
+                <p class="mb-0">
+                    {"Старый токен сразу перестанет работать: если бот уже запущен, "}
+                    {"ему нужно будет передать новый."}
+                </p>
+            </div>
+        </Modal>
+        <Modal
+            modal_id={"bot-token-modal".to_string()}
+            is_visible={token_state.is_some()}
+            on_ok={Callback::from(|_| {})}
+            on_cancel={Callback::from(|_| {})}
+            on_close={on_token_close}
+        >
+            <div class="modal-header">
+                <h1 class="modal-title fs-5">{"Токен "} {bot.username.clone()}</h1>
+            </div>
+            <div class="modal-body">
+                <div class="input-group flex-nowrap">
+                    <span class="input-group-text">
+                        <i class="bi bi-key fs-4"></i>
+                    </span>
+                    <input
+                        class="form-control font-monospace"
+                        type={if *revealed { "text" } else { "password" }}
+                        value={token.clone()}
+                        readonly=true
+                    />
+                    <button
+                        onclick={on_reveal_click}
+                        type="button"
+                        class="btn btn-outline-secondary"
+                        title={if *revealed { "Скрыть" } else { "Показать" }}
+                    >
+                        <i class={if *revealed { "bi bi-eye-slash" } else { "bi bi-eye" }}></i>
+                    </button>
+                    <CopyButton text={token.clone()} />
+                </div>
+                <div class="form-text">
+                    {"Сохраните токен сейчас: окно закроется само через минуту."}
+                </div>
+
+                <BotTokenSnippet
+                    label="Переменная окружения"
+                    shown={format!("export {}={}", TOKEN_ENV_VAR, shown_token)}
+                    copied={format!("export {}={}", TOKEN_ENV_VAR, token)}
+                />
+                <BotTokenSnippet
+                    label="Проверка: новые события бота"
+                    shown={curl_snippet.clone()}
+                    copied={curl_snippet}
+                />
+            </div>
+        </Modal>
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
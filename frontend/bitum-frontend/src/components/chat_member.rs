@@ -1,21 +1,32 @@
 use bitum_frontend::get_random_color_image_url;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
+use yew_router::prelude::Link;
 
 use crate::{
     api::{delete_chat_member, ChatMember},
-    components::{ErrorMessage, LoggedUserInfo},
+    components::{
+        use_current_user, DirectMessageButton, ErrorMessage, MemberPermissionBadges,
+        MemberPermissionsModalButton,
+    },
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::Route,
 };
 
 #[derive(PartialEq, Properties)]
 pub struct ChatMemberButtonProps {
     pub member: ChatMember,
+    /// Участник чата, от имени которого открыт чат
+    pub self_member: Option<ChatMember>,
 }
 
 #[function_component]
 pub fn ChatMemberButton(props: &ChatMemberButtonProps) -> Html {
-    let ChatMemberButtonProps { member } = props;
-    let user = use_context::<LoggedUserInfo>().unwrap().user.unwrap();
+    let ChatMemberButtonProps {
+        member,
+        self_member,
+    } = props;
+    let self_id = use_current_user().map(|user| user.id);
     let error_message_state = use_state(|| Option::<String>::None);
 
     let on_delete_click = {
@@ -30,15 +41,28 @@ pub fn ChatMemberButton(props: &ChatMemberButtonProps) -> Html {
 
                 if let Ok(response) = response {
                     if !response.ok {
-                        error_message_state.set(Some("Не удалось удалить бота".to_string()));
+                        error_message_state.set(Some(
+                            ApiAction::KickMember.error_message(response.error.as_ref()),
+                        ));
                     }
                 } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
                 }
             });
         })
     };
 
+    let is_owner = member.user.id == member.chat.owner.id;
+    let is_self = Some(member.user.id) == self_id;
+    let self_is_owner = Some(member.chat.owner.id) == self_id;
+    let can_kick = self_member
+        .as_ref()
+        .map(|self_member| self_member.permissions.can_kick_members)
+        .unwrap_or(false)
+        && !is_owner
+        && !is_self;
+    let can_edit_permissions = self_is_owner && !is_owner;
+
     html! {
         <>
         <div class="d-flex chat-member-button">
@@ -55,11 +79,30 @@ pub fn ChatMemberButton(props: &ChatMemberButtonProps) -> Html {
                         <span class="badge rounded-pill text-bg-dark">{"бот"}</span>
                         {" "}
                     }
-                    { member.user.username.clone() }
+                    if is_owner {
+                        <i class="bi bi-star-fill text-warning" title="Владелец чата"></i>
+                        {" "}
+                    }
+                    <Link<Route> classes="text-reset text-decoration-none" to={Route::UserProfile { username: member.user.username.clone() }}>
+                        { member.user.username.clone() }
+                    </Link<Route>>
+                    <MemberPermissionBadges permissions={member.permissions.clone()} />
+                </div>
+                <div class="d-flex">
+                    if !is_self {
+                        <DirectMessageButton user={member.user.clone()}>
+                            <i role="button" class="bi bi-chat-dots fs-4 p-1 info-hover"></i>
+                        </DirectMessageButton>
+                    }
+                    if can_edit_permissions {
+                        <MemberPermissionsModalButton member={member.clone()}>
+                            <i role="button" class="bi bi-sliders fs-4 p-1 info-hover"></i>
+                        </MemberPermissionsModalButton>
+                    }
+                    if can_kick {
+                        <i onclick={on_delete_click} role="button" class="bi bi-trash2 fs-3 p-3 danger-hover"></i>
+                    }
                 </div>
-                if member.chat.owner.id == user.id {
-                    <i onclick={on_delete_click} role="button" class="bi bi-trash2 fs-3 p-3 danger-hover"></i>
-                }
             </div>
         </div>
         if let Some(err) = (*error_message_state).clone() {
@@ -1,10 +1,18 @@
+use std::collections::{BTreeMap, HashSet};
+
+use bitum_frontend::get_random_color_image_url;
+use gloo_timers::future::TimeoutFuture;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
 use crate::{
-    api::{add_chat_member, search_bots, search_users, User},
-    components::{ErrorMessage, Modal},
+    api::{
+        add_chat_member, get_chat_members, search_bots, search_users, ApiCallError, Response, User,
+    },
+    components::{DirectMessageButton, ErrorMessage, Modal},
+    constants::{MEMBER_SEARCH_PAGE_SIZE, SEARCH_DEBOUNCE_MILLIS},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
 };
 
 #[derive(PartialEq, Properties)]
@@ -13,20 +21,153 @@ pub struct AddChatMemberModalButtonProps {
     pub children: Children,
 }
 
+/// Позиция в выдаче одного источника: пользователей или ботов
+#[derive(Clone, Copy, PartialEq)]
+struct SearchCursor {
+    offset: i32,
+    has_more: bool,
+}
+
+impl Default for SearchCursor {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            has_more: true,
+        }
+    }
+}
+
+impl SearchCursor {
+    fn advance(&self, received: usize) -> Self {
+        Self {
+            offset: self.offset + received as i32,
+            has_more: received as i32 == MEMBER_SEARCH_PAGE_SIZE,
+        }
+    }
+}
+
+/// Результаты поиска по пользователям и ботам
+#[derive(Clone, Default, PartialEq)]
+struct SearchResults {
+    users: Vec<User>,
+    user_cursor: SearchCursor,
+    bot_cursor: SearchCursor,
+}
+
+impl SearchResults {
+    fn is_first_page(&self) -> bool {
+        self.user_cursor.offset == 0 && self.bot_cursor.offset == 0
+    }
+
+    fn has_more(&self) -> bool {
+        self.user_cursor.has_more || self.bot_cursor.has_more
+    }
+
+    /// Добавляет новую страницу, убирая повторы
+    fn extend(&self, page: SearchPage) -> Self {
+        let mut users = self.users.clone();
+        let mut known_ids: HashSet<i32> = users.iter().map(|user| user.id).collect();
+        let user_cursor = self.user_cursor.advance(page.users.len());
+        let bot_cursor = self.bot_cursor.advance(page.bots.len());
+
+        users.extend(
+            page.users
+                .into_iter()
+                .chain(page.bots)
+                .filter(|user| known_ids.insert(user.id)),
+        );
+
+        Self {
+            users,
+            user_cursor,
+            bot_cursor,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SearchPage {
+    users: Vec<User>,
+    bots: Vec<User>,
+}
+
+/// Следующая страница поиска. У пользователей и ботов свои сдвиги,
+/// источник, в котором результаты кончились, больше не запрашивается
+async fn search_page(username: String, results: &SearchResults) -> Result<SearchPage, String> {
+    let mut page = SearchPage::default();
+
+    if results.user_cursor.has_more {
+        page.users = search_source(
+            search_users(
+                username.clone(),
+                MEMBER_SEARCH_PAGE_SIZE,
+                results.user_cursor.offset,
+            )
+            .await,
+        )?;
+    }
+
+    if results.bot_cursor.has_more {
+        page.bots = search_source(
+            search_bots(username, MEMBER_SEARCH_PAGE_SIZE, results.bot_cursor.offset).await,
+        )?;
+    }
+
+    Ok(page)
+}
+
+fn search_source(response: Result<Response<Vec<User>>, ApiCallError>) -> Result<Vec<User>, String> {
+    match response {
+        Ok(response) if response.ok => Ok(response.data.unwrap_or_default()),
+        Ok(_) => Err("Не удалось получить информацию о пользователях".to_string()),
+        Err(_) => Err(SERVER_UNAVAILABLE.to_string()),
+    }
+}
+
 #[function_component]
 pub fn AddChatMemberModalButton(props: &AddChatMemberModalButtonProps) -> Html {
     let AddChatMemberModalButtonProps { chat_id, children } = props;
 
     let modal_visible = use_state(|| false);
     let error_message_state = use_state(|| Option::<String>::None);
-    let found_users_state = use_state(|| Vec::<User>::new());
-    let input_node_ref = use_node_ref();
+    let query_state = use_state(String::new);
+    let results_state = use_state(SearchResults::default);
+    let is_loading = use_state(|| false);
+    let member_ids = use_state(HashSet::<i32>::new);
+    let selected_state = use_state(BTreeMap::<i32, User>::new);
+    // Номер последнего запроса: ответы на устаревшие запросы отбрасываются
+    let search_generation = use_mut_ref(|| 0_u64);
 
     let on_button_click = {
         let modal_visible = modal_visible.clone();
+        let error_message_state = error_message_state.clone();
+        let member_ids = member_ids.clone();
+        let selected_state = selected_state.clone();
+        let chat_id = *chat_id;
 
         Callback::from(move |_: MouseEvent| {
+            let error_message_state = error_message_state.clone();
+            let member_ids = member_ids.clone();
+
+            selected_state.set(BTreeMap::new());
             modal_visible.set(true);
+
+            spawn_local(async move {
+                match get_chat_members(chat_id).await {
+                    Ok(response) if response.ok => member_ids.set(
+                        response
+                            .data
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|member| member.user.id)
+                            .collect(),
+                    ),
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::LoadMembers.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+            });
         })
     };
 
@@ -38,84 +179,104 @@ pub fn AddChatMemberModalButton(props: &AddChatMemberModalButtonProps) -> Html {
         })
     };
 
-    let member_button_callback = {
-        let chat_id = chat_id.clone();
+    let load_page = {
         let error_message_state = error_message_state.clone();
-        let on_modal_close = on_modal_close.clone();
+        let results_state = results_state.clone();
+        let is_loading = is_loading.clone();
+        let search_generation = search_generation.clone();
 
-        Callback::from(move |user_id: i32| {
-            let chat_id = chat_id.clone();
+        Callback::from(move |(username, results): (String, SearchResults)| {
             let error_message_state = error_message_state.clone();
-            let on_modal_close = on_modal_close.clone();
+            let results_state = results_state.clone();
+            let is_loading = is_loading.clone();
+            let search_generation = search_generation.clone();
+
+            *search_generation.borrow_mut() += 1;
+            let generation = *search_generation.borrow();
+
+            if username.is_empty() {
+                results_state.set(SearchResults::default());
+                is_loading.set(false);
+                return;
+            }
+
+            is_loading.set(true);
 
             spawn_local(async move {
-                let response = add_chat_member(chat_id, user_id).await;
-
-                if let Ok(response) = response {
-                    if response.ok {
-                        on_modal_close.emit(());
-                    } else {
-                        match response.error.unwrap().code {
-                            409 => error_message_state.set(Some(
-                                "Этот пользователь уже состоит в этом чате".to_string(),
-                            )),
-                            _ => error_message_state
-                                .set(Some("Не удалось добавить пользователя в чат".to_string())),
-                        }
+                // Первую страницу ищем только когда пользователь перестал печатать
+                if results.is_first_page() {
+                    TimeoutFuture::new(SEARCH_DEBOUNCE_MILLIS).await;
+
+                    if *search_generation.borrow() != generation {
+                        return;
                     }
-                } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
                 }
+
+                let page = search_page(username, &results).await;
+
+                if *search_generation.borrow() != generation {
+                    return;
+                }
+
+                match page {
+                    Ok(page) => results_state.set(results.extend(page)),
+                    Err(err) => error_message_state.set(Some(err)),
+                }
+
+                is_loading.set(false);
             });
         })
     };
 
     let on_input_change = {
-        let input_node_ref = input_node_ref.clone();
-        let error_message_state = error_message_state.clone();
-        let found_users_state = found_users_state.clone();
+        let query_state = query_state.clone();
+        let load_page = load_page.clone();
 
-        Callback::from(move |_: InputEvent| {
-            let username = input_node_ref.cast::<HtmlInputElement>().unwrap().value();
-            let error_message_state = error_message_state.clone();
-            let found_users_state = found_users_state.clone();
+        Callback::from(move |event: InputEvent| {
+            let username = event.target_unchecked_into::<HtmlInputElement>().value();
 
-            if username.is_empty() {
-                return;
-            }
+            query_state.set(username.clone());
+            load_page.emit((username.trim().to_string(), SearchResults::default()));
+        })
+    };
 
-            spawn_local(async move {
-                let response = search_users(username.clone(), 40, 0).await;
+    let on_load_more = {
+        let query_state = query_state.clone();
+        let results_state = results_state.clone();
+        let load_page = load_page.clone();
 
-                let mut new_user_list = Vec::new();
+        Callback::from(move |_: MouseEvent| {
+            load_page.emit((query_state.trim().to_string(), (*results_state).clone()));
+        })
+    };
 
-                if let Ok(response) = response {
-                    if response.ok {
-                        new_user_list = response.data.unwrap();
-                    } else {
-                        error_message_state.set(Some(
-                            "Не удалось получить информацию о пользователях".to_string(),
-                        ));
-                    }
-                } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
-                }
+    let on_ok = {
+        let chat_id = *chat_id;
+        let error_message_state = error_message_state.clone();
+        let selected_state = selected_state.clone();
 
-                let response = search_bots(username, 40, 0).await;
+        Callback::from(move |_: ()| {
+            let error_message_state = error_message_state.clone();
+            let selected: Vec<User> = selected_state.values().cloned().collect();
 
-                if let Ok(response) = response {
-                    if response.ok {
-                        new_user_list.extend(response.data.unwrap());
-                    } else {
-                        error_message_state.set(Some(
-                            "Не удалось получить информацию о пользователях".to_string(),
-                        ));
+            spawn_local(async move {
+                let mut failed = Vec::new();
+
+                for user in selected {
+                    match add_chat_member(chat_id, user.id).await {
+                        Ok(response) if response.ok => {}
+                        Ok(response) => failed.push(format!(
+                            "{}: {}",
+                            user.username,
+                            ApiAction::AddMember.error_message(response.error.as_ref())
+                        )),
+                        Err(_) => failed.push(format!("{}: {}", user.username, SERVER_UNAVAILABLE)),
                     }
-                } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
                 }
 
-                found_users_state.set(new_user_list);
+                if !failed.is_empty() {
+                    error_message_state.set(Some(failed.join("; ")));
+                }
             });
         })
     };
@@ -126,54 +287,134 @@ pub fn AddChatMemberModalButton(props: &AddChatMemberModalButtonProps) -> Html {
             { for children.iter() }
         </span>
 
-        <Modal modal_id={"add-chat-member-modal"} is_visible={*modal_visible} on_close={on_modal_close} on_cancel={Callback::from(move |_| {})} on_ok={ Callback::from(move |_: ()| {}) } >
+        <Modal
+            modal_id={"add-chat-member-modal"}
+            is_visible={*modal_visible}
+            on_close={on_modal_close.clone()}
+            on_cancel={Callback::from(move |_| {})}
+            on_ok={on_ok}
+            ok_disabled={selected_state.is_empty()}
+        >
             <div class="modal-header">
-                <h1 class="modal-title fs-5">{"Добавить пользователя в чат"}</h1>
+                <h1 class="modal-title fs-5">{"Добавить пользователей в чат"}</h1>
             </div>
             <div class="modal-body">
                 <div class="input-group p-1">
                     <span class="input-group-text">{"Имя пользователя"}</span>
-                    <input oninput={on_input_change} ref={input_node_ref} type="text" class="form-control" aria-label="username" required=true />
+                    <input oninput={on_input_change} value={(*query_state).clone()} type="text" class="form-control" aria-label="username" required=true />
                 </div>
-                <div class="d-block add-member-list-modal">
-                    { for (*found_users_state).iter().map(|user| html! {
-                        <div
-                            onclick={
-                                let user = user.clone();
-                                let member_button_callback = member_button_callback.clone();
-                                Callback::from(move |_| {
-                                    member_button_callback.clone().emit(user.id);
-                                })
+                if !selected_state.is_empty() {
+                    <div class="d-flex flex-wrap gap-1 p-1">
+                        { for selected_state.values().map(|user| {
+                            let selected_state = selected_state.clone();
+                            let user_id = user.id;
+
+                            html! {
+                                <span class="badge rounded-pill text-bg-dark">
+                                    { user.username.clone() }
+                                    {" "}
+                                    <i
+                                        onclick={Callback::from(move |_: MouseEvent| {
+                                            let mut selected = (*selected_state).clone();
+                                            selected.remove(&user_id);
+                                            selected_state.set(selected);
+                                        })}
+                                        role="button"
+                                        class="bi bi-x-lg"
+                                    ></i>
+                                </span>
                             }
-                            role="button"
-                            class="d-flex chat-member-button grow-on-hover"
+                        })}
+                    </div>
+                }
+                <div class="d-block add-member-list-modal">
+                    { for results_state.users.iter().map(|user| {
+                        let is_member = member_ids.contains(&user.id);
+                        let is_selected = selected_state.contains_key(&user.id);
+
+                        let onclick = {
+                            let selected_state = selected_state.clone();
+                            let user = user.clone();
+
+                            Callback::from(move |_: MouseEvent| {
+                                if is_member {
+                                    return;
+                                }
+
+                                let mut selected = (*selected_state).clone();
+
+                                if selected.remove(&user.id).is_none() {
+                                    selected.insert(user.id, user.clone());
+                                }
+
+                                selected_state.set(selected);
+                            })
+                        };
+
+                        html! {
+                            <div
+                                {onclick}
+                                role={if is_member { "" } else { "button" }}
+                                class={classes!("d-flex", "chat-member-button", (!is_member).then_some("grow-on-hover"))}
                             >
-                                <div class="rounded-2 text-overflow-ellipsis d-flex border bg-white flex-grow-1 align-items-center">
-                                <div class="p-3 fs-5 text-dark fw-normal">
-                                    if user.is_bot {
-                                        <span class="badge rounded-pill text-bg-dark">{"бот"}</span>
-                                        {" "}
+                                <img class="rounded-start-2 border object-fit-scale" height=60px width=60px src={
+                                    if user.icon.is_some() {
+                                        format!("/api/files/{}", user.icon.clone().unwrap_or("null.png".to_string()))
+                                    } else {
+                                        get_random_color_image_url(user.username.clone(), 60, 60)
                                     }
-                                    { user.username.clone() }
+                                } alt="icon"/>
+                                <div class={classes!("rounded-end-2", "text-overflow-ellipsis", "d-flex", "border", "border-start-0", "flex-grow-1", "align-items-center", if is_selected { "bg-success-subtle" } else { "bg-white" })}>
+                                    <div class="p-3 fs-5 text-dark fw-normal">
+                                        if is_selected {
+                                            <i class="bi bi-check-circle-fill text-success"></i>
+                                            {" "}
+                                        }
+                                        if user.is_bot {
+                                            <span class="badge rounded-pill text-bg-dark">{"бот"}</span>
+                                            {" "}
+                                        }
+                                        { user.username.clone() }
+                                        if is_member {
+                                            {" "}
+                                            <span class="badge rounded-pill text-bg-secondary">{"уже в чате"}</span>
+                                        }
+                                    </div>
+                                    <div class="ms-auto">
+                                        <DirectMessageButton user={user.clone()} on_open={on_modal_close.clone()}>
+                                            <i role="button" class="bi bi-chat-dots fs-4 p-3 info-hover"></i>
+                                        </DirectMessageButton>
+                                    </div>
                                 </div>
                             </div>
-                        </div>
+                        }
                     })}
+                    if *is_loading {
+                        <div class="text-center p-2">
+                            <div class="spinner-border spinner-border-sm" role="status"></div>
+                        </div>
+                    } else if !results_state.users.is_empty() && results_state.has_more() {
+                        <button onclick={on_load_more} type="button" class="btn btn-outline-dark w-100">
+                            {"Показать ещё"}
+                        </button>
+                    } else if results_state.users.is_empty() && !query_state.trim().is_empty() {
+                        <div class="text-center text-body-secondary p-2">{"Никого не нашлось"}</div>
+                    }
                 </div>
             </div>
         </Modal>
         if let Some(err) = (*error_message_state).clone() {
-                <ErrorMessage
-                    on_close={
-                        let error_message_state = error_message_state.clone();
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
 
-                        Callback::from(move |_| {
-                            error_message_state.set(None);
-                        })
-                    }
-                    value={err}
-                />
-            }
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
         </>
     }
 }
@@ -0,0 +1,179 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::{
+    components::{use_current_user, ChatNotificationMode},
+    constants::CHAT_ORGANIZATION_STORAGE_KEY,
+    session::{load_account_data, save_account_data},
+};
+
+/// Текущая версия схемы в localStorage и в экспортируемом JSON
+const CHAT_ORGANIZATION_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChatFolder {
+    pub name: String,
+    pub chat_ids: BTreeSet<i32>,
+}
+
+/// Закреплённые чаты и папки пользователя
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChatOrganization {
+    pub version: u32,
+    pub pinned: BTreeSet<i32>,
+    pub folders: Vec<ChatFolder>,
+}
+
+impl Default for ChatOrganization {
+    fn default() -> Self {
+        Self {
+            version: CHAT_ORGANIZATION_VERSION,
+            pinned: BTreeSet::new(),
+            folders: Vec::new(),
+        }
+    }
+}
+
+/// Экспортируемый JSON: кроме папок и закреплённых чатов переносит режимы уведомлений,
+/// в том числе заглушённые чаты
+#[derive(Serialize, Deserialize)]
+struct ChatOrganizationExport {
+    version: u32,
+    pinned: BTreeSet<i32>,
+    folders: Vec<ChatFolder>,
+    #[serde(default)]
+    notification_modes: HashMap<i32, ChatNotificationMode>,
+}
+
+impl ChatOrganization {
+    pub fn load(user_id: Option<i32>) -> Self {
+        load_account_data::<Option<serde_json::Value>>(CHAT_ORGANIZATION_STORAGE_KEY, user_id)
+            .and_then(|value| from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, user_id: Option<i32>) {
+        save_account_data(CHAT_ORGANIZATION_STORAGE_KEY, user_id, self);
+    }
+
+    pub fn to_json(&self, notification_modes: &HashMap<i32, ChatNotificationMode>) -> String {
+        serde_json::to_string_pretty(&ChatOrganizationExport {
+            version: self.version,
+            pinned: self.pinned.clone(),
+            folders: self.folders.clone(),
+            notification_modes: notification_modes.clone(),
+        })
+        .unwrap_or_default()
+    }
+
+    /// Организация чатов и режимы уведомлений из экспортированного JSON
+    pub fn from_json(json: &str) -> Result<(Self, HashMap<i32, ChatNotificationMode>), String> {
+        let value = serde_json::from_str(json).map_err(|_| "Некорректный JSON".to_string())?;
+        let export = from_value::<ChatOrganizationExport>(value)?;
+
+        Ok((
+            Self {
+                version: export.version,
+                pinned: export.pinned,
+                folders: export.folders,
+            },
+            export.notification_modes,
+        ))
+    }
+
+    pub fn is_pinned(&self, chat_id: i32) -> bool {
+        self.pinned.contains(&chat_id)
+    }
+
+    pub fn with_pinned(&self, chat_id: i32, pinned: bool) -> Self {
+        let mut organization = self.clone();
+        toggle(&mut organization.pinned, chat_id, pinned);
+        organization
+    }
+
+    pub fn with_folder(&self, name: String) -> Self {
+        let mut organization = self.clone();
+
+        if !organization
+            .folders
+            .iter()
+            .any(|folder| folder.name == name)
+        {
+            organization.folders.push(ChatFolder {
+                name,
+                chat_ids: BTreeSet::new(),
+            });
+        }
+
+        organization
+    }
+
+    pub fn without_folder(&self, folder_index: usize) -> Self {
+        let mut organization = self.clone();
+
+        if folder_index < organization.folders.len() {
+            organization.folders.remove(folder_index);
+        }
+
+        organization
+    }
+
+    pub fn with_chat_in_folder(&self, folder_index: usize, chat_id: i32, in_folder: bool) -> Self {
+        let mut organization = self.clone();
+
+        if let Some(folder) = organization.folders.get_mut(folder_index) {
+            toggle(&mut folder.chat_ids, chat_id, in_folder);
+        }
+
+        organization
+    }
+}
+
+fn from_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, String> {
+    let version = value.get("version").and_then(|version| version.as_u64());
+
+    // Миграции со старых версий схемы добавлять сюда
+    match version {
+        Some(version) if version == CHAT_ORGANIZATION_VERSION as u64 => {
+            serde_json::from_value(value).map_err(|_| "Некорректный формат данных".to_string())
+        }
+        Some(version) => Err(format!("Неподдерживаемая версия настроек: {}", version)),
+        None => Err("Не указана версия настроек".to_string()),
+    }
+}
+
+fn toggle(set: &mut BTreeSet<i32>, chat_id: i32, value: bool) {
+    if value {
+        set.insert(chat_id);
+    } else {
+        set.remove(&chat_id);
+    }
+}
+
+pub type ChatOrganizationContext = UseStateHandle<ChatOrganization>;
+
+#[derive(PartialEq, Properties)]
+pub struct ChatOrganizationProviderProps {
+    pub children: Children,
+}
+
+#[function_component]
+pub fn ChatOrganizationProvider(props: &ChatOrganizationProviderProps) -> Html {
+    let ChatOrganizationProviderProps { children } = props;
+
+    let user_id = use_current_user().map(|user| user.id);
+    let organization = use_state(|| ChatOrganization::load(user_id));
+
+    use_effect_with_deps(
+        move |organization| organization.save(user_id),
+        (*organization).clone(),
+    );
+
+    html! {
+        <ContextProvider<ChatOrganizationContext> context={organization}>
+            { for children.iter() }
+        </ContextProvider<ChatOrganizationContext>>
+    }
+}
@@ -0,0 +1,79 @@
+use gloo_timers::callback::Timeout;
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use yew::prelude::*;
+
+use crate::constants::COPIED_FEEDBACK_MILLIS;
+
+/// `navigator.clipboard.writeText`. В web-sys этот API пока нестабилен, поэтому вызываем через Reflect
+async fn copy_to_clipboard(text: &str) -> Option<()> {
+    let clipboard = Reflect::get(&gloo_utils::window().navigator(), &"clipboard".into()).ok()?;
+    let write_text = Reflect::get(&clipboard, &"writeText".into())
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+    let promise = write_text
+        .call1(&clipboard, &JsValue::from_str(text))
+        .ok()?
+        .dyn_into::<Promise>()
+        .ok()?;
+
+    JsFuture::from(promise).await.ok().map(|_| ())
+}
+
+#[derive(PartialEq, Properties)]
+pub struct CopyButtonProps {
+    pub text: String,
+    #[prop_or_else(|| classes!("btn", "btn-outline-secondary"))]
+    pub class: Classes,
+}
+
+/// Копирует текст в буфер обмена и ненадолго показывает галочку
+#[function_component]
+pub fn CopyButton(props: &CopyButtonProps) -> Html {
+    let CopyButtonProps { text, class } = props;
+
+    let copied = use_state(|| Option::<bool>::None);
+
+    {
+        let is_copied = *copied;
+        let copied = copied.clone();
+
+        use_effect_with_deps(
+            move |is_copied| {
+                let timeout = is_copied
+                    .map(|_| Timeout::new(COPIED_FEEDBACK_MILLIS, move || copied.set(None)));
+
+                move || drop(timeout)
+            },
+            is_copied,
+        );
+    }
+
+    let onclick = {
+        let copied = copied.clone();
+        let text = text.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            let copied = copied.clone();
+            let text = text.clone();
+
+            spawn_local(async move {
+                copied.set(Some(copy_to_clipboard(&text).await.is_some()));
+            });
+        })
+    };
+
+    let (icon, title) = match *copied {
+        Some(true) => ("bi bi-clipboard-check", "Скопировано"),
+        Some(false) => ("bi bi-clipboard-x", "Не удалось скопировать"),
+        None => ("bi bi-clipboard", "Копировать"),
+    };
+
+    html! {
+        <button {onclick} type="button" class={class.clone()} {title}>
+            <i class={icon}></i>
+        </button>
+    }
+}
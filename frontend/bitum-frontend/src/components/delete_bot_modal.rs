@@ -0,0 +1,96 @@
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::{
+    api::{delete_bot, User},
+    components::{ErrorMessage, TypedConfirmModal},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+};
+
+#[derive(PartialEq, Properties)]
+pub struct DeleteBotModalButtonProps {
+    pub bot: User,
+    pub children: Children,
+    /// Вызывается с ID удалённого бота
+    #[prop_or_default]
+    pub on_deleted: Callback<i32>,
+}
+
+/// Удаляет бота после того, как пользователь введёт его имя
+#[function_component]
+pub fn DeleteBotModalButton(props: &DeleteBotModalButtonProps) -> Html {
+    let DeleteBotModalButtonProps {
+        bot,
+        children,
+        on_deleted,
+    } = props;
+
+    let error_message_state = use_state(|| Option::<String>::None);
+    let modal_visible = use_state(|| false);
+
+    let on_open = {
+        let modal_visible = modal_visible.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            modal_visible.set(true);
+        })
+    };
+
+    let on_close = {
+        let modal_visible = modal_visible.clone();
+
+        Callback::from(move |_: ()| {
+            modal_visible.set(false);
+        })
+    };
+
+    let on_confirm = {
+        let error_message_state = error_message_state.clone();
+        let on_deleted = on_deleted.clone();
+        let bot_id = bot.id;
+
+        Callback::from(move |_: ()| {
+            let error_message_state = error_message_state.clone();
+            let on_deleted = on_deleted.clone();
+
+            spawn_local(async move {
+                match delete_bot(bot_id).await {
+                    Ok(response) if response.ok => on_deleted.emit(bot_id),
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::DeleteBot.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <>
+        <span onclick={on_open} title="Удалить бота">
+            { for children.iter() }
+        </span>
+        <TypedConfirmModal
+            modal_id={"delete-bot-modal".to_string()}
+            is_visible={*modal_visible}
+            title={"Удалить бота".to_string()}
+            description={"Бот пропадёт из всех чатов, а его токен перестанет работать. Это действие нельзя отменить.".to_string()}
+            confirmation_text={bot.username.clone()}
+            {on_confirm}
+            {on_close}
+        />
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
@@ -0,0 +1,395 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::use_navigator;
+
+use crate::{
+    api::{
+        add_chat_member, delete_chat, get_chat_members, get_chats, new_chat, Chat, ChatMember,
+        EventType, NewChatRequest, User,
+    },
+    components::{use_current_user, use_events, ErrorMessage},
+    constants::{DIRECT_CHATS_STORAGE_KEY, DIRECT_CHAT_PEER_TTL_MILLIS},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::Route,
+    session::{load_account_data, save_account_data},
+};
+
+/// Личные чаты это обычные чаты из двух участников с именем `dm:<меньший id>:<больший id>`
+const DIRECT_CHAT_PREFIX: &str = "dm";
+
+/// Результат проверки состава чата с именем личного чата
+#[derive(Clone)]
+struct CheckedDirectChat {
+    self_id: i32,
+    /// Собеседник, если в чате ровно двое: мы и он
+    peer: Option<User>,
+    checked_at: f64,
+}
+
+thread_local! {
+    static DIRECT_CHATS: RefCell<HashMap<i32, CheckedDirectChat>> = RefCell::new(HashMap::new());
+}
+
+pub fn direct_chat_name(user_id: i32, other_user_id: i32) -> String {
+    format!(
+        "{}:{}:{}",
+        DIRECT_CHAT_PREFIX,
+        user_id.min(other_user_id),
+        user_id.max(other_user_id)
+    )
+}
+
+/// id собеседника по имени чата. Имя может задать кто угодно,
+/// поэтому показывать собеседника можно только после проверки участников
+fn direct_chat_peer_id(chat: &Chat, self_id: i32) -> Option<i32> {
+    let mut parts = chat.name.split(':');
+
+    if parts.next() != Some(DIRECT_CHAT_PREFIX) {
+        return None;
+    }
+
+    let first = parts.next()?.parse::<i32>().ok()?;
+    let second = parts.next()?.parse::<i32>().ok()?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    match (first == self_id, second == self_id) {
+        (true, _) => Some(second),
+        (_, true) => Some(first),
+        _ => None,
+    }
+}
+
+/// Собеседник, если в чате ровно два участника: мы и `peer_id`
+fn direct_chat_peer(members: &[ChatMember], self_id: i32, peer_id: i32) -> Option<User> {
+    if members.len() != 2 || !members.iter().any(|member| member.user.id == self_id) {
+        return None;
+    }
+
+    members
+        .iter()
+        .find(|member| member.user.id == peer_id && peer_id != self_id)
+        .map(|member| member.user.clone())
+}
+
+/// Ранее проверенный собеседник: `Some(None)` если чат оказался не личным
+fn cached_direct_chat(chat_id: i32, self_id: i32) -> Option<Option<User>> {
+    let now = js_sys::Date::now();
+
+    DIRECT_CHATS.with(|chats| {
+        chats
+            .borrow()
+            .get(&chat_id)
+            .filter(|checked| {
+                checked.self_id == self_id
+                    && now - checked.checked_at < DIRECT_CHAT_PEER_TTL_MILLIS as f64
+            })
+            .map(|checked| checked.peer.clone())
+    })
+}
+
+/// id личного чата с собеседником, который уже открывали. Хранится между перезагрузками,
+/// чтобы не искать чат заново, даже если его переименовали
+fn saved_direct_chat_id(self_id: i32, peer_id: i32) -> Option<i32> {
+    load_account_data::<HashMap<i32, i32>>(DIRECT_CHATS_STORAGE_KEY, Some(self_id))
+        .get(&peer_id)
+        .copied()
+}
+
+fn save_direct_chat_id(self_id: i32, peer_id: i32, chat_id: Option<i32>) {
+    let mut chat_ids =
+        load_account_data::<HashMap<i32, i32>>(DIRECT_CHATS_STORAGE_KEY, Some(self_id));
+
+    match chat_id {
+        Some(chat_id) => chat_ids.insert(peer_id, chat_id),
+        None => chat_ids.remove(&peer_id),
+    };
+
+    save_account_data(DIRECT_CHATS_STORAGE_KEY, Some(self_id), &chat_ids);
+}
+
+fn remember_direct_chat(chat_id: i32, self_id: i32, peer: Option<User>) {
+    if let Some(peer) = &peer {
+        save_direct_chat_id(self_id, peer.id, Some(chat_id));
+    }
+
+    DIRECT_CHATS.with(|chats| {
+        chats.borrow_mut().insert(
+            chat_id,
+            CheckedDirectChat {
+                self_id,
+                peer,
+                checked_at: js_sys::Date::now(),
+            },
+        )
+    });
+}
+
+/// Состав чата поменялся, проверку нужно повторить
+fn forget_direct_chat(chat_id: i32) {
+    DIRECT_CHATS.with(|chats| chats.borrow_mut().remove(&chat_id));
+}
+
+/// Проверяет участников чата, `None` если чат не личный или сервер не ответил
+async fn load_direct_chat_peer(chat: &Chat, self_id: i32) -> Option<User> {
+    let peer_id = direct_chat_peer_id(chat, self_id)?;
+
+    if let Some(peer) = cached_direct_chat(chat.id, self_id) {
+        return peer;
+    }
+
+    let members = match get_chat_members(chat.id).await {
+        Ok(response) if response.ok => response.data.unwrap_or_default(),
+        _ => return None,
+    };
+    let peer = direct_chat_peer(&members, self_id, peer_id);
+    remember_direct_chat(chat.id, self_id, peer.clone());
+
+    peer
+}
+
+/// Собеседник в личном чате, если участники чата уже проверены
+pub fn known_direct_chat_peer(chat: &Chat, self_id: i32) -> Option<User> {
+    direct_chat_peer_id(chat, self_id)?;
+    cached_direct_chat(chat.id, self_id).flatten()
+}
+
+/// Чат, если в нём по-прежнему только мы и `user_id`
+async fn check_direct_chat(chat_id: i32, self_id: i32, user_id: i32) -> Option<Chat> {
+    let members = match get_chat_members(chat_id).await {
+        Ok(response) if response.ok => response.data.unwrap_or_default(),
+        _ => return None,
+    };
+    let chat = members.first()?.chat.clone();
+    let peer = direct_chat_peer(&members, self_id, user_id);
+    remember_direct_chat(chat_id, self_id, peer.clone());
+
+    peer.map(|_| chat)
+}
+
+/// Открывает личный чат с пользователем: сначала уже известный, потом чат с именем личного,
+/// иначе создаёт новый
+pub async fn open_direct_chat(self_id: i32, user_id: i32) -> Result<Chat, String> {
+    if let Some(chat_id) = saved_direct_chat_id(self_id, user_id) {
+        if let Some(chat) = check_direct_chat(chat_id, self_id, user_id).await {
+            return Ok(chat);
+        }
+
+        save_direct_chat_id(self_id, user_id, None);
+    }
+
+    let name = direct_chat_name(self_id, user_id);
+    let chats = match get_chats().await {
+        Ok(response) if response.ok => response.data.unwrap_or_default(),
+        Ok(response) => {
+            return Err(ApiAction::OpenDirectChat.error_message(response.error.as_ref()))
+        }
+        Err(_) => return Err(SERVER_UNAVAILABLE.to_string()),
+    };
+
+    // Участников проверяем только у чатов с именем личного чата, а не у всех подряд
+    for chat in chats.iter().filter(|chat| chat.name == name) {
+        if let Some(chat) = check_direct_chat(chat.id, self_id, user_id).await {
+            return Ok(chat);
+        }
+    }
+
+    let chat = match new_chat(NewChatRequest {
+        name,
+        icon_file: None,
+    })
+    .await
+    {
+        Ok(response) if response.ok => response.data.unwrap(),
+        Ok(response) => {
+            return Err(ApiAction::OpenDirectChat.error_message(response.error.as_ref()))
+        }
+        Err(_) => return Err(SERVER_UNAVAILABLE.to_string()),
+    };
+
+    match add_chat_member(chat.id, user_id).await {
+        Ok(response) if response.ok => {
+            save_direct_chat_id(self_id, user_id, Some(chat.id));
+            Ok(chat)
+        }
+        response => {
+            // Не оставляем пустой чат, если собеседника добавить не удалось
+            let _ = delete_chat(chat.id).await;
+
+            Err(match response {
+                Ok(response) => ApiAction::AddMember.error_message(response.error.as_ref()),
+                Err(_) => SERVER_UNAVAILABLE.to_string(),
+            })
+        }
+    }
+}
+
+/// Как показывать чат в списке и в заголовке
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChatAppearance {
+    pub name: String,
+    pub icon: Option<String>,
+    pub is_direct: bool,
+}
+
+/// Для личных чатов подставляет имя и аватар собеседника
+#[hook]
+pub fn use_chat_appearance(chat: Option<&Chat>) -> Option<ChatAppearance> {
+    let self_id = use_current_user().map(|user| user.id);
+    let candidate = chat
+        .cloned()
+        .zip(self_id)
+        .filter(|(chat, self_id)| direct_chat_peer_id(chat, *self_id).is_some());
+    // Для какого чата проверен собеседник и кто он
+    let peer_state = use_state(|| Option::<(i32, Option<User>)>::None);
+    let membership_revision = use_state(|| 0u32);
+
+    {
+        let chat_id = candidate.as_ref().map(|(chat, _)| chat.id);
+        let membership_revision = membership_revision.clone();
+
+        use_events(move |event| {
+            let changed_chat_id = match event {
+                EventType::MemberAdded(member) => member.chat.id,
+                EventType::MemberKicked { chat, .. } => chat.id,
+                EventType::NewMessage(_) => return,
+            };
+
+            if Some(changed_chat_id) == chat_id {
+                forget_direct_chat(changed_chat_id);
+                membership_revision.set(*membership_revision + 1);
+            }
+        });
+    }
+
+    {
+        let peer_state = peer_state.clone();
+
+        use_effect_with_deps(
+            move |(candidate, _)| {
+                if let Some((chat, self_id)) = candidate.clone() {
+                    spawn_local(async move {
+                        let peer = load_direct_chat_peer(&chat, self_id).await;
+                        peer_state.set(Some((chat.id, peer)));
+                    });
+                }
+            },
+            (candidate.clone(), *membership_revision),
+        );
+    }
+
+    let chat = chat?;
+    let checked_peer = (*peer_state)
+        .clone()
+        .filter(|(chat_id, _)| candidate.is_some() && *chat_id == chat.id)
+        .map(|(_, peer)| peer);
+
+    Some(match checked_peer {
+        Some(Some(peer)) => ChatAppearance {
+            name: peer.username,
+            icon: peer.icon,
+            is_direct: true,
+        },
+        // Пока участники не проверены, не показываем ни имя собеседника, ни имя чата
+        None if candidate.is_some() => ChatAppearance {
+            name: "Личный чат".to_string(),
+            icon: None,
+            is_direct: false,
+        },
+        _ => ChatAppearance {
+            name: chat.name.clone(),
+            icon: chat.icon.clone(),
+            is_direct: false,
+        },
+    })
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ChatNameProps {
+    pub chat: Chat,
+}
+
+/// Имя чата, для личных чатов — имя собеседника
+#[function_component]
+pub fn ChatName(props: &ChatNameProps) -> Html {
+    let ChatNameProps { chat } = props;
+    let appearance = use_chat_appearance(Some(chat)).unwrap();
+
+    html! { {appearance.name} }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct DirectMessageButtonProps {
+    pub user: User,
+    pub children: Children,
+    /// Вызывается после перехода в личный чат
+    #[prop_or_default]
+    pub on_open: Callback<()>,
+}
+
+/// Открывает личный чат с пользователем по клику
+#[function_component]
+pub fn DirectMessageButton(props: &DirectMessageButtonProps) -> Html {
+    let DirectMessageButtonProps {
+        user,
+        children,
+        on_open,
+    } = props;
+
+    let self_user = use_current_user();
+    let error_message_state = use_state(|| Option::<String>::None);
+    let navigator = use_navigator().unwrap();
+
+    let Some(self_user) = self_user.filter(|self_user| self_user.id != user.id) else {
+        return html! {};
+    };
+
+    let onclick = {
+        let error_message_state = error_message_state.clone();
+        let user_id = user.id;
+        let on_open = on_open.clone();
+
+        Callback::from(move |event: MouseEvent| {
+            event.stop_propagation();
+
+            let error_message_state = error_message_state.clone();
+            let navigator = navigator.clone();
+            let self_id = self_user.id;
+            let on_open = on_open.clone();
+
+            spawn_local(async move {
+                match open_direct_chat(self_id, user_id).await {
+                    Ok(chat) => {
+                        navigator.push(&Route::Chat { id: chat.id });
+                        on_open.emit(());
+                    }
+                    Err(err) => error_message_state.set(Some(err)),
+                }
+            });
+        })
+    };
+
+    html! {
+        <>
+        <span {onclick} title="Написать сообщение">
+            { for children.iter() }
+        </span>
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
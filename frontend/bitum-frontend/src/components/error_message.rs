@@ -1,8 +1,6 @@
 use yew::prelude::*;
 
-use crate::{
-    components::{ToastMessage},
-};
+use crate::components::ToastMessage;
 
 #[derive(PartialEq, Properties)]
 pub struct ErrorMessageProps {
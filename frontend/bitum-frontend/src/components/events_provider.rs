@@ -0,0 +1,141 @@
+use std::{cell::Cell, collections::HashSet, rc::Rc};
+
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::{
+    api::{get_events, read_events, Event, EventType, ReadEventsRequest},
+    constants::{API_REFRESH_MILLIS, MAX_READ_EVENTS_PER_REQUEST},
+    error_messages::UNAUTHORIZED,
+};
+
+/// Последняя пачка событий, полученная с сервера
+#[derive(Clone, Debug, Default)]
+pub struct EventsBatch {
+    pub generation: u64,
+    pub events: Rc<Vec<Event>>,
+}
+
+impl PartialEq for EventsBatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.generation == other.generation
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct EventsProviderProps {
+    pub children: Children,
+    /// Опрашивать сервер, только пока пользователь вошёл
+    pub enabled: bool,
+    /// Сервер перестал принимать токен
+    #[prop_or_default]
+    pub on_unauthorized: Callback<()>,
+}
+
+#[function_component]
+pub fn EventsProvider(props: &EventsProviderProps) -> Html {
+    let EventsProviderProps {
+        children,
+        enabled,
+        on_unauthorized,
+    } = props;
+
+    let events_batch = use_state(EventsBatch::default);
+
+    {
+        let events_batch = events_batch.clone();
+        let on_unauthorized = on_unauthorized.clone();
+
+        use_effect_with_deps(
+            move |enabled| {
+                let is_running = Rc::new(Cell::new(*enabled));
+
+                {
+                    let is_running = is_running.clone();
+
+                    spawn_local(async move {
+                        let mut generation = 0;
+                        let mut delivered_event_ids = HashSet::<i32>::new();
+
+                        while is_running.get() {
+                            TimeoutFuture::new(API_REFRESH_MILLIS).await;
+
+                            let events = match get_events().await {
+                                Ok(response) if response.ok => response.data.unwrap_or_default(),
+                                Ok(response)
+                                    if response.error.as_ref().map(|error| error.code)
+                                        == Some(UNAUTHORIZED) =>
+                                {
+                                    on_unauthorized.emit(());
+                                    break;
+                                }
+                                _ => continue,
+                            };
+
+                            if !is_running.get() || events.is_empty() {
+                                continue;
+                            }
+
+                            let event_ids: Vec<i32> = events.iter().map(|event| event.id).collect();
+
+                            for ids in event_ids.chunks(MAX_READ_EVENTS_PER_REQUEST) {
+                                let _ = read_events(ReadEventsRequest { ids: ids.to_vec() }).await;
+                            }
+
+                            // Если сервер не успел отметить события прочитанными,
+                            // они придут ещё раз, но второй раз их никто не увидит
+                            let new_events: Vec<Event> = events
+                                .into_iter()
+                                .filter(|event| delivered_event_ids.insert(event.id))
+                                .collect();
+
+                            if new_events.is_empty() {
+                                continue;
+                            }
+
+                            generation += 1;
+                            events_batch.set(EventsBatch {
+                                generation,
+                                events: Rc::new(new_events),
+                            });
+                        }
+                    });
+                }
+
+                move || is_running.set(false)
+            },
+            *enabled,
+        );
+    }
+
+    html! {
+        <ContextProvider<EventsBatch> context={(*events_batch).clone()}>
+            { for children.iter() }
+        </ContextProvider<EventsBatch>>
+    }
+}
+
+/// Вызывает `on_event` для каждого нового события.
+/// События, пришедшие до монтирования компонента, пропускаются.
+#[hook]
+pub fn use_events<F>(on_event: F)
+where
+    F: Fn(&EventType) + 'static,
+{
+    let events_batch = use_context::<EventsBatch>().unwrap_or_default();
+    let last_generation = use_mut_ref(|| events_batch.generation);
+
+    use_effect_with_deps(
+        move |events_batch| {
+            if *last_generation.borrow() != events_batch.generation {
+                *last_generation.borrow_mut() = events_batch.generation;
+
+                for event in events_batch.events.iter() {
+                    on_event(&event.payload);
+                }
+            }
+        },
+        events_batch,
+    );
+}
@@ -0,0 +1,115 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(PartialEq, Properties)]
+pub struct FormFieldProps {
+    pub id: AttrValue,
+    pub label: AttrValue,
+    pub value: String,
+    pub on_input: Callback<String>,
+    /// Результат проверки значения, см. `crate::validation`
+    #[prop_or_default]
+    pub error: Option<String>,
+    #[prop_or(AttrValue::Static("text"))]
+    pub input_type: AttrValue,
+    #[prop_or_default]
+    pub placeholder: AttrValue,
+    #[prop_or_default]
+    pub autocomplete: Option<AttrValue>,
+    /// Кнопка, показывающая введённый пароль
+    #[prop_or_default]
+    pub revealable: bool,
+    /// Дополнительные подсказки под полем
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Поле формы с ошибкой под ним. Ошибка появляется, как только в поле что-то ввели или ушли из него
+#[function_component]
+pub fn FormField(props: &FormFieldProps) -> Html {
+    let FormFieldProps {
+        id,
+        label,
+        value,
+        on_input,
+        error,
+        input_type,
+        placeholder,
+        autocomplete,
+        revealable,
+        children,
+    } = props;
+
+    let touched = use_state(|| false);
+    let revealed = use_state(|| false);
+
+    let oninput = {
+        let on_input = on_input.clone();
+
+        Callback::from(move |event: InputEvent| {
+            on_input.emit(event.target_unchecked_into::<HtmlInputElement>().value());
+        })
+    };
+
+    let onblur = {
+        let touched = touched.clone();
+
+        Callback::from(move |_: FocusEvent| touched.set(true))
+    };
+
+    let on_reveal_click = {
+        let revealed = revealed.clone();
+
+        Callback::from(move |_: MouseEvent| revealed.set(!*revealed))
+    };
+
+    let visible_error = error.clone().filter(|_| *touched || !value.is_empty());
+    let input_type = if *revealable && *revealed {
+        AttrValue::Static("text")
+    } else {
+        input_type.clone()
+    };
+
+    let input = html! {
+        <input
+            {oninput}
+            {onblur}
+            id={id.clone()}
+            value={value.clone()}
+            type={input_type}
+            placeholder={placeholder.clone()}
+            autocomplete={autocomplete.clone()}
+            class={classes!("form-control", visible_error.is_some().then_some("is-invalid"))}
+            required=true
+        />
+    };
+    let error = html! {
+        if let Some(error) = visible_error {
+            <div class="invalid-feedback">{error}</div>
+        }
+    };
+
+    html! {
+        <div class="col-12 input-group-lg">
+            <label class="fw-medium fs-5" for={id.clone()}>{label.clone()}</label>
+            if *revealable {
+                <div class="input-group input-group-lg has-validation">
+                    {input}
+                    <button
+                        onclick={on_reveal_click}
+                        type="button"
+                        class="btn btn-outline-secondary"
+                        title={if *revealed { "Скрыть" } else { "Показать" }}
+                    >
+                        <i class={if *revealed { "bi bi-eye-slash" } else { "bi bi-eye" }}></i>
+                    </button>
+                    {error}
+                </div>
+            } else {
+                {input}
+                {error}
+            }
+            { for children.iter() }
+        </div>
+    }
+}
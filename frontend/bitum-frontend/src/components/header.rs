@@ -1,8 +1,11 @@
 use bitum_frontend::get_random_color_image_url;
 use yew::prelude::*;
-use yew_router::prelude::Link;
+use yew_router::prelude::{use_navigator, Link};
 
-use crate::{components::LoggedUserInfo, routes::Route};
+use crate::{
+    components::{use_auth, NotificationSettingsContext, NotificationsToggle, UnreadContext},
+    routes::Route,
+};
 
 #[derive(PartialEq, Properties)]
 pub struct HeaderProps {}
@@ -11,7 +14,26 @@ pub struct HeaderProps {}
 #[function_component]
 pub fn Header(props: &HeaderProps) -> Html {
     let HeaderProps {} = props;
-    let logged_user_info = use_context::<LoggedUserInfo>();
+    let auth = use_auth();
+    let navigator = use_navigator();
+
+    let on_logout = {
+        let logout = auth.logout.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            logout.emit(false);
+
+            if let Some(navigator) = navigator.as_ref() {
+                navigator.push(&Route::Login);
+            }
+        })
+    };
+    let muted = use_context::<NotificationSettingsContext>()
+        .map(|settings| settings.muted_chats())
+        .unwrap_or_default();
+    let total_unread_count = use_context::<UnreadContext>()
+        .map(|unread| unread.total_unread_count(&muted))
+        .unwrap_or_default();
 
     html! {
         <header class="header row">
@@ -21,33 +43,71 @@ pub fn Header(props: &HeaderProps) -> Html {
                 </a>
                 <div class="fw-light fs-1 p-4">{"Bitum"}</div>
             </div>
-            if let Some(logged_user_info) = logged_user_info {
-                if let Some(user) = logged_user_info.user {
-                    <nav class="col-md-6 col-sm-12 d-flex text-center justify-content-center align-items-center">
-                        <div class="p-3 fs-5">
-                            <Link<Route> classes="text-decoration-none text-light-emphasis" to={Route::Chats}>{"Чаты"}</Link<Route>>
-                        </div>
-                        <div class="p-3 fs-4">
-                            {"//"}
-                        </div>
-                        <div class="p-3 fs-5">
-                            <Link<Route> classes="text-decoration-none text-light-emphasis" to={Route::MyBots}>{"Боты"}</Link<Route>>
-                        </div>
-                    </nav>
+            if let Some(user) = auth.state.user().cloned() {
+                <nav class="col-md-6 col-sm-12 d-flex text-center justify-content-center align-items-center">
+                    <div class="p-3 fs-5">
+                        <Link<Route> classes="text-decoration-none text-light-emphasis" to={Route::Chats}>
+                            {"Чаты"}
+                            if total_unread_count > 0 {
+                                {" "}
+                                <span class="badge rounded-pill text-bg-success">{total_unread_count}</span>
+                            }
+                        </Link<Route>>
+                    </div>
+                    <div class="p-3 fs-4">
+                        {"//"}
+                    </div>
+                    <div class="p-3 fs-5">
+                        <Link<Route> classes="text-decoration-none text-light-emphasis" to={Route::MyBots}>{"Боты"}</Link<Route>>
+                    </div>
+                    <div class="p-3 fs-4">
+                        {"//"}
+                    </div>
+                    <div class="p-3 fs-5">
+                        <Link<Route> classes="text-decoration-none text-light-emphasis" to={Route::Bots}>{"Каталог"}</Link<Route>>
+                    </div>
+                </nav>
 
-                    <div class="col-md-3 col-sm-12 d-flex text-center justify-content-center align-items-center">
-                        <div class="fs-3 fw-medium p-5">
-                            {user.username.clone()}
+                <div class="col-md-3 col-sm-12 d-flex text-center justify-content-center align-items-center">
+                    <NotificationsToggle />
+                    <div class="dropdown">
+                        <div role="button" data-bs-toggle="dropdown" aria-expanded="false" class="d-flex align-items-center">
+                            <div class="fs-3 fw-medium p-5">
+                                {user.username.clone()}
+                            </div>
+                            <img src={
+                                if user.icon.is_some() {
+                                    format!("/api/files/{}", user.icon.unwrap_or("null.png".to_string()))
+                                } else {
+                                    get_random_color_image_url(user.username.clone(), 60, 60)
+                                }
+                            } class="rounded" width=70px alt="Icon" />
                         </div>
-                        <img src={
-                            if user.icon.is_some() {
-                                format!("/api/files/{}", user.icon.unwrap_or("null.png".to_string()))
-                            } else {
-                                get_random_color_image_url(user.username.clone(), 60, 60)
-                            }
-                        } class="rounded" width=70px alt="Icon" />
+                        <ul class="dropdown-menu dropdown-menu-end">
+                            <li>
+                                <Link<Route> classes="dropdown-item" to={Route::UserProfile { username: user.username.clone() }}>
+                                    <i class="bi bi-person"></i>{" Профиль"}
+                                </Link<Route>>
+                            </li>
+                            <li>
+                                <Link<Route> classes="dropdown-item" to={Route::Settings}>
+                                    <i class="bi bi-gear"></i>{" Настройки"}
+                                </Link<Route>>
+                            </li>
+                            <li>
+                                <Link<Route> classes="dropdown-item" to={Route::MyBots}>
+                                    <i class="bi bi-robot"></i>{" Мои боты"}
+                                </Link<Route>>
+                            </li>
+                            <li><hr class="dropdown-divider" /></li>
+                            <li>
+                                <button onclick={on_logout} type="button" class="dropdown-item text-danger">
+                                    <i class="bi bi-box-arrow-right"></i>{" Выйти"}
+                                </button>
+                            </li>
+                        </ul>
                     </div>
-                }
+                </div>
             }
         </header>
     }
@@ -1,54 +1,52 @@
-use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
-use yew_router::prelude::use_navigator;
+use yew_router::prelude::{use_location, use_navigator};
 
 use crate::{
-    api::{get_current_user_info, User},
-    routes::Route,
+    components::{use_auth, AuthState},
+    routes::{login::LoginRouteQuery, Route},
 };
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct LoggedUserInfo {
-    pub user: Option<User>,
-}
-
 #[derive(PartialEq, Properties)]
 pub struct LoginOrRedirectProps {
     pub children: Children,
 }
 
+/// Показывает содержимое только вошедшему пользователю, остальных отправляет на вход
 #[function_component]
 pub fn LoginOrRedirect(props: &LoginOrRedirectProps) -> Html {
     let LoginOrRedirectProps { children } = props;
 
-    let logged_user_info = use_state(|| LoggedUserInfo { user: None });
+    let auth = use_auth();
     let navigator = use_navigator().unwrap();
-
-    {
-        let logged_user_info = logged_user_info.clone();
-
-        spawn_local(async move {
-            if logged_user_info.user.is_some() {
-                return;
+    // Запомненный путь вернёт пользователя сюда после входа
+    let current_path = use_location().map(|location| location.path().to_string());
+
+    use_effect_with_deps(
+        move |state| {
+            if let AuthState::Anonymous { session_expired } = state {
+                let _ = navigator.push_with_query(
+                    &Route::Login,
+                    &LoginRouteQuery {
+                        session_expired: *session_expired,
+                        redirect: current_path,
+                        ..Default::default()
+                    },
+                );
             }
-
-            let response = get_current_user_info().await;
-
-            if let Ok(resp) = response {
-                if resp.ok {
-                    logged_user_info.set(LoggedUserInfo { user: resp.data });
-                } else {
-                    navigator.push(&Route::Login);
-                }
-            } else {
-                navigator.push(&Route::Login);
-            }
-        });
-    }
-
-    html! {
-        <ContextProvider<LoggedUserInfo> context={(*logged_user_info).clone()}>
-            { for children.iter() }
-        </ContextProvider<LoggedUserInfo>>
+        },
+        auth.state.clone(),
+    );
+
+    match auth.state {
+        AuthState::Authenticated(_) => html! {
+            <>{ for children.iter() }</>
+        },
+        _ => html! {
+            <div class="d-flex justify-content-center p-5">
+                <div class="spinner-border" role="status">
+                    <span class="visually-hidden">{"Загрузка..."}</span>
+                </div>
+            </div>
+        },
     }
 }
@@ -0,0 +1,226 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{
+    api::{patch_chat_member_permissions, ChatMember, ChatMemberPermissions},
+    components::{ErrorMessage, Modal},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MemberRole {
+    Admin,
+    Moderator,
+    Member,
+    ReadOnly,
+}
+
+impl MemberRole {
+    pub const ALL: [MemberRole; 4] = [
+        MemberRole::Admin,
+        MemberRole::Moderator,
+        MemberRole::Member,
+        MemberRole::ReadOnly,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MemberRole::Admin => "Администратор",
+            MemberRole::Moderator => "Модератор",
+            MemberRole::Member => "Участник",
+            MemberRole::ReadOnly => "Только чтение",
+        }
+    }
+
+    pub fn permissions(&self) -> ChatMemberPermissions {
+        let (can_write, can_add_members, can_kick_members) = match self {
+            MemberRole::Admin => (true, true, true),
+            MemberRole::Moderator => (true, false, true),
+            MemberRole::Member => (true, true, false),
+            MemberRole::ReadOnly => (false, false, false),
+        };
+
+        ChatMemberPermissions {
+            can_write,
+            can_add_members,
+            can_kick_members,
+        }
+    }
+
+    pub fn from_permissions(permissions: &ChatMemberPermissions) -> Option<MemberRole> {
+        MemberRole::ALL
+            .into_iter()
+            .find(|role| role.permissions() == *permissions)
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct MemberPermissionBadgesProps {
+    pub permissions: ChatMemberPermissions,
+}
+
+#[function_component]
+pub fn MemberPermissionBadges(props: &MemberPermissionBadgesProps) -> Html {
+    let MemberPermissionBadgesProps { permissions } = props;
+
+    html! {
+        <div class="d-flex gap-1">
+            if permissions.can_write {
+                <span class="badge rounded-pill text-bg-light" title="Может писать сообщения">
+                    <i class="bi bi-pencil"></i>
+                </span>
+            } else {
+                <span class="badge rounded-pill text-bg-secondary" title="Только чтение">
+                    <i class="bi bi-eye"></i>
+                </span>
+            }
+            if permissions.can_add_members {
+                <span class="badge rounded-pill text-bg-light" title="Может добавлять участников">
+                    <i class="bi bi-person-plus"></i>
+                </span>
+            }
+            if permissions.can_kick_members {
+                <span class="badge rounded-pill text-bg-light" title="Может удалять участников">
+                    <i class="bi bi-person-x"></i>
+                </span>
+            }
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct MemberPermissionsModalButtonProps {
+    pub member: ChatMember,
+    pub children: Children,
+}
+
+#[function_component]
+pub fn MemberPermissionsModalButton(props: &MemberPermissionsModalButtonProps) -> Html {
+    let MemberPermissionsModalButtonProps { member, children } = props;
+
+    let modal_visible = use_state(|| false);
+    let error_message_state = use_state(|| Option::<String>::None);
+    let permissions_state = use_state(|| member.permissions.clone());
+
+    let on_open = {
+        let modal_visible = modal_visible.clone();
+        let permissions_state = permissions_state.clone();
+        let permissions = member.permissions.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            permissions_state.set(permissions.clone());
+            modal_visible.set(true);
+        })
+    };
+
+    let on_close = {
+        let modal_visible = modal_visible.clone();
+
+        Callback::from(move |_: ()| {
+            modal_visible.set(false);
+        })
+    };
+
+    let on_ok = {
+        let error_message_state = error_message_state.clone();
+        let permissions_state = permissions_state.clone();
+        let member = member.clone();
+
+        Callback::from(move |_: ()| {
+            let error_message_state = error_message_state.clone();
+            let permissions = (*permissions_state).clone();
+            let member = member.clone();
+
+            spawn_local(async move {
+                let response =
+                    patch_chat_member_permissions(member.chat.id, member.user.id, permissions)
+                        .await;
+
+                if let Ok(response) = response {
+                    if !response.ok {
+                        error_message_state.set(Some(
+                            ApiAction::EditPermissions.error_message(response.error.as_ref()),
+                        ));
+                    }
+                } else {
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                }
+            });
+        })
+    };
+
+    let toggle = |label: &'static str,
+                  get: fn(&ChatMemberPermissions) -> bool,
+                  set: fn(&mut ChatMemberPermissions, bool)| {
+        let permissions_state = permissions_state.clone();
+        let onchange = {
+            let permissions_state = permissions_state.clone();
+
+            Callback::from(move |event: Event| {
+                let input = event.target_unchecked_into::<HtmlInputElement>();
+                let mut permissions = (*permissions_state).clone();
+                set(&mut permissions, input.checked());
+                permissions_state.set(permissions);
+            })
+        };
+
+        html! {
+            <div class="form-check form-switch">
+                <label class="form-check-label">
+                    <input class="form-check-input" type="checkbox" role="switch" checked={get(&permissions_state)} {onchange} />
+                    {label}
+                </label>
+            </div>
+        }
+    };
+
+    let current_role = MemberRole::from_permissions(&permissions_state);
+
+    html! {
+        <>
+        <span onclick={on_open}>
+            { for children.iter() }
+        </span>
+
+        <Modal modal_id={"member-permissions-modal".to_string()} is_visible={*modal_visible} on_ok={on_ok} on_cancel={Callback::from(|_| {})} on_close={on_close}>
+            <div class="modal-header">
+                <h1 class="modal-title fs-5">{"Права "} {member.user.username.clone()}</h1>
+            </div>
+            <div class="modal-body">
+                <div class="d-flex flex-wrap gap-1 pb-3">
+                    { for MemberRole::ALL.into_iter().map(|role| {
+                        let permissions_state = permissions_state.clone();
+
+                        html! {
+                            <button
+                                type="button"
+                                class={classes!("btn", "btn-sm", "m-0", if current_role == Some(role) { "btn-dark" } else { "btn-outline-dark" })}
+                                onclick={Callback::from(move |_: MouseEvent| permissions_state.set(role.permissions()))}
+                            >
+                                {role.name()}
+                            </button>
+                        }
+                    })}
+                </div>
+                { toggle("Писать сообщения", |p| p.can_write, |p, value| p.can_write = value) }
+                { toggle("Добавлять участников", |p| p.can_add_members, |p, value| p.can_add_members = value) }
+                { toggle("Удалять участников", |p| p.can_kick_members, |p, value| p.can_kick_members = value) }
+            </div>
+        </Modal>
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
@@ -1,7 +1,11 @@
 use bitum_frontend::{display_timestamp_date, get_random_color_image_url, parse_markdown_to_html};
 use yew::prelude::*;
+use yew_router::prelude::Link;
 
-use crate::components::{LoggedUserInfo, RawHtml};
+use crate::{
+    components::{use_current_user, RawHtml},
+    routes::Route,
+};
 
 #[derive(PartialEq, Properties)]
 pub struct ChatMessageProps {
@@ -13,12 +17,12 @@ pub fn ChatMessage(props: &ChatMessageProps) -> Html {
     let ChatMessageProps { message } = props;
 
     let message = message.clone();
-    let user = use_context::<LoggedUserInfo>().unwrap().user.unwrap();
+    let self_id = use_current_user().map(|user| user.id);
 
     html! {
         <>
             <div class="d-flex">
-                if message.sender.id == user.id {
+                if Some(message.sender.id) == self_id {
                     <div class="col-lg-1 d-none d-lg-block d-md-none"></div>
                 }
 
@@ -37,7 +41,9 @@ pub fn ChatMessage(props: &ChatMessageProps) -> Html {
                                     <span class="badge rounded-pill text-bg-dark">{"бот"}</span>
                                     {" "}
                                 }
-                                { message.sender.username }
+                                <Link<Route> classes="text-reset text-decoration-none" to={Route::UserProfile { username: message.sender.username.clone() }}>
+                                    { message.sender.username }
+                                </Link<Route>>
                             </div>
                             <div class="fs-5 p-2 text-body-secondary fw-light">
                                 {display_timestamp_date(message.created_at as i64)}
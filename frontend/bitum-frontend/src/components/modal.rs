@@ -8,6 +8,8 @@ pub struct ModalProps {
     pub on_close: Callback<()>,
     pub is_visible: bool,
     pub modal_id: String,
+    #[prop_or_default]
+    pub ok_disabled: bool,
 }
 
 #[function_component]
@@ -19,6 +21,7 @@ pub fn Modal(props: &ModalProps) -> Html {
         on_close,
         is_visible,
         modal_id,
+        ok_disabled,
     } = props;
 
     let on_cancel_click = {
@@ -49,7 +52,7 @@ pub fn Modal(props: &ModalProps) -> Html {
                         { for children.iter() }
                         <div class="modal-footer">
                             <button type="button" onclick={on_cancel_click} class="btn btn-secondary" data-bs-dismiss="modal">{"Отмена"}</button>
-                            <button type="button" onclick={on_ok_click} class="btn btn-primary" data-bs-dismiss="modal">{"ОК"}</button>
+                            <button type="button" onclick={on_ok_click} class="btn btn-primary" disabled={*ok_disabled} data-bs-dismiss="modal">{"ОК"}</button>
                         </div>
                     </div>
                 </div>
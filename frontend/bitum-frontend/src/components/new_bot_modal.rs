@@ -3,23 +3,34 @@ use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
 use crate::{
-    api::new_bot,
-    components::{ErrorMessage, Modal},
+    api::{new_bot, upload_file, NewBotRequest, User},
+    components::{ErrorMessage, FormField, Modal},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    validation::validate_username,
 };
 
 #[derive(PartialEq, Properties)]
 pub struct NewBotModalButtonProps {
     pub children: Children,
     pub username: String,
+    /// Вызывается с только что созданным ботом
+    #[prop_or_default]
+    pub on_created: Callback<User>,
 }
 
 #[function_component]
 pub fn NewBotModalButton(props: &NewBotModalButtonProps) -> Html {
-    let NewBotModalButtonProps { children, username } = props;
+    let NewBotModalButtonProps {
+        children,
+        username,
+        on_created,
+    } = props;
 
     let error_message_state = use_state(|| Option::<String>::None);
     let dialog_visible = use_state(|| false);
-    let bot_username_node_ref = use_node_ref();
+    let bot_username_state = use_state(|| username.clone());
+    let icon_name_state = use_state(|| Option::<String>::None);
+    let is_uploading = use_state(|| false);
 
     let on_close = {
         let dialog_visible = dialog_visible.clone();
@@ -29,29 +40,72 @@ pub fn NewBotModalButton(props: &NewBotModalButtonProps) -> Html {
         })
     };
 
+    let on_icon_input = {
+        let error_message_state = error_message_state.clone();
+        let icon_name_state = icon_name_state.clone();
+        let is_uploading = is_uploading.clone();
+
+        Callback::from(move |event: InputEvent| {
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+            let Some(icon_file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            let error_message_state = error_message_state.clone();
+            let icon_name_state = icon_name_state.clone();
+            let is_uploading = is_uploading.clone();
+
+            is_uploading.set(true);
+
+            spawn_local(async move {
+                match upload_file(icon_file).await {
+                    Ok(response) if response.ok => {
+                        icon_name_state.set(response.data.unwrap_or_default().first().cloned())
+                    }
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::UploadFile.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+
+                is_uploading.set(false);
+            });
+        })
+    };
+
+    let bot_username_error = validate_username(&bot_username_state).err();
+
     let on_ok = {
         let on_close = on_close.clone();
-        let bot_username_node_ref = bot_username_node_ref.clone();
+        let bot_username_state = bot_username_state.clone();
+        let bot_username_error = bot_username_error.clone();
+        let icon_name_state = icon_name_state.clone();
         let error_message_state = error_message_state.clone();
+        let on_created = on_created.clone();
 
         Callback::from(move |_: ()| {
             let error_message_state = error_message_state.clone();
-            let bot_username = bot_username_node_ref
-                .cast::<HtmlInputElement>()
-                .unwrap()
-                .value();
+            let on_created = on_created.clone();
+            let new_bot_request = NewBotRequest {
+                username: (*bot_username_state).clone(),
+                icon_file: (*icon_name_state).clone(),
+            };
 
-            spawn_local(async move {
-                let bot_username = bot_username.clone();
-
-                let response = new_bot(bot_username).await;
+            if let Some(bot_username_error) = bot_username_error.clone() {
+                error_message_state.set(Some(bot_username_error));
+                return;
+            }
 
-                if let Ok(response) = response {
-                    if !response.ok {
-                        error_message_state.set(Some("Что-то пошло не так".to_string()));
+            spawn_local(async move {
+                match new_bot(new_bot_request).await {
+                    Ok(response) if response.ok => {
+                        if let Some(bot) = response.data {
+                            on_created.emit(bot);
+                        }
                     }
-                } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::CreateBot.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
                 }
             });
 
@@ -61,8 +115,13 @@ pub fn NewBotModalButton(props: &NewBotModalButtonProps) -> Html {
 
     let on_open = {
         let dialog_visible = dialog_visible.clone();
+        let bot_username_state = bot_username_state.clone();
+        let icon_name_state = icon_name_state.clone();
+        let username = username.clone();
 
         Callback::from(move |_: MouseEvent| {
+            bot_username_state.set(username.clone());
+            icon_name_state.set(None);
             dialog_visible.set(true);
         })
     };
@@ -73,14 +132,25 @@ pub fn NewBotModalButton(props: &NewBotModalButtonProps) -> Html {
             { for children.iter() }
         </span>
 
-        <Modal modal_id={"new-bot-modal".to_string()} is_visible={*dialog_visible} on_ok={on_ok} on_cancel={Callback::from(|_| {})} on_close={on_close}>
+        <Modal modal_id={"new-bot-modal".to_string()} is_visible={*dialog_visible} on_ok={on_ok} ok_disabled={bot_username_error.is_some() || *is_uploading} on_cancel={Callback::from(|_| {})} on_close={on_close}>
             <div class="modal-header">
                 <h1 class="modal-title fs-5">{"Создать бота"}</h1>
             </div>
             <div class="modal-body">
-                <div class="input-group p-1">
-                    <span class="input-group-text">{"Имя бота"}</span>
-                    <input value={username.clone()} ref={bot_username_node_ref} type="text" class="form-control" aria-label="bot_username" required=true />
+                <FormField
+                    id="new-bot-username-input"
+                    label="Имя бота"
+                    value={(*bot_username_state).clone()}
+                    on_input={
+                        let bot_username_state = bot_username_state.clone();
+
+                        Callback::from(move |value: String| bot_username_state.set(value))
+                    }
+                    error={bot_username_error}
+                />
+                <div class="pt-3">
+                    <label class="fw-medium fs-5" for="new-bot-icon-input">{"Иконка"}</label>
+                    <input oninput={on_icon_input} id="new-bot-icon-input" type="file" class="form-control" accept="image/png, image/jpeg, image/jpg" />
                 </div>
             </div>
         </Modal>
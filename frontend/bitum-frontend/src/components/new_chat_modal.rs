@@ -5,8 +5,9 @@ use yew_router::prelude::use_navigator;
 
 use crate::{
     api::{new_chat, upload_file, NewChatRequest},
-    components::{ErrorMessage, Modal},
+    components::{ErrorMessage, FormField, Modal},
     routes::Route,
+    validation::validate_chat_name,
 };
 
 #[derive(PartialEq, Properties)]
@@ -26,7 +27,7 @@ pub fn NewChatModalButton(props: &NewChatModalButtonProps) -> Html {
 
     let new_chat_dialog_visible = use_state(|| false);
     let icon_name_state = use_state(|| Option::<String>::None);
-    let new_chat_input_node = use_node_ref();
+    let chat_name_state = use_state(|| chat_name.clone());
     let new_chat_icon_node = use_node_ref();
 
     let navigator = use_navigator().unwrap();
@@ -34,8 +35,11 @@ pub fn NewChatModalButton(props: &NewChatModalButtonProps) -> Html {
 
     let on_new_chat_click = {
         let new_chat_dialog_visible = new_chat_dialog_visible.clone();
+        let chat_name_state = chat_name_state.clone();
+        let chat_name = chat_name.clone();
 
         Callback::from(move |_: MouseEvent| {
+            chat_name_state.set(chat_name.clone());
             new_chat_dialog_visible.set(true);
         })
     };
@@ -71,7 +75,7 @@ pub fn NewChatModalButton(props: &NewChatModalButtonProps) -> Html {
                             if !response.ok {
                                 error_message_state.set(Some("Что-то пошло не так".to_string()));
                             } else {
-                                icon_name_state.set(response.data.unwrap().get(0).cloned());
+                                icon_name_state.set(response.data.unwrap().first().cloned());
                             }
                         } else {
                             error_message_state.set(Some("Сервер не отвечает".to_string()));
@@ -82,28 +86,24 @@ pub fn NewChatModalButton(props: &NewChatModalButtonProps) -> Html {
         })
     };
 
+    let chat_name_error = validate_chat_name(&chat_name_state).err();
+
     let on_ok = {
         let error_message_state = error_message_state.clone();
-        let new_chat_input_node = new_chat_input_node.clone();
+        let chat_name_state = chat_name_state.clone();
+        let chat_name_error = chat_name_error.clone();
         let icon_name_state = icon_name_state.clone();
-        let redirect = redirect.clone();
+        let redirect = *redirect;
         let navigator = navigator.clone();
 
         Callback::from(move |_: ()| {
             let error_message_state = error_message_state.clone();
-            let new_chat_input_node = new_chat_input_node.clone();
             let icon_name_state = icon_name_state.clone();
             let navigator = navigator.clone();
+            let chat_name = (*chat_name_state).clone();
 
-            if new_chat_input_node.cast::<HtmlInputElement>().is_none() {
-                return;
-            }
-
-            let input_element = new_chat_input_node.cast::<HtmlInputElement>().unwrap();
-            let chat_name = input_element.value();
-
-            if chat_name.is_empty() {
-                error_message_state.set(Some("Чат не был создан".to_string()));
+            if let Some(chat_name_error) = chat_name_error.clone() {
+                error_message_state.set(Some(chat_name_error));
                 return;
             }
 
@@ -119,7 +119,7 @@ pub fn NewChatModalButton(props: &NewChatModalButtonProps) -> Html {
                     if !response.ok {
                         error_message_state.set(Some("Что-то пошло не так".to_string()));
                     } else {
-                        if redirect.clone() {
+                        if redirect {
                             navigator.push(&Route::Chat {
                                 id: response.data.unwrap().id,
                             });
@@ -138,15 +138,22 @@ pub fn NewChatModalButton(props: &NewChatModalButtonProps) -> Html {
             { for children.iter() }
         </span>
 
-        <Modal modal_id={"new-chat-modal".to_string()} is_visible={*new_chat_dialog_visible} on_ok={on_ok} on_cancel={Callback::from(|_| {})} on_close={on_dialog_close}>
+        <Modal modal_id={"new-chat-modal".to_string()} is_visible={*new_chat_dialog_visible} on_ok={on_ok} ok_disabled={chat_name_error.is_some()} on_cancel={Callback::from(|_| {})} on_close={on_dialog_close}>
             <div class="modal-header">
                 <h1 class="modal-title fs-5">{"Создать чат"}</h1>
             </div>
             <div class="modal-body">
-                <div class="input-group p-1">
-                    <span class="input-group-text">{"Имя чата"}</span>
-                    <input value={chat_name.clone()} ref={new_chat_input_node} type="text" class="form-control" aria-label="chat_name" required=true />
-                </div>
+                <FormField
+                    id="new-chat-name-input"
+                    label="Имя чата"
+                    value={(*chat_name_state).clone()}
+                    on_input={
+                        let chat_name_state = chat_name_state.clone();
+
+                        Callback::from(move |value: String| chat_name_state.set(value))
+                    }
+                    error={chat_name_error}
+                />
                 <div class="input-group mb-3 p-1">
                     <label class="input-group-text">{"Загрузить иконку"}</label>
                     <input oninput={on_file_upload} type="file" ref={new_chat_icon_node} class="form-control" accept="image/png, image/jpeg, image/jpg" />
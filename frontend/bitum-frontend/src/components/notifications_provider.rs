@@ -0,0 +1,354 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+};
+
+use bitum_frontend::markdown_to_plain_text;
+use gloo::events::EventListener;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{Notification, NotificationOptions, NotificationPermission};
+use yew::prelude::*;
+use yew_router::prelude::{use_navigator, Navigator};
+
+use crate::{
+    api::{ChatMessage, EventType},
+    components::{known_direct_chat_peer, use_current_user, use_events},
+    constants::{NOTIFICATIONS_STORAGE_KEY, NOTIFICATION_EXCERPT_MAX_CHARS},
+    routes::Route,
+    session::{load_account_data, save_account_data},
+};
+
+/// Какие сообщения чата показывать в уведомлениях
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatNotificationMode {
+    #[default]
+    All,
+    MentionsOnly,
+    Muted,
+}
+
+impl ChatNotificationMode {
+    pub const ALL: [ChatNotificationMode; 3] = [
+        ChatNotificationMode::All,
+        ChatNotificationMode::MentionsOnly,
+        ChatNotificationMode::Muted,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChatNotificationMode::All => "Все сообщения",
+            ChatNotificationMode::MentionsOnly => "Только упоминания",
+            ChatNotificationMode::Muted => "Без уведомлений",
+        }
+    }
+
+    pub fn icon_class(&self) -> &'static str {
+        match self {
+            ChatNotificationMode::All => "bi-bell",
+            ChatNotificationMode::MentionsOnly => "bi-at",
+            ChatNotificationMode::Muted => "bi-bell-slash",
+        }
+    }
+}
+
+/// Настройки уведомлений, хранятся в localStorage
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    pub chat_modes: HashMap<i32, ChatNotificationMode>,
+}
+
+impl NotificationSettings {
+    pub fn load(user_id: Option<i32>) -> Self {
+        load_account_data(NOTIFICATIONS_STORAGE_KEY, user_id)
+    }
+
+    pub fn save(&self, user_id: Option<i32>) {
+        save_account_data(NOTIFICATIONS_STORAGE_KEY, user_id, self);
+    }
+
+    pub fn chat_mode(&self, chat_id: i32) -> ChatNotificationMode {
+        self.chat_modes.get(&chat_id).cloned().unwrap_or_default()
+    }
+
+    pub fn is_muted(&self, chat_id: i32) -> bool {
+        self.chat_mode(chat_id) == ChatNotificationMode::Muted
+    }
+
+    /// Чаты в режиме "без уведомлений", их непрочитанные не учитываются в общем счётчике
+    pub fn muted_chats(&self) -> BTreeSet<i32> {
+        self.chat_modes
+            .iter()
+            .filter(|(_, mode)| **mode == ChatNotificationMode::Muted)
+            .map(|(chat_id, _)| *chat_id)
+            .collect()
+    }
+
+    pub fn with_chat_mode(&self, chat_id: i32, mode: ChatNotificationMode) -> Self {
+        let mut settings = self.clone();
+
+        if mode == ChatNotificationMode::default() {
+            settings.chat_modes.remove(&chat_id);
+        } else {
+            settings.chat_modes.insert(chat_id, mode);
+        }
+
+        settings
+    }
+
+    /// Заменяет режимы всех чатов, например при импорте настроек
+    pub fn with_chat_modes(&self, chat_modes: HashMap<i32, ChatNotificationMode>) -> Self {
+        Self {
+            chat_modes,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_enabled(&self, enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..self.clone()
+        }
+    }
+
+    fn should_notify(&self, message: &ChatMessage, username: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        match self.chat_mode(message.chat.id) {
+            ChatNotificationMode::All => true,
+            ChatNotificationMode::MentionsOnly => is_mentioned(&message.content, username),
+            ChatNotificationMode::Muted => false,
+        }
+    }
+}
+
+pub type NotificationSettingsContext = UseStateHandle<NotificationSettings>;
+
+/// Упоминание `@username` целым словом: `@bob` не срабатывает на `@bobby` и `mail@bob`
+fn is_mentioned(content: &str, username: &str) -> bool {
+    let content = content.to_lowercase();
+    let mention = format!("@{}", username.to_lowercase());
+    let is_word_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+
+    content.match_indices(&mention).any(|(start, _)| {
+        let before = content[..start].chars().next_back();
+        let after = content[start + mention.len()..].chars().next();
+
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+/// Есть ли в браузере Notification API. В некоторых WebView его нет,
+/// и обращение к `Notification` бросает исключение
+pub fn notifications_supported() -> bool {
+    js_sys::Reflect::has(&gloo_utils::window(), &"Notification".into()).unwrap_or(false)
+}
+
+pub fn notifications_permission_granted() -> bool {
+    notifications_supported() && Notification::permission() == NotificationPermission::Granted
+}
+
+/// Запрашивает у браузера разрешение на уведомления
+pub async fn request_notifications_permission() -> bool {
+    if !notifications_supported() {
+        return false;
+    }
+
+    if let Ok(promise) = Notification::request_permission() {
+        let _ = JsFuture::from(promise).await;
+    }
+
+    notifications_permission_granted()
+}
+
+#[derive(PartialEq, Properties)]
+pub struct NotificationsProviderProps {
+    pub children: Children,
+}
+
+#[function_component]
+pub fn NotificationsProvider(props: &NotificationsProviderProps) -> Html {
+    let NotificationsProviderProps { children } = props;
+
+    let user = use_current_user();
+    let user_id = user.as_ref().map(|user| user.id);
+    let settings = use_state(|| NotificationSettings::load(user_id));
+    let navigator = use_navigator().unwrap();
+
+    {
+        let settings = settings.clone();
+
+        use_events(move |event| {
+            let (EventType::NewMessage(message), Some(user)) = (event, user.as_ref()) else {
+                return;
+            };
+
+            if message.sender.id == user.id
+                || !gloo_utils::document().hidden()
+                || !notifications_permission_granted()
+                || !settings.should_notify(message, &user.username)
+            {
+                return;
+            }
+
+            // В личном чате вместо названия чата показываем собеседника
+            let title = if known_direct_chat_peer(&message.chat, user.id).is_some() {
+                message.sender.username.clone()
+            } else {
+                message.chat.name.clone()
+            };
+
+            show_message_notification(&title, message, navigator.clone());
+        });
+    }
+
+    use_effect_with_deps(move |settings| settings.save(user_id), (*settings).clone());
+
+    html! {
+        <ContextProvider<NotificationSettingsContext> context={settings}>
+            { for children.iter() }
+        </ContextProvider<NotificationSettingsContext>>
+    }
+}
+
+thread_local! {
+    /// Обработчики кликов по показанным уведомлениям, по одному на чат
+    static CLICK_LISTENERS: RefCell<HashMap<i32, EventListener>> = RefCell::new(HashMap::new());
+}
+
+fn show_message_notification(title: &str, message: &ChatMessage, navigator: Navigator) {
+    let options = NotificationOptions::new();
+    options.set_body(&format!(
+        "{}: {}",
+        message.sender.username,
+        markdown_to_plain_text(&message.content, NOTIFICATION_EXCERPT_MAX_CHARS)
+    ));
+    // Новое уведомление из того же чата заменяет предыдущее
+    options.set_tag(&format!("chat-{}", message.chat.id));
+
+    if let Some(icon) = &message.chat.icon {
+        options.set_icon(&format!("/api/files/{}", icon));
+    }
+
+    let Ok(notification) = Notification::new_with_options(title, &options) else {
+        return;
+    };
+
+    let chat_id = message.chat.id;
+    let target = notification.clone();
+
+    let listener = EventListener::once(&target, "click", move |_| {
+        let _ = gloo_utils::window().focus();
+        navigator.push(&Route::Chat { id: chat_id });
+        notification.close();
+    });
+
+    // Уведомление с тем же тегом заменяет предыдущее, вместе с ним отписываемся и от старого
+    CLICK_LISTENERS.with(|listeners| {
+        listeners.borrow_mut().insert(chat_id, listener);
+    });
+}
+
+/// Включает и выключает уведомления в браузере
+#[function_component]
+pub fn NotificationsToggle() -> Html {
+    let settings = use_context::<NotificationSettingsContext>();
+
+    let Some(settings) = settings.filter(|_| notifications_supported()) else {
+        return html! {};
+    };
+
+    let is_enabled = settings.enabled && notifications_permission_granted();
+
+    let onclick = {
+        let settings = settings.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            let settings = settings.clone();
+
+            if is_enabled {
+                settings.set(settings.with_enabled(false));
+                return;
+            }
+
+            spawn_local(async move {
+                let granted = request_notifications_permission().await;
+                settings.set(settings.with_enabled(granted));
+            });
+        })
+    };
+
+    html! {
+        <i
+            {onclick}
+            role="button"
+            class={classes!("bi", "fs-4", "p-2", "info-hover", if is_enabled { "bi-bell-fill" } else { "bi-bell-slash" })}
+            title={if is_enabled { "Уведомления включены" } else { "Включить уведомления" }}
+        ></i>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ChatNotificationModeSelectProps {
+    pub chat_id: i32,
+}
+
+/// Выбор режима уведомлений для одного чата
+#[function_component]
+pub fn ChatNotificationModeSelect(props: &ChatNotificationModeSelectProps) -> Html {
+    let ChatNotificationModeSelectProps { chat_id } = props;
+
+    let settings = use_context::<NotificationSettingsContext>();
+
+    let Some(settings) = settings else {
+        return html! {};
+    };
+
+    let current_mode = settings.chat_mode(*chat_id);
+
+    html! {
+        <div class="dropdown d-inline-block">
+            <i role="button" data-bs-toggle="dropdown" aria-expanded="false" title={current_mode.name()} class={classes!("bi", current_mode.icon_class(), "fs-3", "pe-2", "info-hover")}></i>
+            <ul class="dropdown-menu">
+                { for ChatNotificationMode::ALL.into_iter().map(|mode| {
+                    let settings = settings.clone();
+                    let chat_id = *chat_id;
+
+                    html! {
+                        <li>
+                            <button
+                                type="button"
+                                class={classes!("dropdown-item", (mode == current_mode).then_some("active"))}
+                                onclick={Callback::from(move |_: MouseEvent| settings.set(settings.with_chat_mode(chat_id, mode)))}
+                            >
+                                <i class={classes!("bi", mode.icon_class())}></i>
+                                {" "}
+                                {mode.name()}
+                            </button>
+                        </li>
+                    }
+                })}
+            </ul>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mention_matches_whole_username() {
+        assert!(is_mentioned("@bob привет", "bob"));
+        assert!(is_mentioned("привет, @Bob!", "bob"));
+        assert!(is_mentioned("@bob", "bob"));
+        assert!(!is_mentioned("@bobby привет", "bob"));
+        assert!(!is_mentioned("@bob_2 привет", "bob"));
+        assert!(!is_mentioned("mail@bob.com", "bob"));
+        assert!(is_mentioned("@bobby и @bob", "bob"));
+    }
+}
@@ -0,0 +1,31 @@
+use yew::prelude::*;
+
+use crate::validation::password_strength;
+
+#[derive(PartialEq, Properties)]
+pub struct PasswordStrengthMeterProps {
+    pub password: String,
+}
+
+#[function_component]
+pub fn PasswordStrengthMeter(props: &PasswordStrengthMeterProps) -> Html {
+    let PasswordStrengthMeterProps { password } = props;
+
+    if password.is_empty() {
+        return html! {};
+    }
+
+    let strength = password_strength(password);
+
+    html! {
+        <div class="pt-2">
+            <div class="progress" style="height: 6px;" role="progressbar" aria-label="Надёжность пароля">
+                <div
+                    class={classes!("progress-bar", strength.color_class())}
+                    style={format!("width: {}%", strength.percent())}
+                ></div>
+            </div>
+            <div class="form-text">{format!("Надёжность: {}", strength.name().to_lowercase())}</div>
+        </div>
+    }
+}
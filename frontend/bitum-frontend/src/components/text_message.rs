@@ -9,12 +9,6 @@ pub struct TextMessageProps {
     pub toast_classes: Classes,
 }
 
-#[derive(Clone, Debug)]
-pub struct TextMessageState {
-    pub value: String,
-    pub classes: Classes,
-}
-
 #[function_component]
 pub fn TextMessage(props: &TextMessageProps) -> Html {
     let TextMessageProps {
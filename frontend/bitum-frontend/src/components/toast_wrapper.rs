@@ -17,13 +17,13 @@ pub fn Toast(props: &ToastProps) -> Html {
 
     let toast_host = gloo::utils::document()
         .get_element_by_id(container_id)
-        .expect(&format!("Expected to find #{} element", container_id));
+        .unwrap_or_else(|| panic!("Expected to find #{} element", container_id));
 
     create_portal(
         html! {
             if *is_visible { <>{for children.iter()}</> }
             else {<></>}
         },
-        toast_host.into(),
+        toast_host,
     )
 }
@@ -0,0 +1,85 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::components::Modal;
+
+#[derive(PartialEq, Properties)]
+pub struct TypedConfirmModalProps {
+    pub modal_id: String,
+    pub is_visible: bool,
+    pub title: String,
+    pub description: String,
+    /// Текст, который пользователь должен ввести для подтверждения
+    pub confirmation_text: String,
+    pub on_confirm: Callback<()>,
+    pub on_close: Callback<()>,
+}
+
+#[function_component]
+pub fn TypedConfirmModal(props: &TypedConfirmModalProps) -> Html {
+    let TypedConfirmModalProps {
+        modal_id,
+        is_visible,
+        title,
+        description,
+        confirmation_text,
+        on_confirm,
+        on_close,
+    } = props;
+
+    let typed_text_state = use_state(String::new);
+
+    let on_input = {
+        let typed_text_state = typed_text_state.clone();
+
+        Callback::from(move |event: InputEvent| {
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+            typed_text_state.set(input.value());
+        })
+    };
+
+    let on_modal_close = {
+        let typed_text_state = typed_text_state.clone();
+        let on_close = on_close.clone();
+
+        Callback::from(move |_: ()| {
+            typed_text_state.set(String::new());
+            on_close.emit(());
+        })
+    };
+
+    let on_ok = {
+        let typed_text_state = typed_text_state.clone();
+        let confirmation_text = confirmation_text.clone();
+        let on_confirm = on_confirm.clone();
+
+        Callback::from(move |_: ()| {
+            if *typed_text_state == confirmation_text {
+                on_confirm.emit(());
+            }
+        })
+    };
+
+    html! {
+        <Modal
+            modal_id={modal_id.clone()}
+            is_visible={*is_visible}
+            on_ok={on_ok}
+            on_cancel={Callback::from(|_| {})}
+            on_close={on_modal_close}
+            ok_disabled={*typed_text_state != *confirmation_text}
+        >
+            <div class="modal-header">
+                <h1 class="modal-title fs-5">{title.clone()}</h1>
+            </div>
+            <div class="modal-body">
+                <p>{description.clone()}</p>
+                <p>
+                    {"Для подтверждения введите "}
+                    <span class="fw-semibold">{confirmation_text.clone()}</span>
+                </p>
+                <input oninput={on_input} value={(*typed_text_state).clone()} type="text" class="form-control" aria-label="confirmation" />
+            </div>
+        </Modal>
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    rc::Rc,
+};
+
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::{
+    api::EventType,
+    components::{use_current_user, use_events, NotificationSettingsContext},
+    constants::UNREAD_STORAGE_KEY,
+    session::{load_account_data, save_account_data},
+};
+
+const DOCUMENT_TITLE: &str = "Bitum";
+
+/// Последние прочитанные сообщения и счётчики непрочитанных по чатам.
+/// Хранится в localStorage, чтобы переживать перезагрузку страницы.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UnreadState {
+    pub last_read: HashMap<i32, i32>,
+    pub unread_counts: HashMap<i32, u32>,
+    #[serde(skip)]
+    pub open_chat: Option<i32>,
+}
+
+pub enum UnreadAction {
+    MessageReceived {
+        chat_id: i32,
+        message_id: i32,
+        is_own: bool,
+    },
+    MarkRead {
+        chat_id: i32,
+        message_id: i32,
+    },
+    ChatOpened(i32),
+    ChatClosed(i32),
+}
+
+impl UnreadState {
+    pub fn load(user_id: Option<i32>) -> Self {
+        load_account_data(UNREAD_STORAGE_KEY, user_id)
+    }
+
+    pub fn save(&self, user_id: Option<i32>) {
+        save_account_data(UNREAD_STORAGE_KEY, user_id, self);
+    }
+
+    pub fn last_read(&self, chat_id: i32) -> Option<i32> {
+        self.last_read.get(&chat_id).cloned()
+    }
+
+    pub fn unread_count(&self, chat_id: i32) -> u32 {
+        self.unread_counts
+            .get(&chat_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Сумма непрочитанных без заглушённых чатов
+    pub fn total_unread_count(&self, muted: &BTreeSet<i32>) -> u32 {
+        self.unread_counts
+            .iter()
+            .filter(|(chat_id, _)| !muted.contains(chat_id))
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    fn mark_read(&mut self, chat_id: i32, message_id: i32) {
+        let last_read = self.last_read.entry(chat_id).or_default();
+        *last_read = (*last_read).max(message_id);
+        self.unread_counts.remove(&chat_id);
+    }
+}
+
+impl Reducible for UnreadState {
+    type Action = UnreadAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+
+        match action {
+            UnreadAction::MessageReceived {
+                chat_id,
+                message_id,
+                is_own,
+            } => {
+                if is_own || state.open_chat == Some(chat_id) {
+                    state.mark_read(chat_id, message_id);
+                } else if message_id > state.last_read(chat_id).unwrap_or_default() {
+                    *state.unread_counts.entry(chat_id).or_default() += 1;
+                }
+            }
+            UnreadAction::MarkRead {
+                chat_id,
+                message_id,
+            } => {
+                if state.last_read(chat_id) >= Some(message_id) && state.unread_count(chat_id) == 0
+                {
+                    return self;
+                }
+
+                state.mark_read(chat_id, message_id);
+            }
+            UnreadAction::ChatOpened(chat_id) => state.open_chat = Some(chat_id),
+            UnreadAction::ChatClosed(chat_id) => {
+                if state.open_chat == Some(chat_id) {
+                    state.open_chat = None;
+                }
+            }
+        }
+
+        Rc::new(state)
+    }
+}
+
+pub type UnreadContext = UseReducerHandle<UnreadState>;
+
+#[derive(PartialEq, Properties)]
+pub struct UnreadProviderProps {
+    pub children: Children,
+}
+
+#[function_component]
+pub fn UnreadProvider(props: &UnreadProviderProps) -> Html {
+    let UnreadProviderProps { children } = props;
+
+    let user_id = use_current_user().map(|user| user.id);
+    let unread = use_reducer(|| UnreadState::load(user_id));
+    let muted = use_context::<NotificationSettingsContext>()
+        .map(|settings| settings.muted_chats())
+        .unwrap_or_default();
+
+    {
+        let unread = unread.clone();
+
+        use_events(move |event| {
+            if let EventType::NewMessage(message) = event {
+                unread.dispatch(UnreadAction::MessageReceived {
+                    chat_id: message.chat.id,
+                    message_id: message.id,
+                    is_own: Some(message.sender.id) == user_id,
+                });
+            }
+        });
+    }
+
+    use_effect_with_deps(
+        move |(unread, muted)| {
+            unread.save(user_id);
+
+            let total_unread_count = unread.total_unread_count(muted);
+            let title = if total_unread_count > 0 {
+                format!("({}) {}", total_unread_count, DOCUMENT_TITLE)
+            } else {
+                DOCUMENT_TITLE.to_string()
+            };
+
+            gloo_utils::document().set_title(&title);
+        },
+        ((*unread).clone(), muted),
+    );
+
+    html! {
+        <ContextProvider<UnreadContext> context={unread}>
+            { for children.iter() }
+        </ContextProvider<UnreadContext>>
+    }
+}
+
+/// Отмечает чат открытым, пока компонент смонтирован
+#[hook]
+pub fn use_open_chat(chat_id: i32) {
+    let unread = use_context::<UnreadContext>();
+
+    use_effect_with_deps(
+        move |chat_id| {
+            let chat_id = *chat_id;
+
+            if let Some(unread) = unread.as_ref() {
+                unread.dispatch(UnreadAction::ChatOpened(chat_id));
+            }
+
+            move || {
+                if let Some(unread) = unread {
+                    unread.dispatch(UnreadAction::ChatClosed(chat_id));
+                }
+            }
+        },
+        chat_id,
+    );
+}
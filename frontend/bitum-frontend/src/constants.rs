@@ -1 +1,16 @@
 pub const API_REFRESH_MILLIS: u32 = 1000;
+pub const MAX_READ_EVENTS_PER_REQUEST: usize = 256;
+pub const UNREAD_STORAGE_KEY: &str = "bitum.unread";
+pub const NOTIFICATIONS_STORAGE_KEY: &str = "bitum.notifications";
+pub const NOTIFICATION_EXCERPT_MAX_CHARS: usize = 120;
+pub const CHAT_ORGANIZATION_STORAGE_KEY: &str = "bitum.chat_organization";
+pub const SEARCH_DEBOUNCE_MILLIS: u32 = 300;
+pub const MEMBER_SEARCH_PAGE_SIZE: i32 = 20;
+pub const BOT_DIRECTORY_PAGE_SIZE: i32 = 12;
+pub const PREFERENCES_STORAGE_KEY: &str = "bitum.preferences";
+pub const AVATAR_SIZE: u32 = 256;
+pub const API_TOKEN_COOKIE_NAME: &str = "api_token";
+pub const COPIED_FEEDBACK_MILLIS: u32 = 1500;
+pub const BOT_TOKEN_VISIBLE_MILLIS: u32 = 60_000;
+pub const DIRECT_CHAT_PEER_TTL_MILLIS: u32 = 300_000;
+pub const DIRECT_CHATS_STORAGE_KEY: &str = "bitum.direct_chats";
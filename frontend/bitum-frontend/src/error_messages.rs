@@ -0,0 +1,158 @@
+use crate::api::ApiError;
+
+pub const SERVER_UNAVAILABLE: &str = "Сервер не отвечает";
+
+const BAD_REQUEST: i32 = 400;
+pub const UNAUTHORIZED: i32 = 401;
+pub const FORBIDDEN: i32 = 403;
+pub const NOT_FOUND: i32 = 404;
+const CONFLICT: i32 = 409;
+const TOO_MANY_REQUESTS: i32 = 429;
+const NOT_IMPLEMENTED: i32 = 501;
+
+/// Действия с API, для которых сервер может вернуть ошибку
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ApiAction {
+    LoadChat,
+    LoadSelf,
+    LoadMessages,
+    LoadMembers,
+    SendMessage,
+    AddMember,
+    KickMember,
+    LeaveChat,
+    EditPermissions,
+    EditChat,
+    TransferOwnership,
+    DeleteChat,
+    UploadFile,
+    OpenDirectChat,
+    LoadUser,
+    EditAccount,
+    RequestPasswordReset,
+    ResetPassword,
+    Login,
+    Register,
+    LoadBots,
+    CreateBot,
+    EditBot,
+    DeleteBot,
+    LoadBotToken,
+    ConnectBot,
+    LoadChats,
+    LoadEvents,
+    ReadEvents,
+}
+
+impl ApiAction {
+    /// Сообщение об ошибке для пользователя по ответу сервера
+    pub fn error_message(&self, error: Option<&ApiError>) -> String {
+        let code = error.map(|error| error.code);
+
+        let message = match (self, code) {
+            (ApiAction::Login, Some(NOT_FOUND)) => "Такого пользователя нет",
+            (ApiAction::Login, Some(UNAUTHORIZED)) => "Неверный пароль",
+            (ApiAction::Login, Some(BAD_REQUEST)) => "Проверьте имя пользователя и пароль",
+            (ApiAction::Login, _) => "Не удалось войти",
+
+            (ApiAction::Register, Some(CONFLICT)) => "Пользователь с таким именем уже есть",
+            (ApiAction::Register, Some(BAD_REQUEST)) => "Проверьте введённые данные",
+            (ApiAction::Register, _) => "Не удалось зарегистрироваться",
+
+            (
+                ApiAction::ConnectBot | ApiAction::LoadEvents | ApiAction::ReadEvents,
+                Some(UNAUTHORIZED),
+            ) => "Токен бота недействителен или устарел",
+            (ApiAction::ConnectBot, _) => "Не удалось подключиться с этим токеном",
+
+            (_, Some(UNAUTHORIZED)) => "Необходимо войти в аккаунт",
+
+            (ApiAction::LoadChat, Some(NOT_FOUND)) => "Чат не найден",
+            (ApiAction::LoadChat, _) => "Не удалось получить информацию о чате",
+            (ApiAction::LoadSelf, Some(NOT_FOUND)) => "Вы не состоите в этом чате",
+            (ApiAction::LoadSelf, _) => "Не удалось получить информацию о себе",
+            (ApiAction::LoadMessages, _) => "Не удалось получить сообщения",
+            (ApiAction::LoadMembers, _) => "Не удалось получить список участников",
+            (ApiAction::LoadChats, _) => "Не удалось получить список чатов",
+            (ApiAction::LoadEvents, _) => "Не удалось получить события",
+            (ApiAction::ReadEvents, Some(BAD_REQUEST)) => "Сервер не принял список событий",
+            (ApiAction::ReadEvents, _) => "Не удалось отметить события прочитанными",
+
+            (ApiAction::SendMessage, Some(FORBIDDEN)) => "У вас нет прав писать в этот чат",
+            (ApiAction::SendMessage, _) => "Не удалось отправить сообщение",
+
+            (ApiAction::AddMember, Some(FORBIDDEN)) => "У вас нет прав добавлять участников",
+            (ApiAction::AddMember, Some(NOT_FOUND)) => "Такого пользователя нет",
+            (ApiAction::AddMember, Some(CONFLICT)) => "Этот пользователь уже состоит в этом чате",
+            (ApiAction::AddMember, _) => "Не удалось добавить пользователя в чат",
+
+            (ApiAction::KickMember, Some(FORBIDDEN)) => "У вас нет прав удалять участников",
+            (ApiAction::KickMember, Some(NOT_FOUND)) => "Этот пользователь уже не состоит в чате",
+            (ApiAction::KickMember, _) => "Не удалось удалить участника",
+
+            (ApiAction::LeaveChat, Some(FORBIDDEN)) => {
+                "Владелец не может покинуть чат. Сначала передайте его другому участнику"
+            }
+            (ApiAction::LeaveChat, _) => "Не удалось покинуть чат",
+
+            (ApiAction::EditPermissions, Some(FORBIDDEN)) => {
+                "Изменять права участников может только владелец чата"
+            }
+            (ApiAction::EditPermissions, _) => "Не удалось изменить права участника",
+
+            (ApiAction::EditChat, Some(FORBIDDEN)) => "Изменять чат может только его владелец",
+            (ApiAction::EditChat, _) => "Не удалось изменить чат",
+
+            (ApiAction::TransferOwnership, Some(FORBIDDEN)) => {
+                "Передать чат может только его владелец"
+            }
+            (ApiAction::TransferOwnership, Some(NOT_FOUND)) => "Такого пользователя нет",
+            (ApiAction::TransferOwnership, _) => "Не удалось передать права владельца",
+
+            (ApiAction::DeleteChat, Some(FORBIDDEN)) => "Удалить чат может только его владелец",
+            (ApiAction::DeleteChat, _) => "Не удалось удалить чат",
+
+            (ApiAction::UploadFile, Some(BAD_REQUEST)) => "Файл слишком большой",
+            (ApiAction::UploadFile, _) => "Не удалось загрузить файл",
+
+            (ApiAction::OpenDirectChat, Some(NOT_FOUND)) => "Такого пользователя нет",
+            (ApiAction::OpenDirectChat, _) => "Не удалось открыть личный чат",
+
+            (ApiAction::LoadUser, Some(NOT_FOUND)) => "Такого пользователя нет",
+            (ApiAction::LoadUser, _) => "Не удалось получить информацию о пользователе",
+
+            (ApiAction::LoadBots, _) => "Не удалось получить список ботов",
+            (ApiAction::CreateBot, Some(CONFLICT)) => "Имя уже занято",
+            (ApiAction::CreateBot, Some(BAD_REQUEST)) => "Проверьте имя бота",
+            (ApiAction::CreateBot, _) => "Не удалось создать бота",
+            (ApiAction::EditBot, Some(NOT_FOUND)) => "Это не ваш бот",
+            (ApiAction::EditBot, _) => "Не удалось изменить бота",
+            (ApiAction::DeleteBot, Some(NOT_FOUND)) => "Это не ваш бот",
+            (ApiAction::DeleteBot, _) => "Не удалось удалить бота",
+            (ApiAction::LoadBotToken, Some(NOT_FOUND)) => "Это не ваш бот",
+            (ApiAction::LoadBotToken, _) => "Не удалось получить токен бота",
+
+            (ApiAction::EditAccount, Some(BAD_REQUEST)) => "Проверьте введённые данные",
+            (ApiAction::EditAccount, _) => "Не удалось изменить настройки аккаунта",
+
+            (ApiAction::RequestPasswordReset, Some(NOT_FOUND)) => "Такого пользователя нет",
+            (ApiAction::RequestPasswordReset, Some(FORBIDDEN)) => {
+                "Пароль можно сбросить только у пользователя, не у бота"
+            }
+            (ApiAction::RequestPasswordReset, Some(TOO_MANY_REQUESTS)) => {
+                "Сброс уже запрашивали, попробуйте через минуту"
+            }
+            (ApiAction::RequestPasswordReset | ApiAction::ResetPassword, Some(NOT_IMPLEMENTED)) => {
+                "На этом сервере сброс пароля недоступен"
+            }
+            (ApiAction::RequestPasswordReset, _) => "Не удалось запросить сброс пароля",
+            (ApiAction::ResetPassword, Some(NOT_FOUND)) => {
+                "Ссылка для сброса пароля недействительна или устарела"
+            }
+            (ApiAction::ResetPassword, Some(BAD_REQUEST)) => "Проверьте новый пароль",
+            (ApiAction::ResetPassword, _) => "Не удалось сменить пароль",
+        };
+
+        message.to_string()
+    }
+}
@@ -3,8 +3,7 @@ use pulldown_cmark::{html, Event, Options, Parser};
 use rand::{Rng, SeedableRng};
 
 pub fn display_timestamp_date(timestamp: i64) -> String {
-    let naive_date_time = NaiveDateTime::from_timestamp_opt(timestamp, 0).unwrap();
-    let date_time: DateTime<Utc> = DateTime::from_utc(naive_date_time, Utc);
+    let date_time: DateTime<Utc> = DateTime::from_timestamp(timestamp, 0).unwrap();
 
     format!(
         "{:02}/{:02}/{:02}",
@@ -14,6 +13,40 @@ pub fn display_timestamp_date(timestamp: i64) -> String {
     )
 }
 
+/// Время в 24- или 12-часовом формате
+pub fn display_timestamp_time(timestamp: i64, hour12: bool) -> String {
+    let date_time = Local.timestamp_opt(timestamp, 0).unwrap();
+
+    if hour12 {
+        let (is_pm, hour) = date_time.hour12();
+
+        format!(
+            "{}:{:02} {}",
+            hour,
+            date_time.minute(),
+            if is_pm { "PM" } else { "AM" }
+        )
+    } else {
+        format!("{:02}:{:02}", date_time.hour(), date_time.minute())
+    }
+}
+
+/// Время, если метка сегодняшняя, иначе дата
+pub fn display_timestamp_short(timestamp: i64, hour12: bool) -> String {
+    let date_time = Local.timestamp_opt(timestamp, 0).unwrap();
+
+    if date_time.date_naive() == Local::now().date_naive() {
+        display_timestamp_time(timestamp, hour12)
+    } else {
+        format!(
+            "{:02}/{:02}/{:02}",
+            date_time.day(),
+            date_time.month(),
+            date_time.year()
+        )
+    }
+}
+
 pub fn parse_markdown_to_html(markdown: String) -> String {
     let escaped_html_markdown = html_escape::encode_safe(&markdown);
 
@@ -36,6 +69,29 @@ pub fn parse_markdown_to_html(markdown: String) -> String {
     html_buf
 }
 
+/// Текст сообщения без разметки, обрезанный до `max_chars` символов
+pub fn markdown_to_plain_text(markdown: &str, max_chars: usize) -> String {
+    let mut text = String::new();
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(part) | Event::Code(part) => text.push_str(&part),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.chars().count() > max_chars {
+        let mut truncated: String = text.chars().take(max_chars).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        text
+    }
+}
+
 // https://singlecolorimage.com/api.html
 const SINGLE_COLOR_IMAGE_API_URL: &str = "https://singlecolorimage.com/get/";
 
@@ -43,7 +99,7 @@ pub fn get_random_color_image_url(seed_str: String, width: i32, height: i32) ->
     let seed: u64 = seed_str
         .as_bytes()
         .iter()
-        .map(|byte| byte.clone() as u64)
+        .map(|byte| *byte as u64)
         .sum::<u64>();
 
     let random_color = get_random_rgb_color(Some(seed));
@@ -68,3 +124,36 @@ pub fn get_random_rgb_color(seed: Option<u64>) -> (u8, u8, u8) {
         (rng.gen(), rng.gen(), rng.gen())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_to_plain_text_strips_formatting() {
+        assert_eq!(
+            markdown_to_plain_text("**жирный** и `код` [ссылка](https://example.com)", 100),
+            "жирный и код ссылка"
+        );
+    }
+
+    #[test]
+    fn markdown_to_plain_text_joins_lines_and_blocks() {
+        assert_eq!(
+            markdown_to_plain_text("# Заголовок\n\nпервая\nвторая\n\n- пункт", 100),
+            "Заголовок первая вторая пункт"
+        );
+    }
+
+    #[test]
+    fn markdown_to_plain_text_truncates_by_chars() {
+        assert_eq!(markdown_to_plain_text("привет мир", 6), "привет…");
+        assert_eq!(markdown_to_plain_text("привет", 6), "привет");
+    }
+
+    #[test]
+    fn markdown_to_plain_text_of_empty_message() {
+        assert_eq!(markdown_to_plain_text("", 10), "");
+        assert_eq!(markdown_to_plain_text("  \n\n  ", 10), "");
+    }
+}
@@ -1,13 +1,21 @@
 mod api;
 mod components;
 mod constants;
+mod error_messages;
 // pub mod lib;
+mod preferences;
 mod routes;
+mod session;
+mod validation;
 use routes::Route;
 use yew::prelude::*;
 use yew_router::{BrowserRouter, Switch};
 
-use crate::{components::ToastContainer, routes::switch};
+use crate::{
+    components::{AuthProvider, ToastContainer},
+    preferences::Preferences,
+    routes::switch,
+};
 
 pub const TOAST_CONTAINER_ID: &str = "toasts-container";
 
@@ -16,7 +24,9 @@ fn App() -> Html {
     html! {
         <div class="container">
             <BrowserRouter>
-                <Switch<Route> render={switch}/>
+                <AuthProvider>
+                    <Switch<Route> render={switch}/>
+                </AuthProvider>
             </BrowserRouter>
             <ToastContainer id={TOAST_CONTAINER_ID.to_string()}/>
         </div>
@@ -25,5 +35,6 @@ fn App() -> Html {
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
+    Preferences::load().apply();
     yew::Renderer::<App>::new().render();
 }
@@ -0,0 +1,96 @@
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::PREFERENCES_STORAGE_KEY;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+    System,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Light, Theme::Dark, Theme::System];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Light => "Светлая",
+            Theme::Dark => "Тёмная",
+            Theme::System => "Как в системе",
+        }
+    }
+
+    /// Значение для `data-bs-theme`
+    fn bootstrap_theme(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::System => {
+                let prefers_dark = gloo_utils::window()
+                    .match_media("(prefers-color-scheme: dark)")
+                    .ok()
+                    .flatten()
+                    .map(|media| media.matches())
+                    .unwrap_or(false);
+
+                if prefers_dark {
+                    "dark"
+                } else {
+                    "light"
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeFormat {
+    #[default]
+    Hours24,
+    Hours12,
+}
+
+impl TimeFormat {
+    pub const ALL: [TimeFormat; 2] = [TimeFormat::Hours24, TimeFormat::Hours12];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeFormat::Hours24 => "24 часа",
+            TimeFormat::Hours12 => "12 часов (AM/PM)",
+        }
+    }
+}
+
+/// Настройки интерфейса, которые хранятся только в браузере
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub theme: Theme,
+    pub time_format: TimeFormat,
+}
+
+impl Preferences {
+    pub fn load() -> Self {
+        LocalStorage::get(PREFERENCES_STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let _ = LocalStorage::set(PREFERENCES_STORAGE_KEY, self);
+        self.apply();
+    }
+
+    /// Применяет тему к корневому элементу страницы
+    pub fn apply(&self) {
+        if let Some(root) = gloo_utils::document().document_element() {
+            let _ = root.set_attribute("data-bs-theme", self.theme.bootstrap_theme());
+        }
+    }
+
+    pub fn hour12(&self) -> bool {
+        self.time_format == TimeFormat::Hours12
+    }
+}
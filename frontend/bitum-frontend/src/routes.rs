@@ -1,16 +1,32 @@
+pub mod api_explorer;
+pub mod bot;
+pub mod bot_playground;
+pub mod bots;
 pub mod chat;
+pub mod chat_settings;
 pub mod chats;
 pub mod index;
 pub mod login;
 pub mod my_bots;
 pub mod register;
+pub mod reset_password;
+pub mod settings;
+pub mod user_profile;
 
+use api_explorer::ApiExplorerRoute;
+use bot::BotRoute;
+use bot_playground::BotPlaygroundRoute;
+use bots::BotsRoute;
 use chat::ChatRoute;
+use chat_settings::ChatSettingsRoute;
 use chats::ChatsRoute;
 use index::IndexRoute;
 use login::LoginRoute;
 use my_bots::MyBotsRoute;
 use register::RegisterRoute;
+use reset_password::{RequestPasswordResetRoute, ResetPasswordRoute};
+use settings::SettingsRoute;
+use user_profile::UserProfileRoute;
 use yew::{html, Html};
 use yew_router::prelude::*;
 
@@ -24,17 +40,53 @@ pub enum Route {
     Chats,
     #[at("/chat/:id")]
     Chat { id: i32 },
+    #[at("/chat/:id/settings")]
+    ChatSettings { id: i32 },
     #[at("/login")]
     Login,
     #[at("/register")]
     Register,
+    #[at("/reset_password")]
+    RequestPasswordReset,
+    #[at("/reset_password/:code")]
+    ResetPassword { code: String },
+    #[at("/bots")]
+    Bots,
     #[at("/my_bots")]
     MyBots,
+    #[at("/my_bots/:id")]
+    Bot { id: i32 },
+    #[at("/my_bots/:id/playground")]
+    BotPlayground { id: i32 },
+    #[at("/api-explorer")]
+    ApiExplorer,
+    #[at("/settings")]
+    Settings,
+    #[at("/user/:username")]
+    UserProfile { username: String },
     #[not_found]
     #[at("/404")]
     NotFound,
 }
 
+/// Куда вернуть пользователя после входа. Принимает только пути внутри приложения
+pub fn redirect_target(redirect: Option<&str>) -> Route {
+    redirect
+        .filter(|path| path.starts_with('/') && !path.starts_with("//") && !path.contains('\\'))
+        .and_then(Route::recognize)
+        .filter(|route| {
+            !matches!(
+                route,
+                Route::NotFound
+                    | Route::Login
+                    | Route::Register
+                    | Route::RequestPasswordReset
+                    | Route::ResetPassword { .. }
+            )
+        })
+        .unwrap_or(Route::Chats)
+}
+
 pub fn switch(routes: Route) -> Html {
     match routes {
         Route::Index => html! {<IndexRoute/>},
@@ -43,18 +95,48 @@ pub fn switch(routes: Route) -> Html {
                 <ChatsRoute/>
             </LoginOrRedirect>
         },
+        // Ключ пересоздаёт страницу при переходе в другой чат, чтобы не осталось данных прошлого
         Route::Chat { id } => html! {
             <LoginOrRedirect>
-                <ChatRoute chat_id={id} />
+                <ChatRoute key={id} chat_id={id} />
+            </LoginOrRedirect>
+        },
+        Route::ChatSettings { id } => html! {
+            <LoginOrRedirect>
+                <ChatSettingsRoute chat_id={id} />
             </LoginOrRedirect>
         },
         Route::Login => html! {<LoginRoute />},
         Route::Register => html! {<RegisterRoute />},
+        Route::RequestPasswordReset => html! {<RequestPasswordResetRoute />},
+        Route::ResetPassword { code } => html! {<ResetPasswordRoute {code} />},
+        Route::Bots => html! {<BotsRoute />},
         Route::MyBots => html! {
             <LoginOrRedirect>
                 <MyBotsRoute />
             </LoginOrRedirect>
         },
+        Route::Bot { id } => html! {
+            <LoginOrRedirect>
+                <BotRoute bot_id={id} />
+            </LoginOrRedirect>
+        },
+        Route::BotPlayground { id } => html! {
+            <LoginOrRedirect>
+                <BotPlaygroundRoute bot_id={id} />
+            </LoginOrRedirect>
+        },
+        Route::ApiExplorer => html! {<ApiExplorerRoute />},
+        Route::Settings => html! {
+            <LoginOrRedirect>
+                <SettingsRoute />
+            </LoginOrRedirect>
+        },
+        Route::UserProfile { username } => html! {
+            <LoginOrRedirect>
+                <UserProfileRoute {username} />
+            </LoginOrRedirect>
+        },
         Route::NotFound => html! {<h1>{"Not found!"}</h1>},
     }
 }
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::{
+    api::{
+        explorer::{
+            build_json, build_query, call_endpoint, endpoints, ExplorerBody, FieldKind, FieldSpec,
+            RequestBody,
+        },
+        BotClient,
+    },
+    components::{ErrorMessage, Footer, FormField, Header},
+    error_messages::SERVER_UNAVAILABLE,
+};
+
+/// Что вернул сервер и как это разобрал типизированный клиент
+struct ExplorerResult {
+    status: u16,
+    raw: String,
+    decoded: Result<String, String>,
+}
+
+fn field_input(field: &FieldSpec, values: &UseStateHandle<HashMap<String, String>>) -> Html {
+    let value = values.get(field.name).cloned().unwrap_or_default();
+    let id = format!("explorer-field-{}", field.name);
+    let name = field.name.to_string();
+
+    let on_value = {
+        let values = values.clone();
+
+        move |value: String| {
+            let mut new_values = (*values).clone();
+            new_values.insert(name.clone(), value);
+            values.set(new_values);
+        }
+    };
+
+    let label = html! {
+        <>
+        <span class="font-monospace">{field.name}</span>
+        if field.optional {
+            <span class="text-body-secondary">{" (необязательно)"}</span>
+        }
+        </>
+    };
+
+    if field.kind == FieldKind::Boolean {
+        let checked = value == "true";
+
+        return html! {
+            <div class="col-12">
+                <div class="form-check">
+                    <input
+                        id={id.clone()}
+                        class="form-check-input"
+                        type="checkbox"
+                        {checked}
+                        onchange={Callback::from(move |_: Event| on_value((!checked).to_string()))}
+                    />
+                    <label class="form-check-label" for={id}>{label}</label>
+                </div>
+                <div class="form-text">{field.description}</div>
+            </div>
+        };
+    }
+
+    let (input_type, placeholder) = match field.kind {
+        FieldKind::Integer => ("number", "0"),
+        FieldKind::TextList => ("text", "a, b, c"),
+        FieldKind::IntegerList => ("text", "1, 2, 3"),
+        _ => ("text", ""),
+    };
+
+    html! {
+        <div class="col-12">
+            <label class="fw-medium" for={id.clone()}>{label}</label>
+            <input
+                {id}
+                class="form-control"
+                type={input_type}
+                {placeholder}
+                {value}
+                oninput={Callback::from(move |event: InputEvent| {
+                    on_value(event.target_unchecked_into::<HtmlInputElement>().value())
+                })}
+            />
+            <div class="form-text">{field.description}</div>
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ApiExplorerRouteProps {}
+
+/// Все методы API с формами для запросов, в том числе от имени бота
+#[function_component]
+pub fn ApiExplorerRoute(props: &ApiExplorerRouteProps) -> Html {
+    let ApiExplorerRouteProps {} = props;
+
+    let endpoints = use_memo(|_| endpoints(), ());
+    let error_message_state = use_state(|| Option::<String>::None);
+    let selected_state = use_state(|| 0usize);
+    let token_state = use_state(String::new);
+    let path_values = use_state(HashMap::<String, String>::new);
+    let query_values = use_state(HashMap::<String, String>::new);
+    let body_values = use_state(HashMap::<String, String>::new);
+    let file_state = use_state(|| Option::<web_sys::File>::None);
+    let is_sending = use_state(|| false);
+    let result_state = use_state(|| Option::<ExplorerResult>::None);
+
+    let endpoint = &endpoints[*selected_state];
+
+    let body_preview = match &endpoint.body {
+        RequestBody::Json { fields, check, .. } => Some(
+            build_json(fields, &body_values)
+                .and_then(check)
+                .map(|value| serde_json::to_string_pretty(&value).unwrap_or_default()),
+        ),
+        _ => None,
+    };
+
+    let on_select = {
+        let selected_state = selected_state.clone();
+        let path_values = path_values.clone();
+        let query_values = query_values.clone();
+        let body_values = body_values.clone();
+        let file_state = file_state.clone();
+        let result_state = result_state.clone();
+
+        move |index: usize| {
+            let selected_state = selected_state.clone();
+            let path_values = path_values.clone();
+            let query_values = query_values.clone();
+            let body_values = body_values.clone();
+            let file_state = file_state.clone();
+            let result_state = result_state.clone();
+
+            Callback::from(move |_: MouseEvent| {
+                selected_state.set(index);
+                path_values.set(HashMap::new());
+                query_values.set(HashMap::new());
+                body_values.set(HashMap::new());
+                file_state.set(None);
+                result_state.set(None);
+            })
+        }
+    };
+
+    let onsubmit = {
+        let endpoints = endpoints.clone();
+        let selected = *selected_state;
+        let error_message_state = error_message_state.clone();
+        let token_state = token_state.clone();
+        let path_values = path_values.clone();
+        let query_values = query_values.clone();
+        let body_values = body_values.clone();
+        let file_state = file_state.clone();
+        let is_sending = is_sending.clone();
+        let result_state = result_state.clone();
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            let endpoint = &endpoints[selected];
+            let request = endpoint.url(&path_values).and_then(|url| {
+                let query = build_query(endpoint.query, &query_values)?;
+                let body = match &endpoint.body {
+                    RequestBody::None => ExplorerBody::None,
+                    RequestBody::Json { fields, check, .. } => {
+                        ExplorerBody::Json(check(build_json(fields, &body_values)?)?)
+                    }
+                    RequestBody::File => ExplorerBody::File(
+                        (*file_state)
+                            .clone()
+                            .ok_or_else(|| "Выберите файл".to_string())?,
+                    ),
+                };
+
+                Ok((url, query, body))
+            });
+
+            let (url, query, body) = match request {
+                Ok(request) => request,
+                Err(error) => {
+                    error_message_state.set(Some(error));
+                    return;
+                }
+            };
+
+            let endpoints = endpoints.clone();
+            let error_message_state = error_message_state.clone();
+            let is_sending = is_sending.clone();
+            let result_state = result_state.clone();
+            let token = token_state.trim().to_string();
+
+            is_sending.set(true);
+
+            spawn_local(async move {
+                let endpoint = &endpoints[selected];
+                let client = (!token.is_empty()).then(|| BotClient::new(token));
+
+                match call_endpoint(endpoint.method, &url, query, body, client.as_ref()).await {
+                    Ok(response) => {
+                        let raw = serde_json::from_str::<serde_json::Value>(&response.text)
+                            .ok()
+                            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+                            .unwrap_or(response.text.clone());
+
+                        result_state.set(Some(ExplorerResult {
+                            status: response.status,
+                            raw,
+                            decoded: (endpoint.decode)(&response.text),
+                        }));
+                    }
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+
+                is_sending.set(false);
+            });
+        })
+    };
+
+    html! {
+        <>
+        <Header />
+
+        <h1 class="fw-light fs-2 py-3">{"API Explorer"}</h1>
+        <p class="text-body-secondary">
+            {"Все методы API с формами для запросов. Ответ показывается как есть и так, как его разбирает клиент на Rust."}
+        </p>
+
+        <div class="row">
+            <div class="col-lg-4 col-md-12 gy-3">
+                <div class="list-group">
+                    { for endpoints.iter().enumerate().map(|(index, spec)| html! {
+                        <button
+                            type="button"
+                            onclick={on_select(index)}
+                            class={classes!(
+                                "list-group-item",
+                                "list-group-item-action",
+                                (index == *selected_state).then_some("active"),
+                            )}
+                        >
+                            <span class={classes!("badge", "me-2", spec.method.color_class())}>
+                                {spec.method.name()}
+                            </span>
+                            <span class="font-monospace small">{spec.path}</span>
+                        </button>
+                    }) }
+                </div>
+            </div>
+
+            <div class="col-lg-8 col-md-12 gy-3">
+                <h2 class="fs-4">
+                    <span class={classes!("badge", "me-2", endpoint.method.color_class())}>
+                        {endpoint.method.name()}
+                    </span>
+                    <span class="font-monospace">{endpoint.path}</span>
+                </h2>
+                <p class="mb-1">{endpoint.description}</p>
+                <p class="text-body-secondary small">
+                    <span class="font-monospace">{format!("api::{}", endpoint.function)}</span>
+                    {" → "}
+                    <span class="font-monospace">{endpoint.response}</span>
+                </p>
+
+                <form class="gy-3 row" {onsubmit}>
+                    <FormField
+                        id="explorer-token-input"
+                        label="Токен бота"
+                        input_type="password"
+                        autocomplete="off"
+                        value={(*token_state).clone()}
+                        on_input={
+                            let token_state = token_state.clone();
+                            Callback::from(move |value: String| token_state.set(value))
+                        }
+                        revealable=true
+                    >
+                        <div class="form-text">
+                            {"Необязательно. Без токена запрос уйдёт от имени вошедшего пользователя."}
+                        </div>
+                    </FormField>
+
+                    if !endpoint.path_params().is_empty() {
+                        <h3 class="fs-5 mb-0">{"Параметры пути"}</h3>
+                        { for endpoint.path_params().into_iter().map(|param| field_input(
+                            &FieldSpec {
+                                name: param,
+                                kind: FieldKind::Text,
+                                optional: false,
+                                description: "",
+                            },
+                            &path_values,
+                        )) }
+                    }
+
+                    if !endpoint.query.is_empty() {
+                        <h3 class="fs-5 mb-0">{"Параметры запроса"}</h3>
+                        { for endpoint.query.iter().map(|field| field_input(field, &query_values)) }
+                    }
+
+                    {
+                        match &endpoint.body {
+                            RequestBody::None => html! {},
+                            RequestBody::Json { struct_name, fields, .. } => html! {
+                                <>
+                                <h3 class="fs-5 mb-0">
+                                    {"Тело "}
+                                    <span class="font-monospace">{*struct_name}</span>
+                                </h3>
+                                { for fields.iter().map(|field| field_input(field, &body_values)) }
+                                </>
+                            },
+                            RequestBody::File => html! {
+                                <div class="col-12">
+                                    <label class="fw-medium" for="explorer-file-input">{"Файл"}</label>
+                                    <input
+                                        id="explorer-file-input"
+                                        class="form-control"
+                                        type="file"
+                                        onchange={
+                                            let file_state = file_state.clone();
+                                            Callback::from(move |event: Event| {
+                                                let input = event.target_unchecked_into::<HtmlInputElement>();
+                                                file_state.set(input.files().and_then(|files| files.get(0)));
+                                            })
+                                        }
+                                    />
+                                </div>
+                            },
+                        }
+                    }
+
+                    if let Some(preview) = body_preview {
+                        <div class="col-12">
+                            {
+                                match preview {
+                                    Ok(json) => html! {
+                                        <pre class="bg-body-tertiary rounded p-2 mb-0 small"><code>{json}</code></pre>
+                                    },
+                                    Err(error) => html! {
+                                        <div class="alert alert-warning mb-0 small">{error}</div>
+                                    },
+                                }
+                            }
+                        </div>
+                    }
+
+                    <div class="col-12">
+                        <button type="submit" class="btn btn-success" disabled={*is_sending}>
+                            {"Отправить"}
+                        </button>
+                    </div>
+                </form>
+
+                if let Some(result) = &*result_state {
+                    <h3 class="fs-5 pt-4">
+                        {"Ответ "}
+                        <span class={classes!(
+                            "badge",
+                            if result.status < 400 { "text-bg-success" } else { "text-bg-danger" },
+                        )}>
+                            {result.status}
+                        </span>
+                    </h3>
+                    <pre class="bg-body-tertiary rounded p-2 small"><code>{result.raw.clone()}</code></pre>
+
+                    <h3 class="fs-5">
+                        {"Разбор в "}
+                        <span class="font-monospace">{endpoint.response}</span>
+                    </h3>
+                    {
+                        match &result.decoded {
+                            Ok(decoded) => html! {
+                                <pre class="bg-body-tertiary rounded p-2 small"><code>{decoded.clone()}</code></pre>
+                            },
+                            Err(error) => html! {
+                                <div class="alert alert-danger small">
+                                    {"Клиент не смог разобрать ответ: "}
+                                    {error.clone()}
+                                </div>
+                            },
+                        }
+                    }
+                }
+            </div>
+        </div>
+
+        <Footer />
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
@@ -0,0 +1,215 @@
+use bitum_frontend::{display_timestamp_date, get_random_color_image_url};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::{use_navigator, Link};
+
+use crate::{
+    api::{get_bots, patch_bot, upload_file, PatchBotRequest, User},
+    components::{
+        AddToChatDropdown, AvatarCropper, BotTokenModalButton, DeleteBotModalButton, ErrorMessage,
+        Footer, Header, TextMessage,
+    },
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::Route,
+};
+
+#[derive(PartialEq, Properties)]
+pub struct BotRouteProps {
+    pub bot_id: i32,
+}
+
+#[function_component]
+pub fn BotRoute(props: &BotRouteProps) -> Html {
+    let BotRouteProps { bot_id } = props;
+
+    let error_message_state = use_state(|| Option::<String>::None);
+    let success_message_state = use_state(|| Option::<String>::None);
+    let bot_state = use_state(|| Option::<User>::None);
+    let is_uploading = use_state(|| false);
+    let navigator = use_navigator().unwrap();
+
+    {
+        let error_message_state = error_message_state.clone();
+        let bot_state = bot_state.clone();
+
+        use_effect_with_deps(
+            move |bot_id| {
+                let bot_id = *bot_id;
+
+                spawn_local(async move {
+                    // Отдельного метода для своего бота нет, ищем среди своих
+                    match get_bots().await {
+                        Ok(response) if response.ok => {
+                            let bot = response
+                                .data
+                                .unwrap_or_default()
+                                .into_iter()
+                                .find(|bot| bot.id == bot_id);
+
+                            if bot.is_none() {
+                                error_message_state.set(Some("Такого бота у вас нет".to_string()));
+                            }
+
+                            bot_state.set(bot);
+                        }
+                        Ok(response) => error_message_state.set(Some(
+                            ApiAction::LoadBots.error_message(response.error.as_ref()),
+                        )),
+                        Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                    }
+                });
+            },
+            *bot_id,
+        );
+    }
+
+    let on_icon_save = {
+        let error_message_state = error_message_state.clone();
+        let success_message_state = success_message_state.clone();
+        let bot_state = bot_state.clone();
+        let is_uploading = is_uploading.clone();
+        let bot_id = *bot_id;
+
+        Callback::from(move |file: web_sys::File| {
+            let error_message_state = error_message_state.clone();
+            let success_message_state = success_message_state.clone();
+            let bot_state = bot_state.clone();
+            let is_uploading = is_uploading.clone();
+
+            is_uploading.set(true);
+
+            spawn_local(async move {
+                let icon_file = match upload_file(file).await {
+                    Ok(response) if response.ok => {
+                        response.data.unwrap_or_default().first().cloned()
+                    }
+                    Ok(response) => {
+                        error_message_state.set(Some(
+                            ApiAction::UploadFile.error_message(response.error.as_ref()),
+                        ));
+                        None
+                    }
+                    Err(_) => {
+                        error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                        None
+                    }
+                };
+
+                if let Some(icon_file) = icon_file {
+                    let patch_bot_request = PatchBotRequest {
+                        icon_file: Some(icon_file),
+                    };
+
+                    match patch_bot(bot_id, patch_bot_request).await {
+                        Ok(response) if response.ok => {
+                            bot_state.set(response.data);
+                            success_message_state.set(Some("Иконка обновлена".to_string()));
+                        }
+                        Ok(response) => error_message_state.set(Some(
+                            ApiAction::EditBot.error_message(response.error.as_ref()),
+                        )),
+                        Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                    }
+                }
+
+                is_uploading.set(false);
+            });
+        })
+    };
+
+    let on_deleted = Callback::from(move |_: i32| navigator.push(&Route::MyBots));
+
+    html! {
+        <>
+        <Header />
+
+        <Link<Route> classes="text-decoration-none" to={Route::MyBots}>
+            <i class="bi bi-arrow-left"></i>{" Мои боты"}
+        </Link<Route>>
+
+        if let Some(bot) = (*bot_state).clone() {
+            <div class="row">
+                <div class="col-lg-6 col-md-12 gy-3">
+                    <div class="d-flex align-items-center gap-4 py-3">
+                        <img class="rounded-4 border object-fit-scale" width=150px height=150px src={
+                            if bot.icon.is_some() {
+                                format!("/api/files/{}", bot.icon.clone().unwrap_or("null.png".to_string()))
+                            } else {
+                                get_random_color_image_url(bot.username.clone(), 150, 150)
+                            }
+                        } alt="Bot icon"/>
+                        <div>
+                            <h1 class="fw-medium fs-1">
+                                <span class="badge rounded-pill text-bg-dark fs-6">{"бот"}</span>
+                                {" "}
+                                {bot.username.clone()}
+                            </h1>
+                            <div class="text-body-secondary fs-5">
+                                {"Создан "}
+                                {display_timestamp_date(bot.created_at as i64)}
+                            </div>
+                            <Link<Route> to={Route::UserProfile { username: bot.username.clone() }}>
+                                {"Публичный профиль"}
+                            </Link<Route>>
+                        </div>
+                    </div>
+
+                    <div class="d-flex flex-wrap gap-2">
+                        <BotTokenModalButton bot={bot.clone()}>
+                            <button type="button" class="btn btn-dark">
+                                <i class="bi bi-key"></i>
+                                {" Токен"}
+                            </button>
+                        </BotTokenModalButton>
+                        <Link<Route> classes="btn btn-outline-dark" to={Route::BotPlayground { id: bot.id }}>
+                            <i class="bi bi-terminal"></i>
+                            {" Песочница"}
+                        </Link<Route>>
+                        <AddToChatDropdown user={bot.clone()} />
+                        <DeleteBotModalButton bot={bot.clone()} {on_deleted}>
+                            <button type="button" class="btn btn-outline-danger">
+                                <i class="bi bi-trash2"></i>
+                                {" Удалить"}
+                            </button>
+                        </DeleteBotModalButton>
+                    </div>
+                </div>
+
+                <div class="col-lg-6 col-md-12 gy-3">
+                    <h2 class="fs-4">{"Иконка"}</h2>
+                    <AvatarCropper on_save={on_icon_save} disabled={*is_uploading} />
+                </div>
+            </div>
+        }
+
+        <Footer />
+
+        if let Some(message) = (*success_message_state).clone() {
+            <TextMessage
+                on_close={
+                    let success_message_state = success_message_state.clone();
+
+                    Callback::from(move |_| {
+                        success_message_state.set(None);
+                    })
+                }
+                value={message}
+                toast_classes={classes!("bg-success", "text-bg-primary")}
+            />
+        }
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
@@ -0,0 +1,712 @@
+use std::{cell::Cell, collections::HashSet, rc::Rc};
+
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::{
+    api::{
+        get_bots, BotClient, Event as BotEvent, EventType, ReadEventsRequest, SendMessageRequest,
+        User,
+    },
+    components::{ErrorMessage, Footer, FormField, Header, TextMessage},
+    constants::{API_REFRESH_MILLIS, MAX_READ_EVENTS_PER_REQUEST},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::Route,
+    validation::validate_message_content,
+};
+
+/// Событие так, как его прислал сервер, и то, как его понял клиент
+#[derive(Clone)]
+struct PlaygroundEvent {
+    raw: serde_json::Value,
+    parsed: Result<BotEvent, String>,
+    is_read: bool,
+}
+
+impl PlaygroundEvent {
+    fn new(raw: serde_json::Value) -> Self {
+        let parsed = serde_json::from_value::<BotEvent>(raw.clone()).map_err(|e| e.to_string());
+
+        Self {
+            raw,
+            parsed,
+            is_read: false,
+        }
+    }
+
+    fn id(&self) -> Option<i32> {
+        self.raw
+            .get("id")
+            .and_then(|id| id.as_i64())
+            .map(|id| id as i32)
+    }
+}
+
+/// Все события, полученные за сессию. Пока событие не прочитано, сервер присылает его снова
+#[derive(Default)]
+struct EventLog {
+    events: Vec<PlaygroundEvent>,
+}
+
+enum EventLogAction {
+    Received(Vec<serde_json::Value>),
+    Read(Vec<i32>),
+    Clear,
+}
+
+impl EventLog {
+    fn unread_ids(&self) -> Vec<i32> {
+        self.events
+            .iter()
+            .filter(|event| !event.is_read)
+            .filter_map(PlaygroundEvent::id)
+            .collect()
+    }
+}
+
+impl Reducible for EventLog {
+    type Action = EventLogAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut events = self.events.clone();
+
+        match action {
+            EventLogAction::Received(values) => {
+                let known_ids: HashSet<i32> = events.iter().filter_map(|e| e.id()).collect();
+
+                events.extend(
+                    values
+                        .into_iter()
+                        .map(PlaygroundEvent::new)
+                        .filter(|event| event.id().is_none_or(|id| !known_ids.contains(&id))),
+                );
+            }
+            EventLogAction::Read(ids) => {
+                for event in events.iter_mut() {
+                    if event.id().is_some_and(|id| ids.contains(&id)) {
+                        event.is_read = true;
+                    }
+                }
+            }
+            EventLogAction::Clear => events.clear(),
+        }
+
+        Rc::new(EventLog { events })
+    }
+}
+
+async fn read_playground_events(client: &BotClient, ids: Vec<i32>) -> Result<Vec<i32>, String> {
+    let mut read_ids = Vec::new();
+
+    for ids in ids.chunks(MAX_READ_EVENTS_PER_REQUEST) {
+        match client
+            .read_events(ReadEventsRequest { ids: ids.to_vec() })
+            .await
+        {
+            Ok(response) if response.ok => {
+                read_ids.extend(response.data.unwrap_or_default().read_event_ids)
+            }
+            Ok(response) => {
+                return Err(ApiAction::ReadEvents.error_message(response.error.as_ref()))
+            }
+            Err(_) => return Err(SERVER_UNAVAILABLE.to_string()),
+        }
+    }
+
+    Ok(read_ids)
+}
+
+fn event_type_name(event_type: &EventType) -> &'static str {
+    match event_type {
+        EventType::NewMessage(_) => "new_message",
+        EventType::MemberAdded(_) => "member_added",
+        EventType::MemberKicked { .. } => "member_kicked",
+    }
+}
+
+fn event_summary(event_type: &EventType) -> String {
+    match event_type {
+        EventType::NewMessage(message) => format!(
+            "{} в «{}»: {}",
+            message.sender.username, message.chat.name, message.content
+        ),
+        EventType::MemberAdded(member) => {
+            format!("{} добавлен в «{}»", member.user.username, member.chat.name)
+        }
+        EventType::MemberKicked { user, chat } => {
+            format!("{} исключён из «{}»", user.username, chat.name)
+        }
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct BotPlaygroundRouteProps {
+    pub bot_id: i32,
+}
+
+/// Консоль, в которой владелец работает с API от имени своего бота
+#[function_component]
+pub fn BotPlaygroundRoute(props: &BotPlaygroundRouteProps) -> Html {
+    let BotPlaygroundRouteProps { bot_id } = props;
+
+    let error_message_state = use_state(|| Option::<String>::None);
+    let success_message_state = use_state(|| Option::<String>::None);
+    let bot_state = use_state(|| Option::<User>::None);
+    let token_state = use_state(String::new);
+    let client_state = use_state(|| Option::<BotClient>::None);
+    let is_connecting = use_state(|| false);
+    let is_polling = use_state(|| false);
+    let auto_read = use_state(|| false);
+    let event_log = use_reducer(EventLog::default);
+    let chats_state = use_state(Vec::new);
+    let chat_id_state = use_state(|| Option::<i32>::None);
+    let content_state = use_state(String::new);
+    let is_sending = use_state(|| false);
+
+    {
+        let error_message_state = error_message_state.clone();
+        let bot_state = bot_state.clone();
+
+        use_effect_with_deps(
+            move |bot_id| {
+                let bot_id = *bot_id;
+
+                spawn_local(async move {
+                    match get_bots().await {
+                        Ok(response) if response.ok => {
+                            let bot = response
+                                .data
+                                .unwrap_or_default()
+                                .into_iter()
+                                .find(|bot| bot.id == bot_id);
+
+                            if bot.is_none() {
+                                error_message_state.set(Some("Такого бота у вас нет".to_string()));
+                            }
+
+                            bot_state.set(bot);
+                        }
+                        Ok(response) => error_message_state.set(Some(
+                            ApiAction::LoadBots.error_message(response.error.as_ref()),
+                        )),
+                        Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                    }
+                });
+            },
+            *bot_id,
+        );
+    }
+
+    // Опрос событий от имени бота, пока он включён
+    {
+        let error_message_state = error_message_state.clone();
+        let is_polling_state = is_polling.clone();
+        let event_log = event_log.clone();
+
+        use_effect_with_deps(
+            move |(client, is_polling, auto_read)| {
+                let is_running = Rc::new(Cell::new(*is_polling && client.is_some()));
+                let client = client.clone();
+                let auto_read = *auto_read;
+
+                if let Some(client) = client.filter(|_| is_running.get()) {
+                    let is_running = is_running.clone();
+
+                    spawn_local(async move {
+                        while is_running.get() {
+                            let error = match client.get_raw_events().await {
+                                Ok(response) if response.ok => {
+                                    let values = response.data.unwrap_or_default();
+                                    let ids: Vec<i32> = values
+                                        .iter()
+                                        .filter_map(|value| value.get("id")?.as_i64())
+                                        .map(|id| id as i32)
+                                        .collect();
+
+                                    if is_running.get() && !values.is_empty() {
+                                        event_log.dispatch(EventLogAction::Received(values));
+                                    }
+
+                                    if auto_read && !ids.is_empty() {
+                                        match read_playground_events(&client, ids).await {
+                                            Ok(read_ids) => {
+                                                event_log.dispatch(EventLogAction::Read(read_ids));
+                                                None
+                                            }
+                                            Err(error) => Some(error),
+                                        }
+                                    } else {
+                                        None
+                                    }
+                                }
+                                Ok(response) => Some(
+                                    ApiAction::LoadEvents.error_message(response.error.as_ref()),
+                                ),
+                                Err(_) => Some(SERVER_UNAVAILABLE.to_string()),
+                            };
+
+                            if let Some(error) = error {
+                                if is_running.get() {
+                                    error_message_state.set(Some(error));
+                                    is_polling_state.set(false);
+                                }
+                                break;
+                            }
+
+                            TimeoutFuture::new(API_REFRESH_MILLIS).await;
+                        }
+                    });
+                }
+
+                move || is_running.set(false)
+            },
+            ((*client_state).clone(), *is_polling, *auto_read),
+        );
+    }
+
+    let load_chats = {
+        let error_message_state = error_message_state.clone();
+        let chats_state = chats_state.clone();
+        let chat_id_state = chat_id_state.clone();
+
+        Callback::from(move |client: BotClient| {
+            let error_message_state = error_message_state.clone();
+            let chats_state = chats_state.clone();
+            let chat_id_state = chat_id_state.clone();
+
+            spawn_local(async move {
+                match client.get_chats().await {
+                    Ok(response) if response.ok => {
+                        let chats = response.data.unwrap_or_default();
+
+                        chat_id_state.set(chats.first().map(|chat| chat.id));
+                        chats_state.set(chats);
+                    }
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::LoadChats.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+            });
+        })
+    };
+
+    let on_connect = {
+        let error_message_state = error_message_state.clone();
+        let token_state = token_state.clone();
+        let client_state = client_state.clone();
+        let is_connecting = is_connecting.clone();
+        let load_chats = load_chats.clone();
+        let bot_id = *bot_id;
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            let token = token_state.trim().to_string();
+
+            if token.is_empty() {
+                return;
+            }
+
+            let error_message_state = error_message_state.clone();
+            let client_state = client_state.clone();
+            let is_connecting = is_connecting.clone();
+            let load_chats = load_chats.clone();
+            let client = BotClient::new(token);
+
+            is_connecting.set(true);
+
+            spawn_local(async move {
+                match client.get_current_user_info().await {
+                    Ok(response) if response.ok => match response.data {
+                        Some(user) if user.id == bot_id => {
+                            load_chats.emit(client.clone());
+                            client_state.set(Some(client));
+                        }
+                        Some(user) => error_message_state.set(Some(format!(
+                            "Это токен другого пользователя: {}",
+                            user.username
+                        ))),
+                        None => error_message_state.set(Some(
+                            ApiAction::ConnectBot.error_message(response.error.as_ref()),
+                        )),
+                    },
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::ConnectBot.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+
+                is_connecting.set(false);
+            });
+        })
+    };
+
+    let on_disconnect = {
+        let client_state = client_state.clone();
+        let token_state = token_state.clone();
+        let is_polling = is_polling.clone();
+        let event_log = event_log.clone();
+        let chats_state = chats_state.clone();
+        let chat_id_state = chat_id_state.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            client_state.set(None);
+            token_state.set(String::new());
+            is_polling.set(false);
+            event_log.dispatch(EventLogAction::Clear);
+            chats_state.set(Vec::new());
+            chat_id_state.set(None);
+        })
+    };
+
+    let on_read = {
+        let error_message_state = error_message_state.clone();
+        let client_state = client_state.clone();
+        let event_log = event_log.clone();
+
+        Callback::from(move |ids: Vec<i32>| {
+            let Some(client) = (*client_state).clone() else {
+                return;
+            };
+
+            if ids.is_empty() {
+                return;
+            }
+
+            let error_message_state = error_message_state.clone();
+            let event_log = event_log.clone();
+
+            spawn_local(async move {
+                match read_playground_events(&client, ids).await {
+                    Ok(read_ids) => event_log.dispatch(EventLogAction::Read(read_ids)),
+                    Err(error) => error_message_state.set(Some(error)),
+                }
+            });
+        })
+    };
+
+    let content_error = validate_message_content(content_state.trim()).err();
+
+    let on_send = {
+        let error_message_state = error_message_state.clone();
+        let success_message_state = success_message_state.clone();
+        let client_state = client_state.clone();
+        let chat_id_state = chat_id_state.clone();
+        let content_state = content_state.clone();
+        let is_sending = is_sending.clone();
+        let is_valid = content_error.is_none();
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            let (Some(client), Some(chat_id)) = ((*client_state).clone(), *chat_id_state) else {
+                return;
+            };
+
+            if !is_valid {
+                return;
+            }
+
+            let error_message_state = error_message_state.clone();
+            let success_message_state = success_message_state.clone();
+            let content_state = content_state.clone();
+            let is_sending = is_sending.clone();
+            let send_message_request = SendMessageRequest {
+                content: content_state.trim().to_string(),
+                files: Vec::new(),
+            };
+
+            is_sending.set(true);
+
+            spawn_local(async move {
+                match client.send_message(chat_id, send_message_request).await {
+                    Ok(response) if response.ok => {
+                        content_state.set(String::new());
+                        success_message_state.set(Some("Сообщение отправлено".to_string()));
+                    }
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::SendMessage.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+
+                is_sending.set(false);
+            });
+        })
+    };
+
+    let unread_ids = event_log.unread_ids();
+
+    html! {
+        <>
+        <Header />
+
+        <Link<Route> classes="text-decoration-none" to={Route::Bot { id: *bot_id }}>
+            <i class="bi bi-arrow-left"></i>
+            {" "}
+            {(*bot_state).as_ref().map(|bot| bot.username.clone()).unwrap_or("Бот".to_string())}
+        </Link<Route>>
+
+        <h1 class="fw-light fs-2 py-3">{"Песочница бота"}</h1>
+
+        if let Some(client) = (*client_state).clone() {
+            <div class="row">
+                <div class="col-lg-7 col-md-12 gy-3">
+                    <div class="d-flex flex-wrap align-items-center gap-3 mb-3">
+                        <div class="form-check form-switch m-0">
+                            <input
+                                id="polling-switch"
+                                class="form-check-input"
+                                type="checkbox"
+                                role="switch"
+                                checked={*is_polling}
+                                onchange={
+                                    let is_polling = is_polling.clone();
+                                    Callback::from(move |_: Event| is_polling.set(!*is_polling))
+                                }
+                            />
+                            <label class="form-check-label" for="polling-switch">
+                                {"Опрашивать GET /events"}
+                            </label>
+                        </div>
+                        <div class="form-check m-0">
+                            <input
+                                id="auto-read-check"
+                                class="form-check-input"
+                                type="checkbox"
+                                checked={*auto_read}
+                                onchange={
+                                    let auto_read = auto_read.clone();
+                                    Callback::from(move |_: Event| auto_read.set(!*auto_read))
+                                }
+                            />
+                            <label class="form-check-label" for="auto-read-check">
+                                {"Сразу отмечать прочитанными"}
+                            </label>
+                        </div>
+                        <button
+                            type="button"
+                            class="btn btn-sm btn-outline-primary"
+                            disabled={unread_ids.is_empty()}
+                            onclick={
+                                let on_read = on_read.clone();
+                                let unread_ids = unread_ids.clone();
+                                Callback::from(move |_: MouseEvent| on_read.emit(unread_ids.clone()))
+                            }
+                        >
+                            {format!("DELETE /events ({})", unread_ids.len())}
+                        </button>
+                        <button
+                            type="button"
+                            class="btn btn-sm btn-outline-secondary"
+                            onclick={
+                                let event_log = event_log.clone();
+                                Callback::from(move |_: MouseEvent| event_log.dispatch(EventLogAction::Clear))
+                            }
+                        >
+                            {"Очистить"}
+                        </button>
+                    </div>
+
+                    if event_log.events.is_empty() {
+                        <p class="text-body-secondary">
+                            {"Событий пока нет. Включите опрос и, например, напишите боту в чате."}
+                        </p>
+                    }
+
+                    { for event_log.events.iter().rev().map(|event| {
+                        let id = event.id();
+
+                        html! {
+                            <div class={classes!("card", "mb-3", event.is_read.then_some("opacity-50"))}>
+                                <div class="card-header d-flex align-items-center gap-2">
+                                    <span class="font-monospace">
+                                        {id.map(|id| format!("#{}", id)).unwrap_or("#?".to_string())}
+                                    </span>
+                                    {
+                                        match &event.parsed {
+                                            Ok(parsed) => html! {
+                                                <span class="badge text-bg-primary">{event_type_name(&parsed.payload)}</span>
+                                            },
+                                            Err(_) => html! {
+                                                <span class="badge text-bg-danger">{"не разобрано"}</span>
+                                            },
+                                        }
+                                    }
+                                    if event.is_read {
+                                        <span class="badge text-bg-secondary">{"прочитано"}</span>
+                                    }
+                                    <span class="flex-grow-1"></span>
+                                    if let (Some(id), false) = (id, event.is_read) {
+                                        <button
+                                            type="button"
+                                            class="btn btn-sm btn-outline-primary"
+                                            onclick={
+                                                let on_read = on_read.clone();
+                                                Callback::from(move |_: MouseEvent| on_read.emit(vec![id]))
+                                            }
+                                        >
+                                            {"Прочитать"}
+                                        </button>
+                                    }
+                                </div>
+                                <div class="card-body">
+                                    <p class="card-text text-break">
+                                        {
+                                            match &event.parsed {
+                                                Ok(parsed) => event_summary(&parsed.payload),
+                                                Err(error) => format!("Не удалось разобрать Event: {}", error),
+                                            }
+                                        }
+                                    </p>
+                                    <pre class="bg-body-tertiary rounded p-2 mb-0 small"><code>
+                                        {serde_json::to_string_pretty(&event.raw).unwrap_or_default()}
+                                    </code></pre>
+                                </div>
+                            </div>
+                        }
+                    }) }
+                </div>
+
+                <div class="col-lg-5 col-md-12 gy-3">
+                    <h2 class="fs-4">{"Отправить сообщение"}</h2>
+                    <form class="gy-3 row" onsubmit={on_send}>
+                        <div class="col-12">
+                            <label class="fw-medium" for="playground-chat-select">{"Чат"}</label>
+                            <div class="input-group">
+                                <select
+                                    id="playground-chat-select"
+                                    class="form-select"
+                                    onchange={
+                                        let chat_id_state = chat_id_state.clone();
+                                        Callback::from(move |event: Event| {
+                                            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+                                            chat_id_state.set(value.parse().ok());
+                                        })
+                                    }
+                                >
+                                    if chats_state.is_empty() {
+                                        <option value="" selected=true>{"Бот не состоит ни в одном чате"}</option>
+                                    }
+                                    { for chats_state.iter().map(|chat| html! {
+                                        <option
+                                            value={chat.id.to_string()}
+                                            selected={Some(chat.id) == *chat_id_state}
+                                        >
+                                            {chat.name.clone()}
+                                        </option>
+                                    }) }
+                                </select>
+                                <button
+                                    type="button"
+                                    class="btn btn-outline-secondary"
+                                    title="Обновить список чатов"
+                                    onclick={
+                                        let load_chats = load_chats.clone();
+                                        let client = client.clone();
+                                        Callback::from(move |_: MouseEvent| load_chats.emit(client.clone()))
+                                    }
+                                >
+                                    <i class="bi bi-arrow-clockwise"></i>
+                                </button>
+                            </div>
+                        </div>
+                        <div class="col-12">
+                            <label class="fw-medium" for="playground-content">{"Текст"}</label>
+                            <textarea
+                                id="playground-content"
+                                rows="4"
+                                class="form-control"
+                                value={(*content_state).clone()}
+                                oninput={
+                                    let content_state = content_state.clone();
+                                    Callback::from(move |event: InputEvent| {
+                                        content_state.set(event.target_unchecked_into::<HtmlTextAreaElement>().value());
+                                    })
+                                }
+                            ></textarea>
+                        </div>
+                        <div class="col-12 d-flex justify-content-between">
+                            <button
+                                type="submit"
+                                class="btn btn-success"
+                                disabled={*is_sending || content_error.is_some() || chat_id_state.is_none()}
+                            >
+                                {"Отправить от имени бота"}
+                            </button>
+                            <button type="button" class="btn btn-outline-danger" onclick={on_disconnect}>
+                                {"Отключиться"}
+                            </button>
+                        </div>
+                    </form>
+                </div>
+            </div>
+        } else {
+            <div class="row">
+                <div class="col-lg-6 col-md-12">
+                    <p>
+                        {"Вставьте токен бота, чтобы получать его события и писать в чаты от его имени. "}
+                        {"Токен остаётся только в этой вкладке и передаётся в заголовке Authorization."}
+                    </p>
+                    <form class="text-start gy-3 row" onsubmit={on_connect}>
+                        <FormField
+                            id="bot-token-input"
+                            label="Токен бота"
+                            input_type="password"
+                            autocomplete="off"
+                            value={(*token_state).clone()}
+                            on_input={
+                                let token_state = token_state.clone();
+                                Callback::from(move |value: String| token_state.set(value))
+                            }
+                            revealable=true
+                        />
+                        <div class="col-12">
+                            <button
+                                type="submit"
+                                class="btn btn-success btn-lg"
+                                disabled={*is_connecting || token_state.trim().is_empty()}
+                            >
+                                {"Подключиться"}
+                            </button>
+                        </div>
+                    </form>
+                </div>
+            </div>
+        }
+
+        <Footer />
+
+        if let Some(message) = (*success_message_state).clone() {
+            <TextMessage
+                on_close={
+                    let success_message_state = success_message_state.clone();
+
+                    Callback::from(move |_| {
+                        success_message_state.set(None);
+                    })
+                }
+                value={message}
+                toast_classes={classes!("bg-success", "text-bg-primary")}
+            />
+        }
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
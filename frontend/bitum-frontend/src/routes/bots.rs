@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use bitum_frontend::{display_timestamp_date, get_random_color_image_url};
+use gloo_timers::future::TimeoutFuture;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::{use_location, use_navigator, Link, Routable};
+
+use crate::{
+    api::{get_user, search_bots, User},
+    components::{
+        use_auth, use_current_user, AddToChatDropdown, AuthState, ErrorMessage, Footer, Header,
+    },
+    constants::{BOT_DIRECTORY_PAGE_SIZE, SEARCH_DEBOUNCE_MILLIS},
+    error_messages::SERVER_UNAVAILABLE,
+    routes::{login::LoginRouteQuery, Route},
+};
+
+/// Поиск и страница в адресе, чтобы ссылкой на каталог можно было поделиться
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct BotsRouteQuery {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub q: String,
+    /// Номер страницы с нуля
+    #[serde(default, skip_serializing_if = "is_first_page")]
+    pub page: i32,
+}
+
+fn is_first_page(page: &i32) -> bool {
+    *page == 0
+}
+
+#[derive(Clone, PartialEq, Default)]
+struct BotsPage {
+    bots: Vec<User>,
+    creators: HashMap<i32, User>,
+    has_next: bool,
+}
+
+#[derive(PartialEq, Properties)]
+struct BotDirectoryCardProps {
+    bot: User,
+    creator: Option<User>,
+    can_add: bool,
+    /// Гостю вместо списка чатов предлагаем войти и вернуться в каталог
+    is_anonymous: bool,
+}
+
+#[function_component]
+fn BotDirectoryCard(props: &BotDirectoryCardProps) -> Html {
+    let BotDirectoryCardProps {
+        bot,
+        creator,
+        can_add,
+        is_anonymous,
+    } = props;
+
+    html! {
+        <div class="col-lg-6 col-md-12 gy-3">
+            <div class="d-flex border rounded-3 bg-body h-100">
+                <img class="rounded-start-3 object-fit-scale" height=120px width=120px src={
+                    if bot.icon.is_some() {
+                        format!("/api/files/{}", bot.icon.clone().unwrap_or("null.png".to_string()))
+                    } else {
+                        get_random_color_image_url(bot.username.clone(), 120, 120)
+                    }
+                } alt="Bot icon"/>
+                <div class="d-flex flex-column flex-grow-1 p-3 text-overflow-ellipsis">
+                    <Link<Route> classes="fs-4 text-decoration-none text-reset" to={Route::UserProfile { username: bot.username.clone() }}>
+                        <span class="badge rounded-pill text-bg-dark fs-6">{"бот"}</span>
+                        {" "}
+                        {bot.username.clone()}
+                    </Link<Route>>
+                    <div class="text-body-secondary">
+                        {"Автор: "}
+                        if let Some(creator) = creator {
+                            <Link<Route> to={Route::UserProfile { username: creator.username.clone() }}>
+                                {creator.username.clone()}
+                            </Link<Route>>
+                        } else {
+                            {"неизвестен"}
+                        }
+                    </div>
+                    <div class="text-body-secondary">
+                        {"Создан "}
+                        {display_timestamp_date(bot.created_at as i64)}
+                    </div>
+                    if *can_add {
+                        <div class="mt-auto pt-2">
+                            <AddToChatDropdown user={bot.clone()} />
+                        </div>
+                    } else if *is_anonymous {
+                        <div class="mt-auto pt-2">
+                            <Link<Route, LoginRouteQuery>
+                                classes="btn btn-outline-dark"
+                                to={Route::Login}
+                                query={Some(LoginRouteQuery { redirect: Some(Route::Bots.to_path()), ..Default::default() })}
+                            >
+                                <i class="bi bi-person-plus"></i>
+                                {" Добавить в чат"}
+                            </Link<Route, LoginRouteQuery>>
+                        </div>
+                    }
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct BotsRouteProps {}
+
+/// Каталог всех ботов на сервере
+#[function_component]
+pub fn BotsRoute(props: &BotsRouteProps) -> Html {
+    let BotsRouteProps {} = props;
+
+    let user = use_current_user();
+    let auth = use_auth();
+    let is_anonymous = matches!(auth.state, AuthState::Anonymous { .. });
+    let navigator = use_navigator().unwrap();
+    let query = use_location()
+        .and_then(|location| location.query::<BotsRouteQuery>().ok())
+        .unwrap_or_default();
+    let error_message_state = use_state(|| Option::<String>::None);
+    let page_state = use_state(|| Option::<BotsPage>::None);
+    let is_loading = use_state(|| false);
+    // Создатели не меняются, поэтому запоминаем их между страницами
+    let known_creators = use_mut_ref(HashMap::<i32, User>::new);
+    // Номер последнего запроса: ответы на устаревшие запросы отбрасываются
+    let search_generation = use_mut_ref(|| 0_u64);
+
+    {
+        let error_message_state = error_message_state.clone();
+        let page_state = page_state.clone();
+        let is_loading = is_loading.clone();
+
+        use_effect_with_deps(
+            move |query: &BotsRouteQuery| {
+                let query = query.clone();
+
+                *search_generation.borrow_mut() += 1;
+                let generation = *search_generation.borrow();
+
+                is_loading.set(true);
+
+                spawn_local(async move {
+                    TimeoutFuture::new(SEARCH_DEBOUNCE_MILLIS).await;
+
+                    if *search_generation.borrow() != generation {
+                        return;
+                    }
+
+                    // Берём на один больше, чтобы знать, есть ли следующая страница
+                    let response = search_bots(
+                        query.q.trim().to_string(),
+                        BOT_DIRECTORY_PAGE_SIZE + 1,
+                        query.page.max(0) * BOT_DIRECTORY_PAGE_SIZE,
+                    )
+                    .await;
+
+                    let mut bots = match response {
+                        Ok(response) if response.ok => response.data.unwrap_or_default(),
+                        Ok(_) => {
+                            error_message_state
+                                .set(Some("Не удалось получить список ботов".to_string()));
+                            is_loading.set(false);
+                            return;
+                        }
+                        Err(_) => {
+                            error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                            is_loading.set(false);
+                            return;
+                        }
+                    };
+
+                    let has_next = bots.len() as i32 > BOT_DIRECTORY_PAGE_SIZE;
+                    bots.truncate(BOT_DIRECTORY_PAGE_SIZE as usize);
+
+                    for creator_id in bots.iter().filter_map(|bot| bot.creator_id) {
+                        if known_creators.borrow().contains_key(&creator_id) {
+                            continue;
+                        }
+
+                        if let Ok(response) = get_user(creator_id).await {
+                            if let Some(creator) = response.data {
+                                known_creators.borrow_mut().insert(creator_id, creator);
+                            }
+                        }
+                    }
+
+                    if *search_generation.borrow() != generation {
+                        return;
+                    }
+
+                    page_state.set(Some(BotsPage {
+                        bots,
+                        creators: known_creators.borrow().clone(),
+                        has_next,
+                    }));
+                    is_loading.set(false);
+                });
+            },
+            query.clone(),
+        );
+    }
+
+    let oninput = {
+        let navigator = navigator.clone();
+
+        Callback::from(move |event: InputEvent| {
+            let q = event.target_unchecked_into::<HtmlInputElement>().value();
+            let _ = navigator.replace_with_query(&Route::Bots, &BotsRouteQuery { q, page: 0 });
+        })
+    };
+
+    let go_to_page = |page: i32| {
+        let navigator = navigator.clone();
+        let q = query.q.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            let _ = navigator.push_with_query(&Route::Bots, &BotsRouteQuery { q: q.clone(), page });
+        })
+    };
+
+    let page = (*page_state).clone().unwrap_or_default();
+
+    html! {
+        <>
+        <Header />
+
+        <h1 class="fw-medium fs-1">{"Каталог ботов"}</h1>
+        <p class="text-body-secondary">
+            {"Все боты на этом сервере. Свой бот появится здесь сразу после создания."}
+        </p>
+
+        <div class="input-group input-group-lg">
+            <span class="input-group-text"><i class="bi bi-search"></i></span>
+            <input
+                {oninput}
+                value={query.q.clone()}
+                type="text"
+                class="form-control"
+                placeholder="Имя бота"
+                aria-label="bot_name"
+            />
+        </div>
+
+        <div class="row">
+            { for page.bots.iter().map(|bot| html! {
+                <BotDirectoryCard
+                    key={bot.id}
+                    bot={bot.clone()}
+                    creator={bot.creator_id.and_then(|id| page.creators.get(&id).cloned())}
+                    can_add={user.is_some()}
+                    {is_anonymous}
+                />
+            }) }
+        </div>
+
+        if page_state.is_some() && page.bots.is_empty() && !*is_loading {
+            <p class="text-body-secondary py-4">{"Ботов не найдено"}</p>
+        }
+
+        <div class="d-flex justify-content-center align-items-center gap-3 py-4">
+            <button
+                type="button"
+                class="btn btn-outline-secondary"
+                disabled={query.page <= 0 || *is_loading}
+                onclick={go_to_page(query.page - 1)}
+            >
+                <i class="bi bi-chevron-left"></i>
+            </button>
+            <span>{format!("Страница {}", query.page.max(0) + 1)}</span>
+            <button
+                type="button"
+                class="btn btn-outline-secondary"
+                disabled={!page.has_next || *is_loading}
+                onclick={go_to_page(query.page + 1)}
+            >
+                <i class="bi bi-chevron-right"></i>
+            </button>
+        </div>
+
+        <Footer />
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
@@ -3,17 +3,23 @@ use gloo_timers::future::TimeoutFuture;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::HtmlTextAreaElement;
 use yew::prelude::*;
+use yew_router::prelude::{use_navigator, Link};
 
 use crate::{
     api::{
-        get_chat, get_chat_member, get_chat_members, get_messages, send_message, Chat, ChatMember,
-        ChatMessage as ApiChatMessage, SendMessageRequest,
+        delete_chat_member, get_chat, get_chat_member, get_chat_members, get_messages,
+        send_message, Chat, ChatMember, ChatMessage as ApiChatMessage, EventType,
+        SendMessageRequest,
     },
     components::{
-        AddChatMemberModalButton, ChatMemberButton, ChatMessage, ErrorMessage, Header,
-        LoggedUserInfo,
+        use_chat_appearance, use_current_user, use_events, use_open_chat, AddChatMemberModalButton,
+        ChatMemberButton, ChatMessage, ChatNotificationModeSelect, ErrorMessage, Header, Modal,
+        UnreadAction, UnreadContext,
     },
     constants::API_REFRESH_MILLIS,
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::{chats::ChatsRouteQuery, Route},
+    validation::{validate_message_content, validate_message_files},
 };
 
 #[derive(PartialEq, Properties)]
@@ -24,46 +30,84 @@ pub struct ChatRouteProps {
 #[function_component]
 pub fn ChatRoute(props: &ChatRouteProps) -> Html {
     let ChatRouteProps { chat_id } = props;
-    let user = use_context::<LoggedUserInfo>().unwrap().user.unwrap();
+    let user_id = use_current_user().map(|user| user.id);
     let error_message_state = use_state(|| Option::<String>::None);
     let self_chat_member_state = use_state(|| Option::<ChatMember>::None);
     let chat_state = use_state(|| Option::<Chat>::None);
+    let leave_modal_visible = use_state(|| false);
     let message_input_node = use_node_ref();
+    let navigator = use_navigator().unwrap();
+    let appearance = use_chat_appearance((*chat_state).as_ref());
+    let unread = use_context::<UnreadContext>();
+    // Запоминаем позицию до того, как чат будет отмечен прочитанным
+    let last_read_message_id = use_state(|| {
+        unread
+            .as_ref()
+            .and_then(|unread| unread.last_read(*chat_id))
+    });
+
+    use_open_chat(*chat_id);
+
+    let on_kicked = {
+        let navigator = navigator.clone();
+        let chat_state = chat_state.clone();
+
+        Callback::from(move |_: ()| {
+            let _ = navigator.push_with_query(
+                &Route::Chats,
+                &ChatsRouteQuery {
+                    kicked_from: (*chat_state).as_ref().map(|chat| chat.name.clone()),
+                },
+            );
+        })
+    };
 
-    if self_chat_member_state.is_none() {
+    {
+        let on_kicked = on_kicked.clone();
+        let chat_id = *chat_id;
+
+        use_events(move |event| {
+            if let EventType::MemberKicked { user, chat } = event {
+                if Some(user.id) == user_id && chat.id == chat_id {
+                    on_kicked.emit(());
+                }
+            }
+        });
+    }
+
+    if let (None, Some(user_id)) = (&*self_chat_member_state, user_id) {
         let error_message_state = error_message_state.clone();
         let self_chat_member_state = self_chat_member_state.clone();
-        let chat_id = chat_id.clone();
-        let user = user.clone();
+        let chat_id = *chat_id;
 
         spawn_local(async move {
             let error_message_state = error_message_state.clone();
             let self_chat_member_state = self_chat_member_state.clone();
 
-            let response = get_chat_member(chat_id.clone(), user.id).await;
+            let response = get_chat_member(chat_id, user_id).await;
 
             if let Ok(response) = response {
                 if response.ok {
                     self_chat_member_state.set(response.data);
                 } else {
-                    error_message_state
-                        .set(Some("Не удалось получить информацию о себе".to_string()));
+                    error_message_state.set(Some(
+                        ApiAction::LoadSelf.error_message(response.error.as_ref()),
+                    ));
                 }
             } else {
-                error_message_state.set(Some("Сервер не отвечает".to_string()));
+                error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
             }
         });
     }
 
     {
         let error_message_state = error_message_state.clone();
-        let chat_id = chat_id.clone();
+        let chat_id = *chat_id;
         let chat_state = chat_state.clone();
 
         if chat_state.is_none() {
             spawn_local(async move {
                 let error_message_state = error_message_state.clone();
-                let chat_id = chat_id.clone();
                 let chat_state = chat_state.clone();
 
                 let response = get_chat(chat_id).await;
@@ -72,11 +116,12 @@ pub fn ChatRoute(props: &ChatRouteProps) -> Html {
                     if response.ok {
                         chat_state.set(response.data);
                     } else {
-                        error_message_state
-                            .set(Some("Не удалось получить информацию о чате".to_string()));
+                        error_message_state.set(Some(
+                            ApiAction::LoadChat.error_message(response.error.as_ref()),
+                        ));
                     }
                 } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
                 }
             })
         }
@@ -85,12 +130,11 @@ pub fn ChatRoute(props: &ChatRouteProps) -> Html {
     let on_submit = {
         let message_input_node = message_input_node.clone();
         let error_message_state = error_message_state.clone();
-        let chat_id = chat_id.clone();
+        let chat_id = *chat_id;
 
         Callback::from(move |_: MouseEvent| {
             let message_input_node = message_input_node.clone();
             let error_message_state = error_message_state.clone();
-            let chat_id = chat_id.clone();
 
             spawn_local(async move {
                 let message_input_node = message_input_node
@@ -98,27 +142,33 @@ pub fn ChatRoute(props: &ChatRouteProps) -> Html {
                     .cast::<HtmlTextAreaElement>()
                     .unwrap();
                 let error_message_state = error_message_state.clone();
-                let chat_id = chat_id.clone();
 
                 if message_input_node.value().is_empty() {
                     return;
                 }
 
-                let response = send_message(
-                    chat_id,
-                    SendMessageRequest {
-                        content: message_input_node.value(),
-                        files: Vec::new(),
-                    },
-                )
-                .await;
+                let send_message_request = SendMessageRequest {
+                    content: message_input_node.value(),
+                    files: Vec::new(),
+                };
+
+                if let Err(err) = validate_message_content(&send_message_request.content)
+                    .and_then(|_| validate_message_files(&send_message_request.files))
+                {
+                    error_message_state.set(Some(err));
+                    return;
+                }
+
+                let response = send_message(chat_id, send_message_request).await;
 
                 if let Ok(response) = response {
                     if !response.ok {
-                        error_message_state.set(Some("Не удалось отправить сообщение".to_string()));
+                        error_message_state.set(Some(
+                            ApiAction::SendMessage.error_message(response.error.as_ref()),
+                        ));
                     }
                 } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
                 }
 
                 message_input_node.set_value("");
@@ -126,37 +176,112 @@ pub fn ChatRoute(props: &ChatRouteProps) -> Html {
         })
     };
 
+    let on_leave_confirm = {
+        let error_message_state = error_message_state.clone();
+        let navigator = navigator.clone();
+        let chat_id = *chat_id;
+
+        Callback::from(move |_: ()| {
+            let Some(user_id) = user_id else {
+                return;
+            };
+            let error_message_state = error_message_state.clone();
+            let navigator = navigator.clone();
+
+            spawn_local(async move {
+                let response = delete_chat_member(chat_id, user_id).await;
+
+                if let Ok(response) = response {
+                    if response.ok {
+                        navigator.push(&Route::Chats);
+                    } else {
+                        error_message_state.set(Some(
+                            ApiAction::LeaveChat.error_message(response.error.as_ref()),
+                        ));
+                    }
+                } else {
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                }
+            });
+        })
+    };
+
     html! {
         <>
         <Header/>
-        if let Some(chat) = (*chat_state).clone() {
+        if let (Some(chat), Some(appearance)) = ((*chat_state).clone(), appearance) {
             <h1 class="fw-medium fs-1">
                 <img width=60px class="rounded-3" src={
-                    if chat.icon.is_some() {
-                        format!("/api/files/{}", chat.icon.unwrap_or("null.png".to_string()))
+                    if appearance.icon.is_some() {
+                        format!("/api/files/{}", appearance.icon.unwrap_or("null.png".to_string()))
                     } else {
-                        get_random_color_image_url(chat.name.clone(), 75, 75)
+                        get_random_color_image_url(appearance.name.clone(), 75, 75)
                     }
                 } />
                 <span class="p-3">
-                    {chat.name}
+                    {appearance.name.clone()}
                 </span>
+                <ChatNotificationModeSelect chat_id={chat.id} />
+                if Some(chat.owner.id) == user_id {
+                    <Link<Route> classes="text-decoration-none text-reset" to={Route::ChatSettings { id: chat.id }}>
+                        <i class="bi bi-gear fs-3 info-hover"></i>
+                    </Link<Route>>
+                } else {
+                    <i
+                        onclick={
+                            let leave_modal_visible = leave_modal_visible.clone();
+                            Callback::from(move |_: MouseEvent| leave_modal_visible.set(true))
+                        }
+                        role="button"
+                        title="Покинуть чат"
+                        class="bi bi-box-arrow-right fs-3 danger-hover"
+                    ></i>
+                }
             </h1>
+
+            <Modal
+                modal_id={"leave-chat-modal".to_string()}
+                is_visible={*leave_modal_visible}
+                on_ok={on_leave_confirm}
+                on_cancel={Callback::from(|_| {})}
+                on_close={
+                    let leave_modal_visible = leave_modal_visible.clone();
+                    Callback::from(move |_| leave_modal_visible.set(false))
+                }
+            >
+                <div class="modal-header">
+                    <h1 class="modal-title fs-5">{"Покинуть чат"}</h1>
+                </div>
+                <div class="modal-body">
+                    {"Вы больше не будете получать сообщения из чата "}
+                    <span class="fw-semibold">{appearance.name.clone()}</span>
+                    {". Вернуться можно только если вас добавят снова."}
+                </div>
+            </Modal>
         }
 
         <div class="row">
             <div class="col-lg-9 col-md-12 gy-3">
-                <div class="row gx-1">
-                    <div class="col-lg-9 col-md-12 p-0">
-                        <textarea ref={message_input_node} type="type" placeholder="Сообщение" class="form-control" />
-                    </div>
-                    <div class="col-1 d-none d-md-none d-lg-block"></div>
-                    <div class="col-lg-2 col-md-12 p-0">
-                        <button onclick={on_submit} class="col-12 m-0 btn btn-outline-success">{"Отправить"}</button>
-                    </div>
-                </div>
+                if let Some(chat_member) = (*self_chat_member_state).clone() {
+                    if chat_member.permissions.can_write {
+                        <div class="row gx-1">
+                            <div class="col-lg-9 col-md-12 p-0">
+                                <textarea ref={message_input_node} type="type" placeholder="Сообщение" class="form-control" />
+                            </div>
+                            <div class="col-1 d-none d-md-none d-lg-block"></div>
+                            <div class="col-lg-2 col-md-12 p-0">
+                                <button onclick={on_submit} class="col-12 m-0 btn btn-outline-success">{"Отправить"}</button>
+                            </div>
+                        </div>
+                    } else {
+                        <div class="alert alert-secondary m-0 d-flex align-items-center">
+                            <i class="bi bi-eye fs-4 pe-3"></i>
+                            {"Вы можете только читать этот чат"}
+                        </div>
+                    }
+                }
                 <div class="col-12 overflow-y-scroll overflow-x-hidden border rounded-5 align-items-center chat-messages-list">
-                    <ChatMessagesList chat_id={*chat_id}/>
+                    <ChatMessagesList chat_id={*chat_id} last_read_message_id={*last_read_message_id}/>
                 </div>
             </div>
 
@@ -174,7 +299,23 @@ pub fn ChatRoute(props: &ChatRouteProps) -> Html {
                     }
                 </div>
 
-                <ChatMembersList chat_id={*chat_id}/>
+                <ChatMembersList
+                    chat_id={*chat_id}
+                    self_member={(*self_chat_member_state).clone()}
+                    on_self_member_change={
+                        let self_chat_member_state = self_chat_member_state.clone();
+
+                        let on_kicked = on_kicked.clone();
+
+                        Callback::from(move |chat_member: Option<ChatMember>| {
+                            if chat_member.is_none() {
+                                on_kicked.emit(());
+                            } else {
+                                self_chat_member_state.set(chat_member);
+                            }
+                        })
+                    }
+                />
             </div>
         </div>
 
@@ -197,17 +338,41 @@ pub fn ChatRoute(props: &ChatRouteProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct ChatMessagesListProps {
     chat_id: i32,
+    last_read_message_id: Option<i32>,
 }
 
 #[function_component]
 fn ChatMessagesList(props: &ChatMessagesListProps) -> Html {
-    let ChatMessagesListProps { chat_id } = props;
-    let messages_state = use_state(|| Vec::<ApiChatMessage>::new());
+    let ChatMessagesListProps {
+        chat_id,
+        last_read_message_id,
+    } = props;
+    let messages_state = use_state(Vec::<ApiChatMessage>::new);
     let error_message_state = use_state(|| Option::<String>::None);
+    let unread = use_context::<UnreadContext>();
+
+    use_effect_with_deps(
+        |(chat_id, newest_message_id)| {
+            if let (Some(unread), Some(message_id)) = (unread, *newest_message_id) {
+                unread.dispatch(UnreadAction::MarkRead {
+                    chat_id: *chat_id,
+                    message_id,
+                });
+            }
+        },
+        // Отмечаем прочитанным только после загрузки сообщений именно этого чата
+        (
+            *chat_id,
+            messages_state
+                .first()
+                .filter(|message| message.chat.id == *chat_id)
+                .map(|message| message.id),
+        ),
+    );
 
     {
         let error_message_state = error_message_state.clone();
-        let chat_id = chat_id.clone();
+        let chat_id = *chat_id;
         let messages_state = messages_state.clone();
 
         spawn_local(async move {
@@ -219,18 +384,36 @@ fn ChatMessagesList(props: &ChatMessagesListProps) -> Html {
                 if response.ok {
                     messages_state.set(response.data.unwrap());
                 } else {
-                    error_message_state.set(Some("Не удалось получить сообщения".to_string()));
+                    error_message_state.set(Some(
+                        ApiAction::LoadMessages.error_message(response.error.as_ref()),
+                    ));
                 }
             } else {
-                error_message_state.set(Some("Сервер не отвечает".to_string()));
+                error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
             }
         });
     }
 
     html! {
         <>
-        { for (*messages_state).iter().map(|message| html! {
-            <ChatMessage message={message.clone()}  />
+        { for (*messages_state).iter().enumerate().map(|(index, message)| {
+            // Сообщения идут от новых к старым, разделитель ставим над первым прочитанным
+            let is_first_read = index > 0
+                && Some(message.id) <= *last_read_message_id
+                && Some(messages_state[index - 1].id) > *last_read_message_id;
+
+            html! {
+                <>
+                if is_first_read {
+                    <div class="d-flex align-items-center text-success new-messages-divider">
+                        <hr class="flex-grow-1" />
+                        <span class="px-3">{"Новые сообщения"}</span>
+                        <hr class="flex-grow-1" />
+                    </div>
+                }
+                <ChatMessage message={message.clone()}  />
+                </>
+            }
         }) }
 
         if let Some(err) = (*error_message_state).clone() {
@@ -252,18 +435,27 @@ fn ChatMessagesList(props: &ChatMessagesListProps) -> Html {
 #[derive(PartialEq, Properties)]
 struct ChatMembersListProps {
     chat_id: i32,
+    self_member: Option<ChatMember>,
+    on_self_member_change: Callback<Option<ChatMember>>,
 }
 
 #[function_component]
 fn ChatMembersList(props: &ChatMembersListProps) -> Html {
-    let ChatMembersListProps { chat_id } = props;
+    let ChatMembersListProps {
+        chat_id,
+        self_member,
+        on_self_member_change,
+    } = props;
+    let self_id = use_current_user().map(|user| user.id);
     let error_message_state = use_state(|| Option::<String>::None);
-    let chat_members_state = use_state(|| Vec::<ChatMember>::new());
+    let chat_members_state = use_state(Vec::<ChatMember>::new);
 
     {
         let error_message_state = error_message_state.clone();
-        let chat_id = chat_id.clone();
+        let chat_id = *chat_id;
         let chat_members_state = chat_members_state.clone();
+        let self_member = self_member.clone();
+        let on_self_member_change = on_self_member_change.clone();
 
         spawn_local(async move {
             TimeoutFuture::new(API_REFRESH_MILLIS).await;
@@ -272,12 +464,25 @@ fn ChatMembersList(props: &ChatMembersListProps) -> Html {
 
             if let Ok(response) = response {
                 if response.ok {
-                    chat_members_state.set(response.data.unwrap());
+                    let chat_members = response.data.unwrap();
+                    let new_self_member = chat_members
+                        .iter()
+                        .find(|chat_member| Some(chat_member.user.id) == self_id)
+                        .cloned();
+
+                    // Права могли поменяться, пока чат открыт
+                    if self_member.is_some() && new_self_member != self_member {
+                        on_self_member_change.emit(new_self_member);
+                    }
+
+                    chat_members_state.set(chat_members);
                 } else {
-                    error_message_state.set(Some("Не удалось получить сообщения".to_string()));
+                    error_message_state.set(Some(
+                        ApiAction::LoadMembers.error_message(response.error.as_ref()),
+                    ));
                 }
             } else {
-                error_message_state.set(Some("Сервер не отвечает".to_string()));
+                error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
             }
         });
     }
@@ -285,7 +490,7 @@ fn ChatMembersList(props: &ChatMembersListProps) -> Html {
     html! {
         <>
         { for (*chat_members_state).iter().map(|chat_member| html! {
-            <ChatMemberButton member={chat_member.clone()}/>
+            <ChatMemberButton member={chat_member.clone()} self_member={self_member.clone()}/>
         })}
         if let Some(err) = (*error_message_state).clone() {
             <ErrorMessage
@@ -0,0 +1,412 @@
+use bitum_frontend::get_random_color_image_url;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::prelude::{use_navigator, Link};
+
+use crate::{
+    api::{
+        delete_chat, get_chat, get_chat_members, patch_chat, upload_file, Chat, ChatMember,
+        PatchChatRequest,
+    },
+    components::{
+        use_current_user, ErrorMessage, Footer, Header, Modal, TextMessage, TypedConfirmModal,
+    },
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::Route,
+    validation::validate_chat_name,
+};
+
+#[derive(PartialEq, Properties)]
+pub struct ChatSettingsRouteProps {
+    pub chat_id: i32,
+}
+
+#[function_component]
+pub fn ChatSettingsRoute(props: &ChatSettingsRouteProps) -> Html {
+    let ChatSettingsRouteProps { chat_id } = props;
+    let user = use_current_user();
+    let navigator = use_navigator().unwrap();
+
+    let error_message_state = use_state(|| Option::<String>::None);
+    let success_message_state = use_state(|| Option::<String>::None);
+    let chat_state = use_state(|| Option::<Chat>::None);
+    let members_state = use_state(|| Option::<Vec<ChatMember>>::None);
+    let icon_name_state = use_state(|| Option::<String>::None);
+    let new_owner_state = use_state(|| Option::<i32>::None);
+    let transfer_modal_visible = use_state(|| false);
+    let delete_modal_visible = use_state(|| false);
+    let name_input_node = use_node_ref();
+    let icon_input_node = use_node_ref();
+
+    if chat_state.is_none() {
+        let error_message_state = error_message_state.clone();
+        let chat_state = chat_state.clone();
+        let chat_id = *chat_id;
+
+        spawn_local(async move {
+            let response = get_chat(chat_id).await;
+
+            if let Ok(response) = response {
+                if response.ok {
+                    chat_state.set(response.data);
+                } else {
+                    error_message_state.set(Some(
+                        ApiAction::LoadChat.error_message(response.error.as_ref()),
+                    ));
+                }
+            } else {
+                error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+            }
+        });
+    }
+
+    if members_state.is_none() {
+        let error_message_state = error_message_state.clone();
+        let members_state = members_state.clone();
+        let chat_id = *chat_id;
+
+        spawn_local(async move {
+            let response = get_chat_members(chat_id).await;
+
+            if let Ok(response) = response {
+                if response.ok {
+                    members_state.set(response.data);
+                } else {
+                    error_message_state.set(Some(
+                        ApiAction::LoadMembers.error_message(response.error.as_ref()),
+                    ));
+                }
+            } else {
+                error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+            }
+        });
+    }
+
+    let on_icon_upload = {
+        let icon_input_node = icon_input_node.clone();
+        let error_message_state = error_message_state.clone();
+        let icon_name_state = icon_name_state.clone();
+
+        Callback::from(move |_: InputEvent| {
+            let icon_name_state = icon_name_state.clone();
+            let error_message_state = error_message_state.clone();
+
+            if let Some(files) = icon_input_node.cast::<HtmlInputElement>() {
+                let files = files.files().unwrap();
+
+                if let Some(icon_file) = files.get(0) {
+                    spawn_local(async move {
+                        let response = upload_file(icon_file).await;
+
+                        if let Ok(response) = response {
+                            if !response.ok {
+                                error_message_state.set(Some(
+                                    ApiAction::UploadFile.error_message(response.error.as_ref()),
+                                ));
+                            } else {
+                                icon_name_state.set(response.data.unwrap().first().cloned());
+                            }
+                        } else {
+                            error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                        }
+                    });
+                }
+            }
+        })
+    };
+
+    let on_save = {
+        let name_input_node = name_input_node.clone();
+        let error_message_state = error_message_state.clone();
+        let success_message_state = success_message_state.clone();
+        let chat_state = chat_state.clone();
+        let icon_name_state = icon_name_state.clone();
+        let chat_id = *chat_id;
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            let error_message_state = error_message_state.clone();
+            let success_message_state = success_message_state.clone();
+            let chat_state = chat_state.clone();
+            let icon_name_state = icon_name_state.clone();
+
+            let chat_name = match name_input_node.cast::<HtmlInputElement>() {
+                Some(input) => input.value(),
+                None => return,
+            };
+
+            if let Err(err) = validate_chat_name(&chat_name) {
+                error_message_state.set(Some(err));
+                return;
+            }
+
+            spawn_local(async move {
+                let response = patch_chat(
+                    chat_id,
+                    PatchChatRequest {
+                        name: Some(chat_name),
+                        icon_file: (*icon_name_state).clone(),
+                        owner_id: None,
+                    },
+                )
+                .await;
+
+                if let Ok(response) = response {
+                    if response.ok {
+                        chat_state.set(response.data);
+                        icon_name_state.set(None);
+                        success_message_state.set(Some("Изменения сохранены".to_string()));
+                    } else {
+                        error_message_state.set(Some(
+                            ApiAction::EditChat.error_message(response.error.as_ref()),
+                        ));
+                    }
+                } else {
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                }
+            });
+        })
+    };
+
+    let on_new_owner_change = {
+        let new_owner_state = new_owner_state.clone();
+
+        Callback::from(move |event: Event| {
+            let select = event.target_unchecked_into::<HtmlSelectElement>();
+            new_owner_state.set(select.value().parse::<i32>().ok());
+        })
+    };
+
+    let on_transfer_click = {
+        let transfer_modal_visible = transfer_modal_visible.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            transfer_modal_visible.set(true);
+        })
+    };
+
+    let on_transfer_confirm = {
+        let error_message_state = error_message_state.clone();
+        let new_owner_state = new_owner_state.clone();
+        let navigator = navigator.clone();
+        let chat_id = *chat_id;
+
+        Callback::from(move |_: ()| {
+            let error_message_state = error_message_state.clone();
+            let navigator = navigator.clone();
+
+            let new_owner_id = match *new_owner_state {
+                Some(new_owner_id) => new_owner_id,
+                None => return,
+            };
+
+            spawn_local(async move {
+                let response = patch_chat(
+                    chat_id,
+                    PatchChatRequest {
+                        owner_id: Some(new_owner_id),
+                        ..Default::default()
+                    },
+                )
+                .await;
+
+                if let Ok(response) = response {
+                    if response.ok {
+                        navigator.push(&Route::Chat { id: chat_id });
+                    } else {
+                        error_message_state.set(Some(
+                            ApiAction::TransferOwnership.error_message(response.error.as_ref()),
+                        ));
+                    }
+                } else {
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                }
+            });
+        })
+    };
+
+    let on_delete_click = {
+        let delete_modal_visible = delete_modal_visible.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            delete_modal_visible.set(true);
+        })
+    };
+
+    let on_delete_confirm = {
+        let error_message_state = error_message_state.clone();
+        let navigator = navigator.clone();
+        let chat_id = *chat_id;
+
+        Callback::from(move |_: ()| {
+            let error_message_state = error_message_state.clone();
+            let navigator = navigator.clone();
+
+            spawn_local(async move {
+                let response = delete_chat(chat_id).await;
+
+                if let Ok(response) = response {
+                    if response.ok {
+                        navigator.push(&Route::Chats);
+                    } else {
+                        error_message_state.set(Some(
+                            ApiAction::DeleteChat.error_message(response.error.as_ref()),
+                        ));
+                    }
+                } else {
+                    error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                }
+            });
+        })
+    };
+
+    let is_owner = match (user.as_ref(), (*chat_state).as_ref()) {
+        (Some(user), Some(chat)) => chat.owner.id == user.id,
+        _ => false,
+    };
+
+    let new_owner_name = (*members_state)
+        .iter()
+        .flatten()
+        .find(|member| Some(member.user.id) == *new_owner_state)
+        .map(|member| member.user.username.clone())
+        .unwrap_or_default();
+
+    html! {
+        <>
+        <Header/>
+        if let Some(chat) = (*chat_state).clone() {
+            <h1 class="fw-medium fs-1">
+                <Link<Route> classes="text-decoration-none text-reset" to={Route::Chat { id: chat.id }}>
+                    <i class="bi bi-arrow-left-short"></i>
+                </Link<Route>>
+                <span class="p-3">
+                    {"Настройки чата "}
+                    {chat.name.clone()}
+                </span>
+            </h1>
+
+            if !is_owner {
+                <div class="alert alert-warning">
+                    {"Изменять настройки чата может только его владелец"}
+                </div>
+            } else {
+                <div class="row">
+                    <div class="col-lg-6 col-md-12 gy-3">
+                        <h2 class="fs-3">{"Основное"}</h2>
+                        <form class="gy-3 row" onsubmit={on_save}>
+                            <div class="col-12 d-flex align-items-center">
+                                <img class="rounded-3 border object-fit-scale" width=100px height=100px src={
+                                    if let Some(icon_name) = (*icon_name_state).clone() {
+                                        format!("/api/files/{}", icon_name)
+                                    } else if chat.icon.is_some() {
+                                        format!("/api/files/{}", chat.icon.clone().unwrap_or("null.png".to_string()))
+                                    } else {
+                                        get_random_color_image_url(chat.name.clone(), 100, 100)
+                                    }
+                                } alt="Chat icon" />
+                                <div class="p-3 flex-grow-1">
+                                    <label class="fw-medium fs-5">{"Иконка"}</label>
+                                    <input oninput={on_icon_upload} type="file" ref={icon_input_node} class="form-control" accept="image/png, image/jpeg, image/jpg" />
+                                </div>
+                            </div>
+                            <div class="col-12">
+                                <label class="fw-medium fs-5">{"Имя чата"}</label>
+                                <input ref={name_input_node} value={chat.name.clone()} type="text" class="form-control" aria-label="chat_name" required=true />
+                            </div>
+                            <div class="col-12">
+                                <button type="submit" class="btn btn-success m-0">{"Сохранить"}</button>
+                            </div>
+                        </form>
+                    </div>
+
+                    <div class="col-lg-6 col-md-12 gy-3">
+                        <h2 class="fs-3">{"Владелец"}</h2>
+                        <div class="input-group">
+                            <select class="form-select" onchange={on_new_owner_change}>
+                                <option value="" selected={new_owner_state.is_none()}>{"Выберите участника"}</option>
+                                { for (*members_state).iter().flatten()
+                                    .filter(|member| member.user.id != chat.owner.id && !member.user.is_bot)
+                                    .map(|member| html! {
+                                        <option value={member.user.id.to_string()}>{member.user.username.clone()}</option>
+                                    })
+                                }
+                            </select>
+                            <button onclick={on_transfer_click} class="btn btn-outline-warning m-0" disabled={new_owner_state.is_none()}>
+                                {"Передать"}
+                            </button>
+                        </div>
+
+                        <h2 class="fs-3 pt-4 text-danger">{"Удаление"}</h2>
+                        <p>{"Чат будет удалён вместе со всеми сообщениями и участниками."}</p>
+                        <button onclick={on_delete_click} class="btn btn-danger m-0">{"Удалить чат"}</button>
+                    </div>
+                </div>
+
+                <Modal
+                    modal_id={"transfer-chat-modal".to_string()}
+                    is_visible={*transfer_modal_visible}
+                    on_ok={on_transfer_confirm}
+                    on_cancel={Callback::from(|_| {})}
+                    on_close={
+                        let transfer_modal_visible = transfer_modal_visible.clone();
+                        Callback::from(move |_| transfer_modal_visible.set(false))
+                    }
+                >
+                    <div class="modal-header">
+                        <h1 class="modal-title fs-5">{"Передать чат"}</h1>
+                    </div>
+                    <div class="modal-body">
+                        {"Владельцем чата станет "}
+                        <span class="fw-semibold">{new_owner_name}</span>
+                        {" и получит все права в чате. Вы больше не сможете изменять настройки чата."}
+                    </div>
+                </Modal>
+
+                <TypedConfirmModal
+                    modal_id={"delete-chat-modal".to_string()}
+                    is_visible={*delete_modal_visible}
+                    title={"Удалить чат".to_string()}
+                    description={"Это действие нельзя отменить.".to_string()}
+                    confirmation_text={chat.name.clone()}
+                    on_confirm={on_delete_confirm}
+                    on_close={
+                        let delete_modal_visible = delete_modal_visible.clone();
+                        Callback::from(move |_| delete_modal_visible.set(false))
+                    }
+                />
+            }
+        }
+        <Footer/>
+
+        if let Some(message) = (*success_message_state).clone() {
+            <TextMessage
+                on_close={
+                    let success_message_state = success_message_state.clone();
+
+                    Callback::from(move |_| {
+                        success_message_state.set(None);
+                    })
+                }
+                value={message}
+                toast_classes={classes!("bg-success", "text-bg-primary")}
+            />
+        }
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
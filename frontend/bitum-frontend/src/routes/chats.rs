@@ -1,29 +1,152 @@
-use bitum_frontend::get_random_color_image_url;
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use bitum_frontend::{display_timestamp_short, get_random_color_image_url, markdown_to_plain_text};
 use gloo_timers::future::TimeoutFuture;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
 use yew::prelude::*;
-use yew_router::prelude::Link;
+use yew_router::prelude::{use_location, Link};
 
 use crate::{
-    api::get_chats,
-    components::{ErrorMessage, Footer, Header, NewChatModalButton},
+    api::{get_chats, get_last_messages, Chat, ChatMessage, EventType},
+    components::{
+        known_direct_chat_peer, use_chat_appearance, use_current_user, use_events,
+        ChatNotificationMode, ChatOrganization, ChatOrganizationContext, ErrorMessage, Footer,
+        Header, Modal, NewChatModalButton, NotificationSettingsContext, TextMessage, UnreadContext,
+    },
     constants::API_REFRESH_MILLIS,
+    preferences::Preferences,
     routes::Route,
 };
 
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct ChatsRouteQuery {
+    /// Имя чата, из которого исключили пользователя
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kicked_from: Option<String>,
+}
+
 #[derive(PartialEq, Properties)]
 pub struct ChatsRouteProps {}
 
+const MESSAGE_PREVIEW_MAX_CHARS: usize = 60;
+
+/// Последние сообщения для карточек чатов
+#[derive(Default, PartialEq)]
+struct ChatPreviews {
+    last_messages: HashMap<i32, ChatMessage>,
+}
+
+enum ChatPreviewsAction {
+    MessageLoaded(ChatMessage),
+}
+
+impl ChatPreviews {
+    fn last_activity(&self, chat: &Chat) -> f64 {
+        self.last_messages
+            .get(&chat.id)
+            .map(|message| message.created_at)
+            .unwrap_or(chat.created_at)
+    }
+}
+
+impl Reducible for ChatPreviews {
+    type Action = ChatPreviewsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut last_messages = self.last_messages.clone();
+        let ChatPreviewsAction::MessageLoaded(message) = action;
+
+        let is_newer = last_messages
+            .get(&message.chat.id)
+            .map(|last_message| last_message.id < message.id)
+            .unwrap_or(true);
+
+        if is_newer {
+            last_messages.insert(message.chat.id, message);
+        }
+
+        Rc::new(ChatPreviews { last_messages })
+    }
+}
+
 #[function_component]
 pub fn ChatsRoute(props: &ChatsRouteProps) -> Html {
     let ChatsRouteProps {} = props;
 
+    let user = use_current_user();
+    let unread = use_context::<UnreadContext>();
     let error_message_state = use_state(|| Option::<String>::None);
-    let chats_state = use_state(|| Vec::new());
+    let chats_state = use_state(Vec::<Chat>::new);
+    let previews = use_reducer(ChatPreviews::default);
+    let requested_previews = use_mut_ref(HashSet::<i32>::new);
+    let search_state = use_state(String::new);
+    let hour12 = *use_memo(|_| Preferences::load().hour12(), ());
+    let organization = use_context::<ChatOrganizationContext>().unwrap();
+    let notification_settings = use_context::<NotificationSettingsContext>();
+    let notification_modes = notification_settings
+        .as_ref()
+        .map(|settings| settings.chat_modes.clone())
+        .unwrap_or_default();
+    let selected_folder = use_state(|| Option::<usize>::None);
+    let new_folder_modal_visible = use_state(|| false);
+    let new_folder_name = use_state(String::new);
+    let transfer_modal_visible = use_state(|| false);
+    let transfer_json = use_state(String::new);
+    let query = use_location()
+        .and_then(|location| location.query::<ChatsRouteQuery>().ok())
+        .unwrap_or_default();
+    let notice_state = use_state(|| {
+        query
+            .kicked_from
+            .map(|chat_name| format!("Вас исключили из чата «{}»", chat_name))
+    });
+
+    {
+        let chats_state = chats_state.clone();
+        let notice_state = notice_state.clone();
+        let previews = previews.clone();
+        let user_id = user.as_ref().map(|user| user.id);
+
+        use_events(move |event| match event {
+            EventType::NewMessage(message) => {
+                previews.dispatch(ChatPreviewsAction::MessageLoaded(message.clone()));
+            }
+            EventType::MemberAdded(chat_member) => {
+                if Some(chat_member.user.id) == user_id
+                    && !chats_state
+                        .iter()
+                        .any(|chat| chat.id == chat_member.chat.id)
+                {
+                    let mut chats = (*chats_state).clone();
+                    chats.push(chat_member.chat.clone());
+                    chats_state.set(chats);
+                }
+            }
+            EventType::MemberKicked { user, chat } => {
+                if Some(user.id) == user_id {
+                    chats_state.set(
+                        chats_state
+                            .iter()
+                            .filter(|known_chat| known_chat.id != chat.id)
+                            .cloned()
+                            .collect(),
+                    );
+                    notice_state.set(Some(format!("Вас исключили из чата «{}»", chat.name)));
+                }
+            }
+        });
+    }
 
     {
         let chats_state = chats_state.clone();
         let error_message_state = error_message_state.clone();
+        let previews = previews.clone();
+        let requested_previews = requested_previews.clone();
 
         spawn_local(async move {
             TimeoutFuture::new(API_REFRESH_MILLIS).await;
@@ -32,7 +155,28 @@ pub fn ChatsRoute(props: &ChatsRouteProps) -> Html {
 
             if let Ok(response) = response {
                 if response.ok {
-                    chats_state.set(response.data.unwrap());
+                    let chats = response.data.unwrap();
+
+                    // Последние сообщения приходят одним запросом и только когда появились новые чаты,
+                    // дальше превью обновляются по событиям
+                    let new_chats_count = chats
+                        .iter()
+                        .filter(|chat| requested_previews.borrow_mut().insert(chat.id))
+                        .count();
+
+                    if new_chats_count > 0 {
+                        let previews = previews.clone();
+
+                        spawn_local(async move {
+                            if let Ok(response) = get_last_messages().await {
+                                for message in response.data.unwrap_or_default() {
+                                    previews.dispatch(ChatPreviewsAction::MessageLoaded(message));
+                                }
+                            }
+                        });
+                    }
+
+                    chats_state.set(chats);
                 } else {
                     error_message_state.set(Some("Не удалось получить список чатов".to_string()));
                 }
@@ -42,42 +186,271 @@ pub fn ChatsRoute(props: &ChatsRouteProps) -> Html {
         });
     }
 
+    let on_search_input = {
+        let search_state = search_state.clone();
+
+        Callback::from(move |event: InputEvent| {
+            let input = event.target_unchecked_into::<HtmlInputElement>();
+            search_state.set(input.value());
+        })
+    };
+
+    let on_new_folder_ok = {
+        let organization = organization.clone();
+        let new_folder_name = new_folder_name.clone();
+
+        Callback::from(move |_: ()| {
+            let name = new_folder_name.trim().to_string();
+
+            if !name.is_empty() {
+                organization.set(organization.with_folder(name));
+            }
+        })
+    };
+
+    let on_transfer_ok = {
+        let organization = organization.clone();
+        let notification_settings = notification_settings.clone();
+        let notification_modes = notification_modes.clone();
+        let transfer_json = transfer_json.clone();
+        let error_message_state = error_message_state.clone();
+        let notice_state = notice_state.clone();
+        let selected_folder = selected_folder.clone();
+
+        Callback::from(move |_: ()| {
+            if *transfer_json == organization.to_json(&notification_modes) {
+                return;
+            }
+
+            match ChatOrganization::from_json(&transfer_json) {
+                Ok((imported, imported_modes)) => {
+                    organization.set(imported);
+
+                    if let Some(settings) = &notification_settings {
+                        settings.set(settings.with_chat_modes(imported_modes));
+                    }
+
+                    selected_folder.set(None);
+                    notice_state.set(Some("Настройки чатов импортированы".to_string()));
+                }
+                Err(err) => error_message_state.set(Some(err)),
+            }
+        })
+    };
+
+    let search = search_state.trim().to_lowercase();
+    let folder = selected_folder.and_then(|index| organization.folders.get(index));
+    let mut chats: Vec<&Chat> = chats_state
+        .iter()
+        .filter(|chat| {
+            let name = user
+                .as_ref()
+                .and_then(|user| known_direct_chat_peer(chat, user.id))
+                .map(|peer| peer.username)
+                .unwrap_or_else(|| chat.name.clone());
+
+            search.is_empty() || name.to_lowercase().contains(&search)
+        })
+        .filter(|chat| folder.is_none_or(|folder| folder.chat_ids.contains(&chat.id)))
+        .collect();
+    chats.sort_by(|a, b| {
+        organization
+            .is_pinned(b.id)
+            .cmp(&organization.is_pinned(a.id))
+            .then(
+                previews
+                    .last_activity(b)
+                    .total_cmp(&previews.last_activity(a)),
+            )
+    });
+
     html! {
         <>
             <Header/>
-            <h1 class="fw-medium fs-1">
-                {"Чаты "}
-                <NewChatModalButton redirect=true chat_name={"".to_string()}>
-                    //<button class="btn btn-light btn-lg">
-                            <i class="bi bi-plus-square-fill fs-1 p-3"></i>
-                    //</button>
-                </NewChatModalButton>
-            </h1>
+            <div class="d-flex flex-wrap justify-content-between align-items-center">
+                <h1 class="fw-medium fs-1">
+                    {"Чаты "}
+                    <NewChatModalButton redirect=true chat_name={"".to_string()}>
+                        //<button class="btn btn-light btn-lg">
+                                <i class="bi bi-plus-square-fill fs-1 p-3"></i>
+                        //</button>
+                    </NewChatModalButton>
+                </h1>
+                <div class="input-group chats-search">
+                    <span class="input-group-text"><i class="bi bi-search"></i></span>
+                    <input oninput={on_search_input} value={(*search_state).clone()} type="search" placeholder="Поиск" class="form-control" aria-label="search" />
+                    <button
+                        onclick={
+                            let transfer_modal_visible = transfer_modal_visible.clone();
+                            let transfer_json = transfer_json.clone();
+                            let organization = organization.clone();
+                            let notification_modes = notification_modes.clone();
+
+                            Callback::from(move |_: MouseEvent| {
+                                transfer_json.set(organization.to_json(&notification_modes));
+                                transfer_modal_visible.set(true);
+                            })
+                        }
+                        type="button"
+                        class="btn btn-outline-secondary"
+                        title="Экспорт и импорт настроек чатов"
+                    >
+                        <i class="bi bi-arrow-down-up"></i>
+                    </button>
+                </div>
+            </div>
+            <ul class="nav nav-tabs pb-2">
+                <li class="nav-item">
+                    <button
+                        onclick={
+                            let selected_folder = selected_folder.clone();
+                            Callback::from(move |_: MouseEvent| selected_folder.set(None))
+                        }
+                        class={classes!("nav-link", selected_folder.is_none().then_some("active"))}
+                    >
+                        {"Все"}
+                    </button>
+                </li>
+                { for organization.folders.iter().enumerate().map(|(index, folder)| {
+                    let is_active = *selected_folder == Some(index);
+
+                    html! {
+                        <li class="nav-item">
+                            <button
+                                onclick={
+                                    let selected_folder = selected_folder.clone();
+                                    Callback::from(move |_: MouseEvent| selected_folder.set(Some(index)))
+                                }
+                                class={classes!("nav-link", is_active.then_some("active"))}
+                            >
+                                {folder.name.clone()}
+                                if is_active {
+                                    {" "}
+                                    <i
+                                        onclick={
+                                            let selected_folder = selected_folder.clone();
+                                            let organization = organization.clone();
+
+                                            Callback::from(move |event: MouseEvent| {
+                                                event.stop_propagation();
+                                                selected_folder.set(None);
+                                                organization.set(organization.without_folder(index));
+                                            })
+                                        }
+                                        role="button"
+                                        title="Удалить папку"
+                                        class="bi bi-x-lg danger-hover"
+                                    ></i>
+                                }
+                            </button>
+                        </li>
+                    }
+                })}
+                <li class="nav-item">
+                    <button
+                        onclick={
+                            let new_folder_modal_visible = new_folder_modal_visible.clone();
+                            let new_folder_name = new_folder_name.clone();
+
+                            Callback::from(move |_: MouseEvent| {
+                                new_folder_name.set(String::new());
+                                new_folder_modal_visible.set(true);
+                            })
+                        }
+                        class="nav-link"
+                        title="Новая папка"
+                    >
+                        <i class="bi bi-folder-plus"></i>
+                    </button>
+                </li>
+            </ul>
             <div class="row">
                 {
-                    for (*chats_state).iter().map(|chat| {
+                    for chats.into_iter().map(|chat| {
                         html! {
-                            <div class="col-lg-4 col-md-12 p-2">
-                                <Link<Route> classes="text-decoration-none d-flex grow-on-hover" to={Route::Chat {id: chat.id.clone()}}>
-                                    <img class="rounded-start-2 border object-fit-scale" height=75px src={
-                            if chat.icon.is_some() {
-                                format!("/api/files/{}", (*chat).clone().icon.unwrap_or("null.png".to_string()))
-                            } else {
-                                get_random_color_image_url(chat.name.clone(), 75, 75)
-                            }
-                        } alt="Chat icon"/>
-                                    <div class="rounded-end-2 text-overflow-ellipsis d-flex border border-start-0 bg-white flex-grow-1 align-items-center">
-                                        <div class="p-3 fs-4 text-dark fw-normal">
-                                            {chat.name.clone()}
-                                        </div>
-                                    </div>
-                                </Link<Route>>
-                            </div>
+                            <ChatCard
+                                chat={chat.clone()}
+                                last_message={previews.last_messages.get(&chat.id).cloned()}
+                                unread_count={unread.as_ref().map(|unread| unread.unread_count(chat.id)).unwrap_or_default()}
+                                {hour12}
+                            />
                         }
                     })
                 }
             </div>
             <Footer/>
+            <Modal
+                modal_id={"new-folder-modal".to_string()}
+                is_visible={*new_folder_modal_visible}
+                on_ok={on_new_folder_ok}
+                on_cancel={Callback::from(|_| {})}
+                on_close={
+                    let new_folder_modal_visible = new_folder_modal_visible.clone();
+                    Callback::from(move |_| new_folder_modal_visible.set(false))
+                }
+                ok_disabled={new_folder_name.trim().is_empty()}
+            >
+                <div class="modal-header">
+                    <h1 class="modal-title fs-5">{"Новая папка"}</h1>
+                </div>
+                <div class="modal-body">
+                    <input
+                        oninput={
+                            let new_folder_name = new_folder_name.clone();
+                            Callback::from(move |event: InputEvent| {
+                                new_folder_name.set(event.target_unchecked_into::<HtmlInputElement>().value());
+                            })
+                        }
+                        value={(*new_folder_name).clone()}
+                        type="text"
+                        placeholder="Название, например «Боты»"
+                        class="form-control"
+                    />
+                </div>
+            </Modal>
+            <Modal
+                modal_id={"chats-transfer-modal".to_string()}
+                is_visible={*transfer_modal_visible}
+                on_ok={on_transfer_ok}
+                on_cancel={Callback::from(|_| {})}
+                on_close={
+                    let transfer_modal_visible = transfer_modal_visible.clone();
+                    Callback::from(move |_| transfer_modal_visible.set(false))
+                }
+            >
+                <div class="modal-header">
+                    <h1 class="modal-title fs-5">{"Экспорт и импорт"}</h1>
+                </div>
+                <div class="modal-body">
+                    <p class="text-body-secondary">
+                        {"Скопируйте JSON, чтобы перенести закреплённые чаты, папки и режимы уведомлений в другой браузер, или вставьте сюда сохранённый ранее."}
+                    </p>
+                    <textarea
+                        oninput={
+                            let transfer_json = transfer_json.clone();
+                            Callback::from(move |event: InputEvent| {
+                                transfer_json.set(event.target_unchecked_into::<HtmlTextAreaElement>().value());
+                            })
+                        }
+                        value={(*transfer_json).clone()}
+                        rows="10"
+                        class="form-control font-monospace"
+                    ></textarea>
+                </div>
+            </Modal>
+            if let Some(notice) = (*notice_state).clone() {
+                <TextMessage
+                    on_close={
+                        let notice_state = notice_state.clone();
+
+                        Callback::from(move |_| {
+                            notice_state.set(None);
+                        })
+                    }
+                    value={notice}
+                    toast_classes={classes!("bg-warning", "text-bg-warning")}
+                />
+            }
             if let Some(err) = (*error_message_state).clone() {
                 <ErrorMessage
                     on_close={
@@ -93,3 +466,140 @@ pub fn ChatsRoute(props: &ChatsRouteProps) -> Html {
         </>
     }
 }
+
+#[derive(PartialEq, Properties)]
+struct ChatCardProps {
+    chat: Chat,
+    last_message: Option<ChatMessage>,
+    unread_count: u32,
+    hour12: bool,
+}
+
+#[function_component]
+fn ChatCard(props: &ChatCardProps) -> Html {
+    let ChatCardProps {
+        chat,
+        last_message,
+        unread_count,
+        hour12,
+    } = props;
+    let organization = use_context::<ChatOrganizationContext>().unwrap();
+    let appearance = use_chat_appearance(Some(chat)).unwrap();
+    let is_pinned = organization.is_pinned(chat.id);
+    let notification_settings = use_context::<NotificationSettingsContext>();
+    let is_muted = notification_settings
+        .as_ref()
+        .map(|settings| settings.is_muted(chat.id))
+        .unwrap_or(false);
+
+    let menu_item = |label: String, icon: &'static str, updated: ChatOrganization| {
+        let organization = organization.clone();
+
+        html! {
+            <li>
+                <button
+                    onclick={Callback::from(move |_: MouseEvent| organization.set(updated.clone()))}
+                    type="button"
+                    class="dropdown-item"
+                >
+                    <i class={classes!("bi", icon)}></i>
+                    {" "}
+                    {label}
+                </button>
+            </li>
+        }
+    };
+
+    html! {
+        <div class="col-lg-4 col-md-12 p-2 position-relative">
+            <div class="dropdown position-absolute top-0 end-0 chat-card-menu">
+                <i role="button" data-bs-toggle="dropdown" aria-expanded="false" class="bi bi-three-dots-vertical fs-5 info-hover"></i>
+                <ul class="dropdown-menu">
+                    if is_pinned {
+                        { menu_item("Открепить".to_string(), "bi-pin-angle", organization.with_pinned(chat.id, false)) }
+                    } else {
+                        { menu_item("Закрепить".to_string(), "bi-pin-angle-fill", organization.with_pinned(chat.id, true)) }
+                    }
+                    if let Some(settings) = notification_settings {
+                        <li>
+                            <button
+                                onclick={
+                                    let chat_id = chat.id;
+                                    let mode = if is_muted { ChatNotificationMode::All } else { ChatNotificationMode::Muted };
+                                    Callback::from(move |_: MouseEvent| settings.set(settings.with_chat_mode(chat_id, mode)))
+                                }
+                                type="button"
+                                class="dropdown-item"
+                            >
+                                <i class={classes!("bi", if is_muted { "bi-volume-up" } else { "bi-volume-mute" })}></i>
+                                {" "}
+                                {if is_muted { "Включить звук" } else { "Заглушить" }}
+                            </button>
+                        </li>
+                    }
+                    if !organization.folders.is_empty() {
+                        <li><hr class="dropdown-divider" /></li>
+                        <li><h6 class="dropdown-header">{"Папки"}</h6></li>
+                    }
+                    { for organization.folders.iter().enumerate().map(|(index, folder)| {
+                        let in_folder = folder.chat_ids.contains(&chat.id);
+
+                        menu_item(
+                            folder.name.clone(),
+                            if in_folder { "bi-folder-check" } else { "bi-folder" },
+                            organization.with_chat_in_folder(index, chat.id, !in_folder),
+                        )
+                    })}
+                </ul>
+            </div>
+            <Link<Route> classes="text-decoration-none d-flex grow-on-hover" to={Route::Chat {id: chat.id}}>
+                <img class="rounded-start-2 border object-fit-scale" height=75px src={
+                    if appearance.icon.is_some() {
+                        format!("/api/files/{}", appearance.icon.clone().unwrap_or("null.png".to_string()))
+                    } else {
+                        get_random_color_image_url(appearance.name.clone(), 75, 75)
+                    }
+                } alt="Chat icon"/>
+                <div class="rounded-end-2 text-overflow-ellipsis d-flex border border-start-0 bg-white flex-grow-1 align-items-center">
+                    <div class="ps-3 pe-4 text-overflow-ellipsis flex-grow-1">
+                        <div class="d-flex justify-content-between align-items-center">
+                            <div class="fs-4 text-dark fw-normal text-overflow-ellipsis">
+                                if is_pinned {
+                                    <i class="bi bi-pin-angle-fill fs-6 text-secondary" title="Закреплён"></i>
+                                    {" "}
+                                }
+                                if appearance.is_direct {
+                                    <i class="bi bi-person fs-6 text-secondary" title="Личный чат"></i>
+                                    {" "}
+                                }
+                                {appearance.name.clone()}
+                                if is_muted {
+                                    {" "}
+                                    <i class="bi bi-volume-mute fs-6 text-secondary" title="Заглушён"></i>
+                                }
+                            </div>
+                            if let Some(message) = last_message {
+                                <div class="text-body-secondary fw-light">
+                                    {display_timestamp_short(message.created_at as i64, *hour12)}
+                                </div>
+                            }
+                        </div>
+                        <div class="d-flex justify-content-between align-items-center">
+                            <div class="text-body-secondary text-overflow-ellipsis">
+                                if let Some(message) = last_message {
+                                    <span class="fw-medium">{message.sender.username.clone()}{": "}</span>
+                                    {markdown_to_plain_text(&message.content, MESSAGE_PREVIEW_MAX_CHARS)}
+                                } else {
+                                    {"Сообщений пока нет"}
+                                }
+                            </div>
+                            if *unread_count > 0 && !is_muted {
+                                <span class="badge rounded-pill text-bg-success">{unread_count}</span>
+                            }
+                        </div>
+                    </div>
+                </div>
+            </Link<Route>>
+        </div>
+    }
+}
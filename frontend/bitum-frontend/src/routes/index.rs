@@ -1,7 +1,10 @@
 use yew::prelude::*;
-use yew_router::prelude::Link;
+use yew_router::prelude::{Link, Redirect};
 
-use crate::{components::Footer, routes::Route};
+use crate::{
+    components::{use_current_user, Footer},
+    routes::Route,
+};
 
 #[derive(PartialEq, Properties)]
 pub struct IndexRouteProps {}
@@ -9,6 +12,11 @@ pub struct IndexRouteProps {}
 #[function_component]
 pub fn IndexRoute(props: &IndexRouteProps) -> Html {
     let IndexRouteProps {} = props;
+
+    if use_current_user().is_some() {
+        return html! { <Redirect<Route> to={Route::Chats} /> };
+    }
+
     html! {
         <>
         <div class="d-block text-center">
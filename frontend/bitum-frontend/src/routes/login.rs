@@ -1,14 +1,29 @@
+use serde::{Deserialize, Serialize};
 use wasm_bindgen_futures::spawn_local;
-use web_sys::HtmlInputElement;
 use yew::prelude::*;
-use yew_router::prelude::{use_navigator, Link};
+use yew_router::prelude::{use_location, use_navigator, Link};
 
 use crate::{
-    api::{get_user_token, GetTokenRequest},
-    components::{ErrorMessage, Footer, Header},
-    routes::Route,
+    api::{get_password_reset_info, get_user_token, GetTokenRequest},
+    components::{use_auth, ErrorMessage, Footer, FormField, Header},
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::{redirect_target, register::RegisterRouteQuery, Route},
+    validation::{validate_password, validate_username},
 };
 
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct LoginRouteQuery {
+    /// Пользователя выкинуло из аккаунта из-за истёкшего токена
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub session_expired: bool,
+    /// Аккаунт создан, но войти сразу после регистрации не получилось
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub registered: bool,
+    /// Путь, на который вернуть пользователя после входа
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+}
+
 #[derive(PartialEq, Properties)]
 pub struct LoginRouteProps {}
 
@@ -17,83 +32,132 @@ pub fn LoginRoute(props: &LoginRouteProps) -> Html {
     let LoginRouteProps {} = props;
 
     let error_message_state = use_state(|| Option::<String>::None);
-    let username_input_node = use_node_ref();
-    let password_input_node = use_node_ref();
+    let username_state = use_state(String::new);
+    let password_state = use_state(String::new);
     let navigator = use_navigator().unwrap();
+    let auth = use_auth();
+    let query = use_location()
+        .and_then(|location| location.query::<LoginRouteQuery>().ok())
+        .unwrap_or_default();
+    // Сброс пароля включён не на каждом сервере, без него ссылку не показываем
+    let reset_available_state = use_state(|| false);
+
+    {
+        let reset_available_state = reset_available_state.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                spawn_local(async move {
+                    if let Ok(response) = get_password_reset_info().await {
+                        reset_available_state
+                            .set(response.ok && response.data.is_some_and(|info| info.available));
+                    }
+                });
+            },
+            (),
+        );
+    }
+
+    let username_error = validate_username(&username_state).err();
+    let password_error = validate_password(&password_state).err();
+    let is_valid = username_error.is_none() && password_error.is_none();
 
     let onsubmit = {
-        let username_input_node = username_input_node.clone();
-        let password_input_node = password_input_node.clone();
+        let username_state = username_state.clone();
+        let password_state = password_state.clone();
         let error_message_state = error_message_state.clone();
+        let redirect = query.redirect.clone();
+        let refresh = auth.refresh.clone();
 
         Callback::from(move |submit_event: SubmitEvent| {
             let navigator = navigator.clone();
+            let refresh = refresh.clone();
             let error_message_state = error_message_state.clone();
+            let redirect = redirect.clone();
             submit_event.prevent_default();
 
-            if [username_input_node.clone(), password_input_node.clone()]
-                .iter()
-                .any(|input| input.cast::<HtmlInputElement>().is_none())
-            {
+            if !is_valid {
                 return;
             }
 
-            let username = username_input_node
-                .cast::<HtmlInputElement>()
-                .unwrap()
-                .value();
-            let password = password_input_node
-                .cast::<HtmlInputElement>()
-                .unwrap()
-                .value();
+            let username = (*username_state).clone();
+            let password = (*password_state).clone();
 
             spawn_local(async move {
-                let response = get_user_token(GetTokenRequest {
-                    username: username.clone(),
-                    password: password.clone(),
-                })
-                .await;
-
-                let mut error = Option::<String>::None;
-
-                if let Ok(resp) = response {
-                    if !resp.ok {
-                        match resp.error.unwrap().code {
-                            404 => error = Some(String::from("Ошибка! Такого пользователя нет.")),
-                            _ => error = Some(String::from("Неизвестная ошибка!")),
-                        }
-                    } else {
-                        navigator.push(&Route::Chats);
+                let response = get_user_token(GetTokenRequest { username, password }).await;
+
+                match response {
+                    Ok(response) if response.ok => {
+                        refresh.emit(());
+                        navigator.push(&redirect_target(redirect.as_deref()));
                     }
-                } else {
-                    error = Some(String::from("Неизвестная ошибка в работе сервера."))
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::Login.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
                 }
-
-                error_message_state.set(error);
             });
         })
     };
 
+    let text_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+
+        Callback::from(move |value: String| state.set(value))
+    };
+
     html! {
         <>
         <Header />
         <div class="text-center row justify-content-md-center">
             <div class="col-md-4 col-sm-12">
                 <h1 class="fw-light fs-2 p-4">{"Вход"}</h1>
-                <form class="text-start gy-3 row input-group-lg" {onsubmit}>
-                    <div class="col-12 input-group-lg">
-                        <label class="fw-medium fs-5"  >{"Имя пользователя"}</label>
-                        <input ref={username_input_node} type="text" placeholder="username" class="form-control" id="username-input" required=true />
+                if query.registered {
+                    <div class="alert alert-success" role="alert">
+                        {"Аккаунт создан. Войдите, чтобы продолжить."}
                     </div>
-
-                    <div class="col-12 input-group-lg">
-                        <label class="fw-medium fs-5" for="password-input">{"Пароль"}</label>
-                        <input ref={password_input_node} type="password" placeholder="password" class="form-control" id="password-input" required=true />
+                }
+                if query.session_expired {
+                    <div class="alert alert-warning" role="alert">
+                        {"Сессия истекла. Войдите снова."}
                     </div>
+                }
+                <form class="text-start gy-3 row input-group-lg" {onsubmit}>
+                    <FormField
+                        id="username-input"
+                        label="Имя пользователя"
+                        placeholder="username"
+                        autocomplete="username"
+                        value={(*username_state).clone()}
+                        on_input={text_input(&username_state)}
+                        error={username_error}
+                    />
+
+                    <FormField
+                        id="password-input"
+                        label="Пароль"
+                        input_type="password"
+                        placeholder="password"
+                        autocomplete="current-password"
+                        value={(*password_state).clone()}
+                        on_input={text_input(&password_state)}
+                        error={password_error}
+                        revealable=true
+                    >
+                        if *reset_available_state {
+                            <Link<Route> classes="form-text" to={Route::RequestPasswordReset}>{"Забыли пароль?"}</Link<Route>>
+                        }
+                    </FormField>
 
                     <div class="col-12 d-flex p-2 justify-content-between">
-                        <button class="btn btn-success btn-lg" type="submit" id="submit-button">{"Войти"}</button>
-                        <Link<Route> classes="btn btn-light btn-lg" to={Route::Register}>{"Регистрация"}</Link<Route>>
+                        <button disabled={!is_valid} class="btn btn-success btn-lg" type="submit" id="submit-button">{"Войти"}</button>
+                        <Link<Route, RegisterRouteQuery>
+                            classes="btn btn-light btn-lg"
+                            to={Route::Register}
+                            query={Some(RegisterRouteQuery { redirect: query.redirect.clone() })}
+                        >
+                            {"Регистрация"}
+                        </Link<Route, RegisterRouteQuery>>
                     </div>
                 </form>
             </div>
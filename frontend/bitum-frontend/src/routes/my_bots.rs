@@ -3,10 +3,17 @@ use gloo_timers::future::TimeoutFuture;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
+use yew_router::prelude::Link;
+
 use crate::{
-    api::{delete_bot, get_bot_token, get_bots, User},
-    components::{ErrorMessage, Header, LoggedUserInfo, Modal, NewBotModalButton},
+    api::{get_bots, User},
+    components::{
+        use_current_user, BotTokenModalButton, DeleteBotModalButton, ErrorMessage, Header,
+        NewBotModalButton,
+    },
     constants::API_REFRESH_MILLIS,
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::Route,
 };
 
 #[derive(PartialEq, Properties)]
@@ -15,9 +22,9 @@ pub struct MyBotsRouteProps {}
 #[function_component]
 pub fn MyBotsRoute(props: &MyBotsRouteProps) -> Html {
     let MyBotsRouteProps {} = props;
-    let logged_user = use_context::<LoggedUserInfo>();
+    let logged_user = use_current_user();
     let error_message_state = use_state(|| Option::<String>::None);
-    let bots_state = use_state(|| Vec::<User>::new());
+    let bots_state = use_state(Vec::<User>::new);
 
     {
         let error_message_state = error_message_state.clone();
@@ -31,35 +38,62 @@ pub fn MyBotsRoute(props: &MyBotsRouteProps) -> Html {
                 if response.ok {
                     bots_state.set(response.data.unwrap());
                 } else {
-                    error_message_state.set(Some("Не удалось получить список чатов".to_string()));
+                    error_message_state.set(Some(
+                        ApiAction::LoadBots.error_message(response.error.as_ref()),
+                    ));
                 }
             } else {
-                error_message_state.set(Some("Сервер не отвечает".to_string()));
+                error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
             }
         });
     }
 
+    let on_created = {
+        let bots_state = bots_state.clone();
+
+        Callback::from(move |bot: User| {
+            let mut bots = (*bots_state).clone();
+            bots.push(bot);
+            bots_state.set(bots);
+        })
+    };
+
+    let on_deleted = {
+        let bots_state = bots_state.clone();
+
+        Callback::from(move |bot_id: i32| {
+            let mut bots = (*bots_state).clone();
+            bots.retain(|bot| bot.id != bot_id);
+            bots_state.set(bots);
+        })
+    };
+
     html! {
         <>
         <Header />
 
         <h1 class="fw-medium fs-1">
             {"Мои боты "}
-            <NewBotModalButton username={"".to_string()}>
+            <NewBotModalButton username={"".to_string()} {on_created}>
                 <i class="bi bi-plus-square-fill fs-1 p-3"></i>
             </NewBotModalButton>
         </h1>
-
-        if let Some(user) = logged_user {
-            if let Some(_) = user.user {
-                <div class="row">
-                { for (*bots_state).iter().map(|bot| {
-                    html! {
-                        <BotCard bot={bot.clone()} />
-                    }
-                })}
-                </div>
-            }
+        <p>
+            <Link<Route> to={Route::ApiExplorer}>
+                <i class="bi bi-braces"></i>
+                {" API Explorer"}
+            </Link<Route>>
+            {": попробуйте запросы к API прямо в браузере"}
+        </p>
+
+        if logged_user.is_some() {
+            <div class="row">
+            { for (*bots_state).iter().map(|bot| {
+                html! {
+                    <BotCard bot={bot.clone()} on_deleted={on_deleted.clone()} />
+                }
+            })}
+            </div>
         }
 
         if let Some(err) = (*error_message_state).clone() {
@@ -81,75 +115,14 @@ pub fn MyBotsRoute(props: &MyBotsRouteProps) -> Html {
 #[derive(PartialEq, Properties)]
 pub struct BotCardProps {
     pub bot: User,
+    pub on_deleted: Callback<i32>,
 }
 
 #[function_component]
 pub fn BotCard(props: &BotCardProps) -> Html {
-    let BotCardProps { bot } = props;
-
-    let error_message_state = use_state(|| Option::<String>::None);
-    let token_modal_visible = use_state(|| false);
-    let token = use_state(|| String::new());
-
-    let on_close = {
-        let token_modal_visible = token_modal_visible.clone();
-
-        Callback::from(move |_: ()| {
-            token_modal_visible.set(false);
-        })
-    };
-
-    let on_token_click = {
-        let token_modal_visible = token_modal_visible.clone();
-        let token = token.clone();
-        let bot = bot.clone();
-        let error_message_state = error_message_state.clone();
-
-        Callback::from(move |_: MouseEvent| {
-            let token_modal_visible = token_modal_visible.clone();
-            let error_message_state = error_message_state.clone();
-            let token = token.clone();
-
-            spawn_local(async move {
-                let response = get_bot_token(bot.id.clone()).await;
-
-                if let Ok(response) = response {
-                    if response.ok {
-                        token.set(response.data.unwrap().token);
-                        token_modal_visible.set(true);
-                    } else {
-                        error_message_state.set(Some("Не удалось получить токен бота".to_string()));
-                    }
-                } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
-                }
-            });
-        })
-    };
-
-    let on_delete_click = {
-        let bot = bot.clone();
-        let error_message_state = error_message_state.clone();
-
-        Callback::from(move |_: MouseEvent| {
-            let error_message_state = error_message_state.clone();
-
-            spawn_local(async move {
-                let response = delete_bot(bot.id.clone()).await;
-
-                if let Ok(response) = response {
-                    if !response.ok {
-                        error_message_state.set(Some("Не удалось удалить бота".to_string()));
-                    }
-                } else {
-                    error_message_state.set(Some("Сервер не отвечает".to_string()));
-                }
-            });
-        })
-    };
+    let BotCardProps { bot, on_deleted } = props;
 
     html! {
-        <>
         <div class="col-lg-4 col-md-12 p-2">
             <div class="d-flex">
                 <img class="rounded-start-2 border object-fit-scale" height=75px src={
@@ -160,41 +133,19 @@ pub fn BotCard(props: &BotCardProps) -> Html {
                     }
             } alt="Bot icon"/>
                 <div class="rounded-end-2 text-overflow-ellipsis justify-content-between d-flex border border-start-0 bg-white flex-grow-1 align-items-center">
-                    <div class="p-3 fs-4 text-dark fw-normal">
+                    <Link<Route> classes="p-3 fs-4 text-dark fw-normal text-decoration-none" to={Route::Bot { id: bot.id }}>
                         {bot.username.clone()}
-                    </div>
+                    </Link<Route>>
                     <div>
-                        <i onclick={on_token_click} role="button" class="bi bi-key fs-3 p-1 info-hover"></i>
-                        <i onclick={on_delete_click} role="button" class="bi bi-trash2 fs-3 p-3 danger-hover"></i>
+                        <BotTokenModalButton bot={bot.clone()}>
+                            <i role="button" class="bi bi-key fs-3 p-1 info-hover"></i>
+                        </BotTokenModalButton>
+                        <DeleteBotModalButton bot={bot.clone()} on_deleted={on_deleted.clone()}>
+                            <i role="button" class="bi bi-trash2 fs-3 p-3 danger-hover"></i>
+                        </DeleteBotModalButton>
                     </div>
                 </div>
             </div>
         </div>
-        <Modal modal_id={"bot-token-modal".to_string()} is_visible={*token_modal_visible} on_ok={Callback::from(|_| {})} on_cancel={Callback::from(|_| {})} on_close={on_close}>
-            <div class="modal-header">
-                <h1 class="modal-title fs-5">{"Токен "} {bot.username.clone()}</h1>
-            </div>
-            <div class="modal-body">
-                <div class="input-group flex-nowrap">
-                    <span class="input-group-text" id="addon-wrapping">
-                        <i class="bi bi-key fs-4"></i>
-                    </span>
-                    <input class="form-control" type="text" value={(*token).clone()} readonly=true />
-                </div>
-            </div>
-        </Modal>
-        if let Some(err) = (*error_message_state).clone() {
-            <ErrorMessage
-                on_close={
-                    let error_message_state = error_message_state.clone();
-
-                    Callback::from(move |_| {
-                        error_message_state.set(None);
-                    })
-                }
-                value={err}
-            />
-        }
-        </>
     }
 }
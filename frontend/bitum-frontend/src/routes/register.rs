@@ -1,14 +1,36 @@
+use gloo_timers::future::TimeoutFuture;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen_futures::spawn_local;
-use web_sys::HtmlInputElement;
 use yew::prelude::*;
-use yew_router::prelude::{use_navigator, Link};
+use yew_router::prelude::{use_location, use_navigator, Link};
 
 use crate::{
-    api::{get_user_token, new_user, GetTokenRequest, NewUserRequest},
-    components::{error_message::ErrorMessage, Footer, Header},
-    routes::Route,
+    api::{get_user_by_username, get_user_token, new_user, GetTokenRequest, NewUserRequest},
+    components::{
+        error_message::ErrorMessage, use_auth, Footer, FormField, Header, PasswordStrengthMeter,
+    },
+    constants::SEARCH_DEBOUNCE_MILLIS,
+    error_messages::{ApiAction, NOT_FOUND, SERVER_UNAVAILABLE},
+    routes::{login::LoginRouteQuery, redirect_target, Route},
+    validation::{validate_email, validate_password, validate_username},
 };
 
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct RegisterRouteQuery {
+    /// Путь, на который вернуть пользователя после регистрации
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+}
+
+/// Свободно ли введённое имя пользователя
+#[derive(Clone, Copy, PartialEq)]
+enum UsernameAvailability {
+    Unknown,
+    Checking,
+    Available,
+    Taken,
+}
+
 #[derive(PartialEq, Properties)]
 pub struct RegisterRouteProps {}
 
@@ -17,79 +39,145 @@ pub fn RegisterRoute(props: &RegisterRouteProps) -> Html {
     let RegisterRouteProps {} = props;
 
     let error_message_state = use_state(|| Option::<String>::None);
-    let username_input_node = use_node_ref();
-    let password_input_node = use_node_ref();
-    let email_input_node = use_node_ref();
+    let username_state = use_state(String::new);
+    let password_state = use_state(String::new);
+    let password_confirm_state = use_state(String::new);
+    let email_state = use_state(String::new);
+    let availability_state = use_state(|| UsernameAvailability::Unknown);
+    // Номер последней проверки имени: ответы на устаревшие проверки отбрасываются
+    let check_generation = use_mut_ref(|| 0_u64);
     let navigator = use_navigator().unwrap();
+    let auth = use_auth();
+    let query = use_location()
+        .and_then(|location| location.query::<RegisterRouteQuery>().ok())
+        .unwrap_or_default();
+
+    {
+        let availability_state = availability_state.clone();
+        let check_generation = check_generation.clone();
+
+        use_effect_with_deps(
+            move |username: &String| {
+                *check_generation.borrow_mut() += 1;
+                let generation = *check_generation.borrow();
+
+                if validate_username(username).is_err() {
+                    availability_state.set(UsernameAvailability::Unknown);
+                    return;
+                }
+
+                let username = username.clone();
+                availability_state.set(UsernameAvailability::Checking);
+
+                spawn_local(async move {
+                    // Проверяем только когда пользователь перестал печатать
+                    TimeoutFuture::new(SEARCH_DEBOUNCE_MILLIS).await;
+
+                    if *check_generation.borrow() != generation {
+                        return;
+                    }
+
+                    let response = get_user_by_username(&username).await;
+
+                    if *check_generation.borrow() != generation {
+                        return;
+                    }
+
+                    availability_state.set(match response {
+                        Ok(response) if response.ok => UsernameAvailability::Taken,
+                        Ok(response)
+                            if response.error.as_ref().map(|error| error.code)
+                                == Some(NOT_FOUND) =>
+                        {
+                            UsernameAvailability::Available
+                        }
+                        _ => UsernameAvailability::Unknown,
+                    });
+                });
+            },
+            (*username_state).clone(),
+        );
+    }
+
+    let username_error = validate_username(&username_state).err().or_else(|| {
+        (*availability_state == UsernameAvailability::Taken)
+            .then(|| "Это имя уже занято".to_string())
+    });
+    let password_error = validate_password(&password_state).err();
+    let password_confirm_error =
+        (*password_state != *password_confirm_state).then(|| "Пароли не совпадают".to_string());
+    let email_error = validate_email(&email_state).err();
+    let is_valid = username_error.is_none()
+        && password_error.is_none()
+        && password_confirm_error.is_none()
+        && email_error.is_none();
 
     let onsubmit = {
-        let username_input_node = username_input_node.clone();
-        let password_input_node = password_input_node.clone();
-        let email_input_node = email_input_node.clone();
+        let username_state = username_state.clone();
+        let password_state = password_state.clone();
+        let email_state = email_state.clone();
         let error_message_state = error_message_state.clone();
+        let redirect = query.redirect.clone();
+        let refresh = auth.refresh.clone();
 
         Callback::from(move |submit_event: SubmitEvent| {
             let navigator = navigator.clone();
+            let refresh = refresh.clone();
             let error_message_state = error_message_state.clone();
+            let redirect = redirect.clone();
             submit_event.prevent_default();
 
-            if [
-                username_input_node.clone(),
-                password_input_node.clone(),
-                email_input_node.clone(),
-            ]
-            .iter()
-            .any(|input| input.cast::<HtmlInputElement>().is_none())
-            {
+            if !is_valid {
                 return;
             }
 
-            let username = username_input_node
-                .cast::<HtmlInputElement>()
-                .unwrap()
-                .value();
-            let password = password_input_node
-                .cast::<HtmlInputElement>()
-                .unwrap()
-                .value();
-            let email = email_input_node.cast::<HtmlInputElement>().unwrap().value();
+            let username = (*username_state).clone();
+            let password = (*password_state).clone();
+            let email = (*email_state).clone();
 
             spawn_local(async move {
                 let response = new_user(NewUserRequest {
                     username: username.clone(),
                     password: password.clone(),
-                    email: email.clone(),
+                    email,
                 })
                 .await;
 
-                let mut error = Option::<String>::None;
-
-                if let Ok(resp) = response {
-                    if resp.ok {
-                        let _ = get_user_token(GetTokenRequest {
-                            username: username.clone(),
-                            password: password.clone(),
-                        })
-                        .await;
-
-                        navigator.push(&Route::Chats);
-                    } else {
-                        match resp.error.unwrap().code {
-                            409 => {
-                                error = Some(String::from("Ошибка! Такой пользователь уже есть."))
+                match response {
+                    Ok(response) if response.ok => {
+                        match get_user_token(GetTokenRequest { username, password }).await {
+                            Ok(response) if response.ok => {
+                                refresh.emit(());
+                                navigator.push(&redirect_target(redirect.as_deref()));
+                            }
+                            // Аккаунт уже создан, осталось только войти
+                            _ => {
+                                let _ = navigator.push_with_query(
+                                    &Route::Login,
+                                    &LoginRouteQuery {
+                                        registered: true,
+                                        redirect,
+                                        ..Default::default()
+                                    },
+                                );
                             }
-                            _ => error = Some(String::from("Неизвестная ошибка!")),
                         }
                     }
-                } else {
-                    error = Some(String::from("Неизвестная ошибка в работе сервера."))
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::Register.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
                 }
-
-                error_message_state.set(error);
             });
         })
     };
 
+    let text_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+
+        Callback::from(move |value: String| state.set(value))
+    };
+
     html! {
         <>
         <Header />
@@ -97,24 +185,76 @@ pub fn RegisterRoute(props: &RegisterRouteProps) -> Html {
             <div class="col-md-4 col-sm-12">
                 <h1 class="fw-light fs-2 p-4">{"Регистрация"}</h1>
                 <form class="text-start gy-3 row input-group-lg" {onsubmit}>
-                    <div class="col-12 input-group-lg">
-                        <label class="fw-medium fs-5"  >{"Имя пользователя"}</label>
-                        <input ref={username_input_node} type="text" placeholder="username" class="form-control" id="username-input" required=true />
-                    </div>
+                    <FormField
+                        id="username-input"
+                        label="Имя пользователя"
+                        placeholder="username"
+                        autocomplete="username"
+                        value={(*username_state).clone()}
+                        on_input={text_input(&username_state)}
+                        error={username_error.clone()}
+                    >
+                        if username_error.is_none() {
+                            {
+                                match *availability_state {
+                                    UsernameAvailability::Checking => html! {
+                                        <div class="form-text">{"Проверяем имя..."}</div>
+                                    },
+                                    UsernameAvailability::Available => html! {
+                                        <div class="form-text text-success">{"Имя свободно"}</div>
+                                    },
+                                    _ => html! {},
+                                }
+                            }
+                        }
+                    </FormField>
 
-                    <div class="col-12 input-group-lg">
-                        <label class="fw-medium fs-5" for="password-input">{"Пароль"}</label>
-                        <input ref={password_input_node} type="password" placeholder="password" class="form-control" id="password-input" required=true />
-                    </div>
+                    <FormField
+                        id="password-input"
+                        label="Пароль"
+                        input_type="password"
+                        placeholder="password"
+                        autocomplete="new-password"
+                        value={(*password_state).clone()}
+                        on_input={text_input(&password_state)}
+                        error={password_error}
+                        revealable=true
+                    >
+                        <PasswordStrengthMeter password={(*password_state).clone()} />
+                    </FormField>
 
-                    <div class="col-12 input-group-lg">
-                        <label class="fw-medium fs-5" for="email-input">{"Электронная почта"}</label>
-                        <input ref={email_input_node} type="email" placeholder="email" class="form-control" id="email-input" required=true />
-                    </div>
+                    <FormField
+                        id="password-confirm-input"
+                        label="Повторите пароль"
+                        input_type="password"
+                        placeholder="password"
+                        autocomplete="new-password"
+                        value={(*password_confirm_state).clone()}
+                        on_input={text_input(&password_confirm_state)}
+                        error={password_confirm_error}
+                        revealable=true
+                    />
+
+                    <FormField
+                        id="email-input"
+                        label="Электронная почта"
+                        input_type="email"
+                        placeholder="email"
+                        autocomplete="email"
+                        value={(*email_state).clone()}
+                        on_input={text_input(&email_state)}
+                        error={email_error}
+                    />
 
                     <div class="col-12 d-flex p-2 justify-content-between">
-                        <button class="btn btn-success btn-lg" type="submit" id="submit-button">{"Зарегистрироваться"}</button>
-                        <Link<Route> classes="btn btn-light btn-lg" to={Route::Login}>{"Вход"}</Link<Route>>
+                        <button disabled={!is_valid} class="btn btn-success btn-lg" type="submit" id="submit-button">{"Зарегистрироваться"}</button>
+                        <Link<Route, LoginRouteQuery>
+                            classes="btn btn-light btn-lg"
+                            to={Route::Login}
+                            query={Some(LoginRouteQuery { redirect: query.redirect.clone(), ..Default::default() })}
+                        >
+                            {"Вход"}
+                        </Link<Route, LoginRouteQuery>>
                     </div>
                 </form>
             </div>
@@ -126,7 +266,6 @@ pub fn RegisterRoute(props: &RegisterRouteProps) -> Html {
                     let error_message_state = error_message_state.clone();
 
                     Callback::from(move |_| {
-
                         error_message_state.set(None);
                     })
                 }
@@ -0,0 +1,271 @@
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::{
+    api::{request_reset_password, reset_password, ResetPasswordRequest},
+    components::{ErrorMessage, Footer, FormField, Header, PasswordStrengthMeter},
+    error_messages::{ApiAction, NOT_FOUND, SERVER_UNAVAILABLE},
+    routes::Route,
+    validation::validate_password,
+};
+
+#[derive(PartialEq, Properties)]
+pub struct RequestPasswordResetRouteProps {}
+
+#[function_component]
+pub fn RequestPasswordResetRoute(props: &RequestPasswordResetRouteProps) -> Html {
+    let RequestPasswordResetRouteProps {} = props;
+
+    let error_message_state = use_state(|| Option::<String>::None);
+    let username_state = use_state(String::new);
+    let is_sending = use_state(|| false);
+    let is_sent = use_state(|| false);
+
+    let username = username_state.trim().to_string();
+
+    let onsubmit = {
+        let error_message_state = error_message_state.clone();
+        let is_sending = is_sending.clone();
+        let is_sent = is_sent.clone();
+        let username = username.clone();
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            if username.is_empty() {
+                return;
+            }
+
+            let error_message_state = error_message_state.clone();
+            let is_sending = is_sending.clone();
+            let is_sent = is_sent.clone();
+            let username = username.clone();
+
+            is_sending.set(true);
+
+            spawn_local(async move {
+                match request_reset_password(&username).await {
+                    Ok(response) if response.ok => is_sent.set(true),
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::RequestPasswordReset.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+
+                is_sending.set(false);
+            });
+        })
+    };
+
+    let oninput = {
+        let username_state = username_state.clone();
+
+        Callback::from(move |event: InputEvent| {
+            username_state.set(event.target_unchecked_into::<HtmlInputElement>().value());
+        })
+    };
+
+    html! {
+        <>
+        <Header />
+        <div class="text-center row justify-content-md-center">
+            <div class="col-md-4 col-sm-12">
+                <h1 class="fw-light fs-2 p-4">{"Восстановление пароля"}</h1>
+                if *is_sent {
+                    // Писем сервер не отправляет: код попадает в журнал сервера
+                    <div class="alert alert-success" role="alert">
+                        {"Код для сброса пароля пользователя "}
+                        <b>{username.clone()}</b>
+                        {" создан. Писем этот сервер не отправляет, поэтому ссылку для сброса попросите у администратора. Код действует час."}
+                    </div>
+                    <Link<Route> classes="btn btn-light btn-lg" to={Route::Login}>{"Ко входу"}</Link<Route>>
+                } else {
+                    <form class="text-start gy-3 row input-group-lg" {onsubmit}>
+                        <div class="col-12 input-group-lg">
+                            <label class="fw-medium fs-5" for="username-input">{"Имя пользователя"}</label>
+                            <input {oninput} value={(*username_state).clone()} type="text" placeholder="username" class="form-control" id="username-input" required=true />
+                            <div class="form-text">{"Сервер создаст одноразовый код для сброса, ссылку с ним выдаёт администратор"}</div>
+                        </div>
+
+                        <div class="col-12 d-flex p-2 justify-content-between">
+                            <button disabled={*is_sending || username.is_empty()} class="btn btn-success btn-lg" type="submit">
+                                {"Запросить сброс"}
+                            </button>
+                            <Link<Route> classes="btn btn-light btn-lg" to={Route::Login}>{"Ко входу"}</Link<Route>>
+                        </div>
+                    </form>
+                }
+            </div>
+        </div>
+        <Footer />
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ResetPasswordState {
+    Form,
+    InvalidCode,
+    Done,
+}
+
+#[derive(PartialEq, Properties)]
+pub struct ResetPasswordRouteProps {
+    pub code: String,
+}
+
+#[function_component]
+pub fn ResetPasswordRoute(props: &ResetPasswordRouteProps) -> Html {
+    let ResetPasswordRouteProps { code } = props;
+
+    let error_message_state = use_state(|| Option::<String>::None);
+    let password_state = use_state(String::new);
+    let password_confirm_state = use_state(String::new);
+    let is_sending = use_state(|| false);
+    let reset_state = use_state(|| ResetPasswordState::Form);
+
+    let password_error = validate_password(&password_state).err();
+    let password_confirm_error =
+        (*password_state != *password_confirm_state).then(|| "Пароли не совпадают".to_string());
+    let is_valid = password_error.is_none() && password_confirm_error.is_none();
+
+    let onsubmit = {
+        let error_message_state = error_message_state.clone();
+        let password_state = password_state.clone();
+        let is_sending = is_sending.clone();
+        let reset_state = reset_state.clone();
+        let code = code.clone();
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            if !is_valid {
+                return;
+            }
+
+            let error_message_state = error_message_state.clone();
+            let is_sending = is_sending.clone();
+            let reset_state = reset_state.clone();
+            let code = code.clone();
+            let password = (*password_state).clone();
+
+            is_sending.set(true);
+
+            spawn_local(async move {
+                match reset_password(&code, ResetPasswordRequest { password }).await {
+                    Ok(response) if response.ok => reset_state.set(ResetPasswordState::Done),
+                    Ok(response)
+                        if response.error.as_ref().map(|error| error.code) == Some(NOT_FOUND) =>
+                    {
+                        reset_state.set(ResetPasswordState::InvalidCode)
+                    }
+                    Ok(response) => error_message_state.set(Some(
+                        ApiAction::ResetPassword.error_message(response.error.as_ref()),
+                    )),
+                    Err(_) => error_message_state.set(Some(SERVER_UNAVAILABLE.to_string())),
+                }
+
+                is_sending.set(false);
+            });
+        })
+    };
+
+    let text_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+
+        Callback::from(move |value: String| state.set(value))
+    };
+
+    html! {
+        <>
+        <Header />
+        <div class="text-center row justify-content-md-center">
+            <div class="col-md-4 col-sm-12">
+                <h1 class="fw-light fs-2 p-4">{"Новый пароль"}</h1>
+                {
+                    match *reset_state {
+                        ResetPasswordState::Done => html! {
+                            <>
+                            <div class="alert alert-success" role="alert">
+                                {"Пароль изменён. Теперь можно войти с новым паролем."}
+                            </div>
+                            <Link<Route> classes="btn btn-success btn-lg" to={Route::Login}>{"Войти"}</Link<Route>>
+                            </>
+                        },
+                        ResetPasswordState::InvalidCode => html! {
+                            <>
+                            <div class="alert alert-danger" role="alert">
+                                {"Ссылка для сброса пароля недействительна или устарела."}
+                            </div>
+                            <Link<Route> classes="btn btn-light btn-lg" to={Route::RequestPasswordReset}>
+                                {"Запросить новую ссылку"}
+                            </Link<Route>>
+                            </>
+                        },
+                        ResetPasswordState::Form => html! {
+                            <form class="text-start gy-3 row input-group-lg" {onsubmit}>
+                                <FormField
+                                    id="password-input"
+                                    label="Новый пароль"
+                                    input_type="password"
+                                    autocomplete="new-password"
+                                    value={(*password_state).clone()}
+                                    on_input={text_input(&password_state)}
+                                    error={password_error}
+                                    revealable=true
+                                >
+                                    <PasswordStrengthMeter password={(*password_state).clone()} />
+                                </FormField>
+
+                                <FormField
+                                    id="password-confirm-input"
+                                    label="Повторите пароль"
+                                    input_type="password"
+                                    autocomplete="new-password"
+                                    value={(*password_confirm_state).clone()}
+                                    on_input={text_input(&password_confirm_state)}
+                                    error={password_confirm_error}
+                                    revealable=true
+                                />
+
+                                <div class="col-12 d-flex p-2 justify-content-between">
+                                    <button disabled={*is_sending || !is_valid} class="btn btn-success btn-lg" type="submit">
+                                        {"Сменить пароль"}
+                                    </button>
+                                </div>
+                            </form>
+                        },
+                    }
+                }
+            </div>
+        </div>
+        <Footer />
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
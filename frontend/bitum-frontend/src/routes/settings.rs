@@ -0,0 +1,362 @@
+use bitum_frontend::get_random_color_image_url;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::{
+    api::{patch_user, upload_file, PatchUserRequest, User},
+    components::{
+        request_notifications_permission, use_auth, AvatarCropper, ErrorMessage, Footer, Header,
+        NotificationSettingsContext, TextMessage,
+    },
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    preferences::{Preferences, Theme, TimeFormat},
+    validation::{validate_email, validate_password},
+};
+
+#[derive(PartialEq, Properties)]
+pub struct SettingsRouteProps {}
+
+async fn save_account(
+    patch_user_request: PatchUserRequest,
+    success_message: &str,
+    error_message_state: UseStateHandle<Option<String>>,
+    success_message_state: UseStateHandle<Option<String>>,
+    set_user: Callback<User>,
+) -> Option<User> {
+    match patch_user(patch_user_request).await {
+        Ok(response) if response.ok => {
+            success_message_state.set(Some(success_message.to_string()));
+            // Чтобы шапка сразу показала новый аватар
+            if let Some(user) = response.data.clone() {
+                set_user.emit(user);
+            }
+            response.data
+        }
+        Ok(response) => {
+            error_message_state.set(Some(
+                ApiAction::EditAccount.error_message(response.error.as_ref()),
+            ));
+            None
+        }
+        Err(_) => {
+            error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+            None
+        }
+    }
+}
+
+#[function_component]
+pub fn SettingsRoute(props: &SettingsRouteProps) -> Html {
+    let SettingsRouteProps {} = props;
+    let auth = use_auth();
+    let logged_user = auth.state.user().cloned();
+    let notification_settings = use_context::<NotificationSettingsContext>();
+    let error_message_state = use_state(|| Option::<String>::None);
+    let success_message_state = use_state(|| Option::<String>::None);
+    let icon_state = use_state(|| Option::<String>::None);
+    let is_uploading = use_state(|| false);
+    let email_state = use_state(String::new);
+    let password_state = use_state(String::new);
+    let password_confirm_state = use_state(String::new);
+    let preferences_state = use_state(Preferences::load);
+
+    let on_avatar_save = {
+        let error_message_state = error_message_state.clone();
+        let success_message_state = success_message_state.clone();
+        let set_user = auth.set_user.clone();
+        let icon_state = icon_state.clone();
+        let is_uploading = is_uploading.clone();
+
+        Callback::from(move |file: web_sys::File| {
+            let error_message_state = error_message_state.clone();
+            let success_message_state = success_message_state.clone();
+            let set_user = set_user.clone();
+            let icon_state = icon_state.clone();
+            let is_uploading = is_uploading.clone();
+
+            is_uploading.set(true);
+
+            spawn_local(async move {
+                let icon_file = match upload_file(file).await {
+                    Ok(response) if response.ok => response.data.unwrap().first().cloned(),
+                    Ok(response) => {
+                        error_message_state.set(Some(
+                            ApiAction::UploadFile.error_message(response.error.as_ref()),
+                        ));
+                        None
+                    }
+                    Err(_) => {
+                        error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                        None
+                    }
+                };
+
+                if let Some(icon_file) = icon_file {
+                    let user = save_account(
+                        PatchUserRequest {
+                            icon_file: Some(icon_file),
+                            ..Default::default()
+                        },
+                        "Аватар обновлён",
+                        error_message_state,
+                        success_message_state,
+                        set_user,
+                    )
+                    .await;
+
+                    if let Some(user) = user {
+                        icon_state.set(user.icon);
+                    }
+                }
+
+                is_uploading.set(false);
+            });
+        })
+    };
+
+    let text_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+
+        Callback::from(move |event: InputEvent| {
+            state.set(event.target_unchecked_into::<HtmlInputElement>().value());
+        })
+    };
+
+    let email = email_state.trim().to_string();
+    let is_email_valid = validate_email(&email).is_ok();
+
+    let on_email_save = {
+        let error_message_state = error_message_state.clone();
+        let success_message_state = success_message_state.clone();
+        let set_user = auth.set_user.clone();
+        let email_state = email_state.clone();
+        let email = email.clone();
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            let error_message_state = error_message_state.clone();
+            let success_message_state = success_message_state.clone();
+            let set_user = set_user.clone();
+            let email_state = email_state.clone();
+            let email = email.clone();
+
+            spawn_local(async move {
+                let user = save_account(
+                    PatchUserRequest {
+                        email: Some(email),
+                        ..Default::default()
+                    },
+                    "Почта изменена",
+                    error_message_state,
+                    success_message_state,
+                    set_user,
+                )
+                .await;
+
+                if user.is_some() {
+                    email_state.set(String::new());
+                }
+            });
+        })
+    };
+
+    let passwords_match = *password_state == *password_confirm_state;
+    let is_password_valid = validate_password(&password_state).is_ok();
+
+    let on_password_save = {
+        let error_message_state = error_message_state.clone();
+        let success_message_state = success_message_state.clone();
+        let set_user = auth.set_user.clone();
+        let password_state = password_state.clone();
+        let password_confirm_state = password_confirm_state.clone();
+
+        Callback::from(move |submit_event: SubmitEvent| {
+            submit_event.prevent_default();
+
+            let error_message_state = error_message_state.clone();
+            let success_message_state = success_message_state.clone();
+            let set_user = set_user.clone();
+            let password_state = password_state.clone();
+            let password_confirm_state = password_confirm_state.clone();
+            let password = (*password_state).clone();
+
+            spawn_local(async move {
+                let user = save_account(
+                    PatchUserRequest {
+                        password: Some(password),
+                        ..Default::default()
+                    },
+                    "Пароль изменён",
+                    error_message_state,
+                    success_message_state,
+                    set_user,
+                )
+                .await;
+
+                if user.is_some() {
+                    password_state.set(String::new());
+                    password_confirm_state.set(String::new());
+                }
+            });
+        })
+    };
+
+    let preference_select = |label: &'static str,
+                             options: Vec<(&'static str, bool)>,
+                             update: fn(&mut Preferences, usize)| {
+        let preferences_state = preferences_state.clone();
+
+        let onchange = Callback::from(move |event: Event| {
+            let select = event.target_unchecked_into::<HtmlSelectElement>();
+            let mut preferences = (*preferences_state).clone();
+
+            update(&mut preferences, select.selected_index().max(0) as usize);
+            preferences.save();
+            preferences_state.set(preferences);
+        });
+
+        html! {
+            <label class="form-label w-100">
+                {label}
+                <select {onchange} class="form-select">
+                    { for options.into_iter().map(|(name, selected)| html! {
+                        <option {selected}>{name}</option>
+                    })}
+                </select>
+            </label>
+        }
+    };
+
+    let on_notifications_change = {
+        let notification_settings = notification_settings.clone();
+
+        Callback::from(move |event: Event| {
+            let Some(notification_settings) = notification_settings.clone() else {
+                return;
+            };
+            let enabled = event.target_unchecked_into::<HtmlInputElement>().checked();
+
+            if !enabled {
+                notification_settings.set(notification_settings.with_enabled(false));
+                return;
+            }
+
+            spawn_local(async move {
+                let granted = request_notifications_permission().await;
+                notification_settings.set(notification_settings.with_enabled(granted));
+            });
+        })
+    };
+
+    let preferences = (*preferences_state).clone();
+
+    html! {
+        <>
+        <Header />
+
+        <h1 class="fw-medium fs-1">{"Настройки"}</h1>
+
+        if let Some(user) = logged_user {
+            <div class="row">
+                <div class="col-lg-6 col-md-12 gy-3">
+                    <h2 class="fs-4">{"Аватар"}</h2>
+                    <div class="d-flex align-items-center gap-3 pb-3">
+                        <img class="rounded-4 border" width=100px height=100px src={
+                            match (*icon_state).clone().or(user.icon.clone()) {
+                                Some(icon) => format!("/api/files/{}", icon),
+                                None => get_random_color_image_url(user.username.clone(), 100, 100),
+                            }
+                        } alt="icon" />
+                        <div class="fs-4">{user.username.clone()}</div>
+                    </div>
+                    <AvatarCropper on_save={on_avatar_save} disabled={*is_uploading} />
+
+                    <h2 class="fs-4 pt-4">{"Почта"}</h2>
+                    <form onsubmit={on_email_save} class="input-group">
+                        <input oninput={text_input(&email_state)} value={(*email_state).clone()} type="email" placeholder="Новая почта" class="form-control" />
+                        <button disabled={!is_email_valid} type="submit" class="btn btn-dark">{"Сохранить"}</button>
+                    </form>
+
+                    <h2 class="fs-4 pt-4">{"Пароль"}</h2>
+                    <form onsubmit={on_password_save}>
+                        <input oninput={text_input(&password_state)} value={(*password_state).clone()} type="password" placeholder="Новый пароль" class="form-control mb-2" />
+                        <input
+                            oninput={text_input(&password_confirm_state)}
+                            value={(*password_confirm_state).clone()}
+                            type="password"
+                            placeholder="Повторите пароль"
+                            class={classes!("form-control", "mb-2", (!passwords_match).then_some("is-invalid"))}
+                        />
+                        if !passwords_match {
+                            <div class="invalid-feedback d-block mb-2">{"Пароли не совпадают"}</div>
+                        }
+                        <button disabled={!passwords_match || !is_password_valid} type="submit" class="btn btn-dark">
+                            {"Изменить пароль"}
+                        </button>
+                    </form>
+                </div>
+
+                <div class="col-lg-6 col-md-12 gy-3">
+                    <h2 class="fs-4">{"Интерфейс"}</h2>
+                    <p class="text-body-secondary">{"Эти настройки хранятся только в этом браузере."}</p>
+                    { preference_select(
+                        "Тема",
+                        Theme::ALL.iter().map(|theme| (theme.name(), *theme == preferences.theme)).collect(),
+                        |preferences, index| preferences.theme = Theme::ALL[index],
+                    ) }
+                    { preference_select(
+                        "Формат времени",
+                        TimeFormat::ALL.iter().map(|format| (format.name(), *format == preferences.time_format)).collect(),
+                        |preferences, index| preferences.time_format = TimeFormat::ALL[index],
+                    ) }
+                    if let Some(notification_settings) = notification_settings {
+                        <div class="form-check form-switch">
+                            <label class="form-check-label">
+                                <input
+                                    onchange={on_notifications_change}
+                                    checked={notification_settings.enabled}
+                                    class="form-check-input"
+                                    type="checkbox"
+                                    role="switch"
+                                />
+                                {"Уведомления о новых сообщениях"}
+                            </label>
+                        </div>
+                    }
+                </div>
+            </div>
+        }
+
+        <Footer />
+
+        if let Some(message) = (*success_message_state).clone() {
+            <TextMessage
+                on_close={
+                    let success_message_state = success_message_state.clone();
+
+                    Callback::from(move |_| {
+                        success_message_state.set(None);
+                    })
+                }
+                value={message}
+                toast_classes={classes!("bg-success", "text-bg-primary")}
+            />
+        }
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
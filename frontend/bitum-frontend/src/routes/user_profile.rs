@@ -0,0 +1,180 @@
+use bitum_frontend::{display_timestamp_date, get_random_color_image_url};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+use crate::{
+    api::{get_shared_chats, get_user, get_user_by_username, Chat, User},
+    components::{
+        use_current_user, AddToChatDropdown, ChatName, DirectMessageButton, ErrorMessage, Footer,
+        Header,
+    },
+    error_messages::{ApiAction, SERVER_UNAVAILABLE},
+    routes::Route,
+};
+
+#[derive(PartialEq, Properties)]
+pub struct UserProfileRouteProps {
+    pub username: String,
+}
+
+/// Наши чаты, в которых состоит пользователь
+async fn load_shared_chats(user_id: i32) -> Result<Vec<Chat>, String> {
+    match get_shared_chats(user_id).await {
+        Ok(response) if response.ok => Ok(response.data.unwrap_or_default()),
+        Ok(_) => Err("Не удалось получить список общих чатов".to_string()),
+        Err(_) => Err(SERVER_UNAVAILABLE.to_string()),
+    }
+}
+
+#[function_component]
+pub fn UserProfileRoute(props: &UserProfileRouteProps) -> Html {
+    let UserProfileRouteProps { username } = props;
+    let self_user = use_current_user();
+    let error_message_state = use_state(|| Option::<String>::None);
+    let user_state = use_state(|| Option::<User>::None);
+    let creator_state = use_state(|| Option::<User>::None);
+    let shared_chats_state = use_state(Vec::<Chat>::new);
+
+    {
+        let error_message_state = error_message_state.clone();
+        let user_state = user_state.clone();
+        let creator_state = creator_state.clone();
+        let shared_chats_state = shared_chats_state.clone();
+
+        use_effect_with_deps(
+            move |username: &String| {
+                let username = username.clone();
+
+                user_state.set(None);
+                creator_state.set(None);
+                shared_chats_state.set(Vec::new());
+
+                spawn_local(async move {
+                    let user = match get_user_by_username(&username).await {
+                        Ok(response) if response.ok => response.data.unwrap(),
+                        Ok(response) => {
+                            error_message_state.set(Some(
+                                ApiAction::LoadUser.error_message(response.error.as_ref()),
+                            ));
+                            return;
+                        }
+                        Err(_) => {
+                            error_message_state.set(Some(SERVER_UNAVAILABLE.to_string()));
+                            return;
+                        }
+                    };
+
+                    user_state.set(Some(user.clone()));
+
+                    if let Some(creator_id) = user.creator_id {
+                        if let Ok(response) = get_user(creator_id).await {
+                            creator_state.set(response.data);
+                        }
+                    }
+
+                    match load_shared_chats(user.id).await {
+                        Ok(shared_chats) => shared_chats_state.set(shared_chats),
+                        Err(err) => error_message_state.set(Some(err)),
+                    }
+                });
+            },
+            username.clone(),
+        );
+    }
+
+    let on_added_to_chat = {
+        let shared_chats_state = shared_chats_state.clone();
+
+        Callback::from(move |chat: Chat| {
+            let mut shared_chats = (*shared_chats_state).clone();
+            shared_chats.push(chat);
+            shared_chats_state.set(shared_chats);
+        })
+    };
+
+    let is_self = match (&self_user, &*user_state) {
+        (Some(self_user), Some(user)) => self_user.id == user.id,
+        _ => false,
+    };
+
+    html! {
+        <>
+        <Header />
+
+        if let Some(user) = (*user_state).clone() {
+            <div class="d-flex flex-wrap align-items-center gap-4 py-3">
+                <img class="rounded-4 border object-fit-scale" width=150px height=150px src={
+                    if user.icon.is_some() {
+                        format!("/api/files/{}", user.icon.clone().unwrap_or("null.png".to_string()))
+                    } else {
+                        get_random_color_image_url(user.username.clone(), 150, 150)
+                    }
+                } alt="icon"/>
+                <div>
+                    <h1 class="fw-medium fs-1">
+                        if user.is_bot {
+                            <span class="badge rounded-pill text-bg-dark fs-6">{"бот"}</span>
+                            {" "}
+                        }
+                        {user.username.clone()}
+                    </h1>
+                    <div class="text-body-secondary fs-5">
+                        {"На Bitum с "}
+                        {display_timestamp_date(user.created_at as i64)}
+                    </div>
+                    if let Some(creator) = (*creator_state).clone() {
+                        <div class="text-body-secondary fs-5">
+                            {"Создатель: "}
+                            <Link<Route> to={Route::UserProfile { username: creator.username.clone() }}>
+                                {creator.username.clone()}
+                            </Link<Route>>
+                        </div>
+                    }
+                    if !is_self {
+                        <div class="d-flex gap-2 pt-3">
+                            <DirectMessageButton user={user.clone()}>
+                                <button type="button" class="btn btn-dark">
+                                    <i class="bi bi-chat-dots"></i>
+                                    {" Написать"}
+                                </button>
+                            </DirectMessageButton>
+                            <AddToChatDropdown user={user.clone()} on_added={on_added_to_chat} />
+                        </div>
+                    }
+                </div>
+            </div>
+
+            if !is_self {
+                <h2 class="fw-medium fs-3 pt-3">{"Общие чаты"}</h2>
+                if shared_chats_state.is_empty() {
+                    <div class="text-body-secondary">{"Общих чатов нет"}</div>
+                } else {
+                    <div class="list-group user-profile-chats">
+                        { for shared_chats_state.iter().map(|chat| html! {
+                            <Link<Route> classes="list-group-item list-group-item-action" to={Route::Chat { id: chat.id }}>
+                                <ChatName chat={chat.clone()} />
+                            </Link<Route>>
+                        })}
+                    </div>
+                }
+            }
+        }
+
+        <Footer />
+
+        if let Some(err) = (*error_message_state).clone() {
+            <ErrorMessage
+                on_close={
+                    let error_message_state = error_message_state.clone();
+
+                    Callback::from(move |_| {
+                        error_message_state.set(None);
+                    })
+                }
+                value={err}
+            />
+        }
+        </>
+    }
+}
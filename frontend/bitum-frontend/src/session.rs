@@ -0,0 +1,106 @@
+use gloo::storage::{LocalStorage, Storage};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlDocument;
+
+use crate::{
+    constants::{
+        API_TOKEN_COOKIE_NAME, CHAT_ORGANIZATION_STORAGE_KEY, DIRECT_CHATS_STORAGE_KEY,
+        NOTIFICATIONS_STORAGE_KEY, PREFERENCES_STORAGE_KEY, UNREAD_STORAGE_KEY,
+    },
+    preferences::Preferences,
+};
+
+/// Ключи localStorage, которые хранятся отдельно для каждого аккаунта
+const ACCOUNT_STORAGE_KEYS: [&str; 4] = [
+    UNREAD_STORAGE_KEY,
+    NOTIFICATIONS_STORAGE_KEY,
+    CHAT_ORGANIZATION_STORAGE_KEY,
+    DIRECT_CHATS_STORAGE_KEY,
+];
+
+#[derive(Deserialize)]
+struct JwtPayload {
+    exp: f64,
+    user_id: Option<i32>,
+}
+
+fn html_document() -> Option<HtmlDocument> {
+    gloo_utils::document().dyn_into::<HtmlDocument>().ok()
+}
+
+/// JWT из cookie, если пользователь входил с этого браузера
+pub fn api_token() -> Option<String> {
+    let cookies = html_document()?.cookie().ok()?;
+
+    cookies
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == API_TOKEN_COOKIE_NAME)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Payload токена без проверки подписи
+fn token_payload(token: &str) -> Option<JwtPayload> {
+    let payload = token.split('.').nth(1)?;
+    let mut base64 = payload.replace('-', "+").replace('_', "/");
+
+    while base64.len() % 4 != 0 {
+        base64.push('=');
+    }
+
+    let json = gloo_utils::window().atob(&base64).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Ключ localStorage для данных одного аккаунта, чтобы аккаунты в одном браузере их не делили
+pub fn account_storage_key(key: &str, user_id: i32) -> String {
+    format!("{}.{}", key, user_id)
+}
+
+/// Данные аккаунта из localStorage. Без входа всегда значение по умолчанию
+pub fn load_account_data<T: DeserializeOwned + Default>(key: &str, user_id: Option<i32>) -> T {
+    user_id
+        .and_then(|user_id| LocalStorage::get(account_storage_key(key, user_id)).ok())
+        .unwrap_or_default()
+}
+
+/// Сохраняет данные аккаунта. Без входа ничего не сохраняется
+pub fn save_account_data<T: Serialize>(key: &str, user_id: Option<i32>, value: &T) {
+    if let Some(user_id) = user_id {
+        let _ = LocalStorage::set(account_storage_key(key, user_id), value);
+    }
+}
+
+/// Есть ли токен, срок которого уже истёк
+pub fn is_token_expired() -> bool {
+    api_token()
+        .and_then(|token| token_payload(&token))
+        .map(|payload| payload.exp * 1000.0 <= js_sys::Date::now())
+        .unwrap_or(false)
+}
+
+/// Удаляет токен и всё, что браузер хранит для аккаунта из этого токена:
+/// непрочитанные, папки, режимы уведомлений, личные чаты, а также настройки интерфейса
+pub fn clear_session() {
+    let user_id = api_token()
+        .and_then(|token| token_payload(&token))
+        .and_then(|payload| payload.user_id);
+
+    if let Some(user_id) = user_id {
+        for key in ACCOUNT_STORAGE_KEYS {
+            LocalStorage::delete(account_storage_key(key, user_id));
+        }
+    }
+
+    LocalStorage::delete(PREFERENCES_STORAGE_KEY);
+    Preferences::default().apply();
+
+    if let Some(document) = html_document() {
+        let _ = document.set_cookie(&format!(
+            "{}=; expires=Thu, 01 Jan 1970 00:00:00 GMT; path=/",
+            API_TOKEN_COOKIE_NAME
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+//! Проверки полей форм по тем же правилам, что и `api/validation_schemas.py` на сервере
+
+use std::{collections::HashSet, ops::RangeInclusive};
+
+pub const USERNAME_CHARS: RangeInclusive<usize> = 1..=100;
+pub const PASSWORD_CHARS: RangeInclusive<usize> = 4..=100;
+pub const EMAIL_CHARS: RangeInclusive<usize> = 6..=127;
+pub const CHAT_NAME_CHARS: RangeInclusive<usize> = 1..=100;
+pub const MESSAGE_CONTENT_CHARS: RangeInclusive<usize> = 1..=4000;
+pub const MESSAGE_FILE_NAME_CHARS: RangeInclusive<usize> = 10..=128;
+pub const MAX_MESSAGE_FILES: usize = 10;
+
+/// `Err` с текстом ошибки для пользователя
+pub type ValidationResult = Result<(), String>;
+
+/// Длину считаем в символах, как jsonschema на сервере
+fn check_length(value: &str, range: &RangeInclusive<usize>, field: &str) -> ValidationResult {
+    let chars = value.chars().count();
+
+    if chars == 0 && *range.start() > 0 {
+        Err(format!("Заполните поле «{}»", field))
+    } else if !range.contains(&chars) {
+        Err(format!(
+            "{}: от {} до {} символов",
+            field,
+            range.start(),
+            range.end()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_username(username: &str) -> ValidationResult {
+    check_length(username, &USERNAME_CHARS, "Имя пользователя")
+}
+
+pub fn validate_password(password: &str) -> ValidationResult {
+    check_length(password, &PASSWORD_CHARS, "Пароль")
+}
+
+/// `\s` в Python: `char::is_whitespace` плюс разделители U+001C..U+001F
+fn is_python_whitespace(c: char) -> bool {
+    c.is_whitespace() || ('\u{1c}'..='\u{1f}').contains(&c)
+}
+
+/// То же, что `re.search(r'^\S+@\S+\.\S+$', email)` в jsonschema на сервере
+fn matches_email_pattern(email: &str) -> bool {
+    // `$` в Python совпадает и перед последним переводом строки
+    let email = email.strip_suffix('\n').unwrap_or(email);
+
+    if email.chars().any(is_python_whitespace) {
+        return false;
+    }
+
+    let chars: Vec<char> = email.chars().collect();
+
+    // Самая ранняя `@` после первого символа оставляет больше всего места для точки
+    let Some(at) = chars
+        .iter()
+        .skip(1)
+        .position(|c| *c == '@')
+        .map(|at| at + 1)
+    else {
+        return false;
+    };
+
+    // Между `@` и точкой и после точки нужно хотя бы по символу
+    chars
+        .get(at + 2..chars.len().saturating_sub(1))
+        .is_some_and(|middle| middle.contains(&'.'))
+}
+
+pub fn validate_email(email: &str) -> ValidationResult {
+    check_length(email, &EMAIL_CHARS, "Почта")?;
+
+    if matches_email_pattern(email) {
+        Ok(())
+    } else {
+        Err("Почта должна выглядеть как name@example.com".to_string())
+    }
+}
+
+pub fn validate_chat_name(name: &str) -> ValidationResult {
+    check_length(name, &CHAT_NAME_CHARS, "Имя чата")
+}
+
+pub fn validate_message_content(content: &str) -> ValidationResult {
+    check_length(content, &MESSAGE_CONTENT_CHARS, "Сообщение")
+}
+
+pub fn validate_message_files(files: &[String]) -> ValidationResult {
+    if files.len() > MAX_MESSAGE_FILES {
+        return Err(format!(
+            "К сообщению можно прикрепить не больше {} файлов",
+            MAX_MESSAGE_FILES
+        ));
+    }
+
+    files
+        .iter()
+        .try_for_each(|file| check_length(file, &MESSAGE_FILE_NAME_CHARS, "Имя файла"))
+}
+
+/// Насколько пароль трудно подобрать
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PasswordStrength {
+    VeryWeak,
+    Weak,
+    Medium,
+    Strong,
+}
+
+impl PasswordStrength {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PasswordStrength::VeryWeak => "Очень слабый",
+            PasswordStrength::Weak => "Слабый",
+            PasswordStrength::Medium => "Средний",
+            PasswordStrength::Strong => "Надёжный",
+        }
+    }
+
+    /// Класс Bootstrap для полоски индикатора
+    pub fn color_class(&self) -> &'static str {
+        match self {
+            PasswordStrength::VeryWeak => "bg-danger",
+            PasswordStrength::Weak => "bg-warning",
+            PasswordStrength::Medium => "bg-info",
+            PasswordStrength::Strong => "bg-success",
+        }
+    }
+
+    pub fn percent(&self) -> u32 {
+        match self {
+            PasswordStrength::VeryWeak => 25,
+            PasswordStrength::Weak => 50,
+            PasswordStrength::Medium => 75,
+            PasswordStrength::Strong => 100,
+        }
+    }
+}
+
+/// Энтропия в битах: длина пароля на log2 размера алфавита, из которого он набран
+pub fn password_entropy_bits(password: &str) -> f64 {
+    let has = |predicate: fn(&char) -> bool| password.chars().any(|c| predicate(&c));
+
+    let pool = [
+        (has(char::is_ascii_lowercase), 26),
+        (has(char::is_ascii_uppercase), 26),
+        (has(char::is_ascii_digit), 10),
+        (has(|c| c.is_ascii_punctuation() || *c == ' '), 33),
+        // Кириллица и прочие символы не из ASCII
+        (has(|c| !c.is_ascii()), 66),
+    ]
+    .iter()
+    .filter(|(present, _)| *present)
+    .map(|(_, size)| size)
+    .sum::<u32>();
+
+    if pool == 0 {
+        return 0.0;
+    }
+
+    // Повторы почти не усложняют подбор: "aaaaaaaa" не лучше "aa"
+    let distinct = password.chars().collect::<HashSet<_>>().len();
+    let length = password.chars().count().min(distinct * 2);
+
+    length as f64 * f64::from(pool).log2()
+}
+
+pub fn password_strength(password: &str) -> PasswordStrength {
+    match password_entropy_bits(password) {
+        bits if bits < 28.0 => PasswordStrength::VeryWeak,
+        bits if bits < 36.0 => PasswordStrength::Weak,
+        bits if bits < 60.0 => PasswordStrength::Medium,
+        _ => PasswordStrength::Strong,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn email_pattern_matches_server_regex() {
+        let cases = [
+            ("a@b.c", true),
+            ("a@b..", true),
+            ("a@b.c\n", true),
+            ("a@@b.c", true),
+            ("a@b@c.d", true),
+            ("a@b.c\n\n", false),
+            ("@a.b", false),
+            ("a@.b", false),
+            ("a@b.", false),
+            ("a.b@c", false),
+            ("a b@c.d", false),
+            ("a@b.c\u{1c}", false),
+            ("a@b\u{a0}.c", false),
+            ("ab.c@d", false),
+            ("a@bc", false),
+            (".@..", false),
+            ("a@b.\n", false),
+            ("a@b.c\r", false),
+        ];
+
+        for (email, expected) in cases {
+            assert_eq!(matches_email_pattern(email), expected, "{:?}", email);
+        }
+    }
+
+    #[test]
+    fn email_checks_length_before_pattern() {
+        assert!(validate_email("a@b.cd").is_ok());
+        assert!(validate_email("a@b.c").is_err());
+        assert!(validate_email(&format!("a@b.{}", "c".repeat(123))).is_ok());
+        assert!(validate_email(&format!("a@b.{}", "c".repeat(124))).is_err());
+        assert!(validate_email("name@example").is_err());
+    }
+
+    #[test]
+    fn length_is_counted_in_chars() {
+        assert!(validate_password("пароль").is_ok());
+        assert!(validate_password("абв").is_err());
+        assert!(validate_username("").is_err());
+        assert!(validate_username(&"я".repeat(100)).is_ok());
+        assert!(validate_username(&"я".repeat(101)).is_err());
+    }
+
+    #[test]
+    fn message_files_are_limited() {
+        let file = "a".repeat(10);
+
+        assert!(validate_message_files(&vec![file.clone(); MAX_MESSAGE_FILES]).is_ok());
+        assert!(validate_message_files(&vec![file; MAX_MESSAGE_FILES + 1]).is_err());
+        assert!(validate_message_files(&["short".to_string()]).is_err());
+    }
+
+    fn assert_bits(password: &str, length: f64, pool: f64) {
+        let expected = length * pool.log2();
+        let actual = password_entropy_bits(password);
+
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{:?}: {} != {}",
+            password,
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn entropy_pool_grows_with_character_classes() {
+        assert_eq!(password_entropy_bits(""), 0.0);
+        assert_bits("abcd", 4.0, 26.0);
+        assert_bits("ABCD", 4.0, 26.0);
+        assert_bits("1234", 4.0, 10.0);
+        assert_bits("!?-_", 4.0, 33.0);
+        assert_bits("a b!", 4.0, 26.0 + 33.0);
+        assert_bits("abCD", 4.0, 52.0);
+        assert_bits("abC1", 4.0, 62.0);
+        assert_bits("aC1!", 4.0, 95.0);
+        assert_bits("пароль", 6.0, 66.0);
+        assert_bits("aC1!ж", 5.0, 161.0);
+    }
+
+    #[test]
+    fn entropy_ignores_repeats_beyond_twice_distinct() {
+        assert_bits("aaaaaaaa", 2.0, 26.0);
+        assert_bits("abababab", 4.0, 26.0);
+        assert_bits("abcabc", 6.0, 26.0);
+    }
+
+    #[test]
+    fn strength_boundaries_by_length() {
+        // Только строчные: log2(26) ≈ 4.7 бита на символ
+        assert_eq!(password_strength(""), PasswordStrength::VeryWeak);
+        assert_eq!(password_strength("abcde"), PasswordStrength::VeryWeak);
+        assert_eq!(password_strength("abcdef"), PasswordStrength::Weak);
+        assert_eq!(password_strength("abcdefg"), PasswordStrength::Weak);
+        assert_eq!(password_strength("abcdefgh"), PasswordStrength::Medium);
+        assert_eq!(password_strength("abcdefghijkl"), PasswordStrength::Medium);
+        assert_eq!(password_strength("abcdefghijklm"), PasswordStrength::Strong);
+    }
+
+    #[test]
+    fn strength_boundaries_by_character_classes() {
+        // Те же 5 символов, но из большего алфавита
+        assert_eq!(password_strength("abcde"), PasswordStrength::VeryWeak);
+        assert_eq!(password_strength("abcDE"), PasswordStrength::Weak);
+        assert_eq!(password_strength("ab1D!"), PasswordStrength::Weak);
+        assert_eq!(password_strength("ab1D!ж"), PasswordStrength::Medium);
+        assert_eq!(
+            password_strength("aaaaaaaaaaaaaaaa"),
+            PasswordStrength::VeryWeak
+        );
+    }
+}
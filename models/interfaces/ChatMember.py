@@ -37,7 +37,7 @@ class ChatMember:
     def set_permissions(self, new_permissions: ChatMemberPermissions):
         raise NotImplementedError()
 
-    def delete(self):
+    def delete(self, notify_member: bool = True):
         raise NotImplementedError()
 
     @classmethod
@@ -3,6 +3,7 @@ from typing import Iterable, TYPE_CHECKING, Union
 if TYPE_CHECKING:
     from .Event import Event
     from .Chat import Chat
+    from .ChatMessage import ChatMessage
 
 
 class User:
@@ -61,6 +62,18 @@ class User:
     def chats(self) -> Iterable["Chat"]:
         raise NotImplementedError()
 
+    def last_messages(self) -> Iterable["ChatMessage"]:
+        """
+        Последнее сообщение в каждом чате пользователя, в котором есть сообщения
+        """
+        raise NotImplementedError()
+
+    def shared_chats(self, other: "User") -> Iterable["Chat"]:
+        """
+        Чаты, в которых состоят оба пользователя
+        """
+        raise NotImplementedError()
+
     def delete(self):
         raise NotImplementedError()
 
@@ -87,10 +100,13 @@ class User:
         raise NotImplementedError()
 
     def to_dict(self) -> dict:
+        creator = self.creator() if self.is_bot() else None
+
         return {
             'id': self.id(),
             'username': self.username(),
             'is_bot': self.is_bot(),
             'created_at': self.created_timestamp(),
-            'icon': self.icon()
+            'icon': self.icon(),
+            'creator_id': creator.id() if creator is not None else None
         }
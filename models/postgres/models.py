@@ -127,6 +127,27 @@ class User(IUser):
         chats = [el.chat() for el in chat_members]
         return chats
 
+    def last_messages(self) -> Iterable["ChatMessage"]:
+        chat_ids = DbChatMember.select(DbChatMember.chat_id).where(
+            DbChatMember.user_id == self.id())
+        last_message_ids = DbChatMessage.select(peewee.fn.MAX(DbChatMessage.id)).where(
+            DbChatMessage.chat_id.in_(chat_ids)
+        ).group_by(DbChatMessage.chat_id)
+        messages = DbChatMessage.select().where(
+            DbChatMessage.id.in_(last_message_ids))
+
+        return [ChatMessage.from_db_model(el) for el in messages]
+
+    def shared_chats(self, other: "User") -> Iterable["Chat"]:
+        own_chat_ids = DbChatMember.select(DbChatMember.chat_id).where(
+            DbChatMember.user_id == self.id())
+        other_chat_ids = DbChatMember.select(DbChatMember.chat_id).where(
+            DbChatMember.user_id == other.id())
+        chats = DbChat.select().where(
+            DbChat.id.in_(own_chat_ids) & DbChat.id.in_(other_chat_ids))
+
+        return [Chat.from_db_model(el) for el in chats]
+
     def delete(self):
         DbUser.delete().where(DbUser.id == self.id()).execute()
 
@@ -135,7 +156,7 @@ class User(IUser):
         users = DbUser.select().where(
             (DbUser.username ** f'%{username}%') &
             ~(DbUser.is_bot)
-        ).offset(offset).limit(limit)
+        ).order_by(DbUser.id).offset(offset).limit(limit)
 
         return [User.from_db_model(el) for el in users]
 
@@ -144,7 +165,7 @@ class User(IUser):
         users = DbUser.select().where(
             (DbUser.username ** f'%{username}%') &
             (DbUser.is_bot)
-        ).offset(offset).limit(limit)
+        ).order_by(DbUser.id).offset(offset).limit(limit)
 
         return [User.from_db_model(el) for el in users]
 
@@ -312,11 +333,18 @@ class ChatMember(IChatMember):
 
         self._permissions = value
 
-    def delete(self):
+    def delete(self, notify_member: bool = True):
         chat = self.chat()
         event_payload = MemberKicked(self.user(), chat).to_dict()
-        DbChatMember.delete().where(DbChatMember.id == self.id()).execute()
-        chat.send_event_to_members(event_payload)
+
+        # Исключённому событие отправляется до удаления, чтобы он его получил.
+        # Вышедшему самому сообщать не о чем
+        if notify_member:
+            chat.send_event_to_members(event_payload)
+            DbChatMember.delete().where(DbChatMember.id == self.id()).execute()
+        else:
+            DbChatMember.delete().where(DbChatMember.id == self.id()).execute()
+            chat.send_event_to_members(event_payload)
 
     @classmethod
     @ApiError.wrap_exception(peewee.DoesNotExist, HTTPStatus.NOT_FOUND, "User is not in chat")
@@ -0,0 +1,345 @@
+//! `#[derive(BotCommands)]` для `bitum-bot`. Описание атрибутов см. у `bitum_bot::BotCommands`
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, GenericArgument, Ident,
+    Lit, LitStr, Meta, PathArguments, Type,
+};
+
+const PERMISSIONS: [&str; 3] = ["can_write", "can_add_members", "can_kick_members"];
+
+#[proc_macro_derive(BotCommands, attributes(command))]
+pub fn derive_bot_commands(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ArgKind {
+    Required,
+    Optional,
+    Rest,
+    OptionalRest,
+}
+
+struct Arg {
+    /// Имя в справке
+    name: String,
+    kind: ArgKind,
+}
+
+struct Command {
+    variant: Ident,
+    name: String,
+    description: String,
+    permissions: Vec<String>,
+    fields: Fields,
+    args: Vec<Arg>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "BotCommands можно вывести только для enum",
+        ));
+    };
+
+    let commands = data
+        .variants
+        .iter()
+        .map(|variant| parse_command(&variant.ident, &variant.attrs, &variant.fields))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    for (index, command) in commands.iter().enumerate() {
+        if commands[..index]
+            .iter()
+            .any(|other| other.name == command.name)
+        {
+            return Err(syn::Error::new_spanned(
+                &command.variant,
+                format!("команда /{} объявлена дважды", command.name),
+            ));
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let descriptions = commands.iter().map(|command| {
+        let name = &command.name;
+        let description = &command.description;
+        let usage = command
+            .args
+            .iter()
+            .map(|arg| match arg.kind {
+                ArgKind::Required => format!("<{}>", arg.name),
+                ArgKind::Optional => format!("[{}]", arg.name),
+                ArgKind::Rest => format!("<{}...>", arg.name),
+                ArgKind::OptionalRest => format!("[{}...]", arg.name),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let permissions = PERMISSIONS.iter().map(|permission| {
+            let field = format_ident!("{}", permission);
+            let required = command.permissions.iter().any(|p| p == permission);
+            quote! { #field: #required }
+        });
+
+        quote! {
+            ::bitum_bot::CommandDescription {
+                name: #name,
+                usage: #usage,
+                description: #description,
+                permissions: ::bitum_bot::ChatMemberPermissions { #(#permissions),* },
+            }
+        }
+    });
+
+    let name_arms = commands.iter().map(|command| {
+        let variant = &command.variant;
+        let name = &command.name;
+        quote! { Self::#variant { .. } => #name }
+    });
+
+    let parse_arms = commands.iter().map(|command| {
+        let variant = &command.variant;
+        let name = &command.name;
+        let bindings: Vec<Ident> = (0..command.args.len())
+            .map(|index| format_ident!("__arg{}", index))
+            .collect();
+        let extract = command.args.iter().zip(&bindings).map(|(arg, binding)| {
+            let arg_name = &arg.name;
+            let method = match arg.kind {
+                ArgKind::Required => quote!(required),
+                ArgKind::Optional => quote!(optional),
+                ArgKind::Rest => quote!(rest),
+                ArgKind::OptionalRest => quote!(optional_rest),
+            };
+            quote! { let #binding = __args.#method(#arg_name)?; }
+        });
+        let construct = match &command.fields {
+            Fields::Named(fields) => {
+                let names = fields.named.iter().map(|field| &field.ident);
+                quote! { Self::#variant { #(#names: #bindings),* } }
+            }
+            Fields::Unnamed(_) => quote! { Self::#variant(#(#bindings),*) },
+            Fields::Unit => quote! { Self::#variant },
+        };
+
+        quote! {
+            #name => {
+                #(#extract)*
+                __args.finish()?;
+                ::std::result::Result::Ok(#construct)
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::bitum_bot::BotCommands for #ident #type_generics #where_clause {
+            fn descriptions() -> &'static [::bitum_bot::CommandDescription] {
+                const DESCRIPTIONS: &[::bitum_bot::CommandDescription] = &[#(#descriptions),*];
+                DESCRIPTIONS
+            }
+
+            fn name(&self) -> &'static str {
+                match *self {
+                    #(#name_arms,)*
+                }
+            }
+
+            fn parse(
+                name: &str,
+                args: ::std::vec::Vec<::std::string::String>,
+            ) -> ::std::result::Result<Self, ::bitum_bot::CommandError> {
+                let mut __args = ::bitum_bot::__private::Args::new(args);
+
+                match name {
+                    #(#parse_arms)*
+                    _ => ::std::result::Result::Err(
+                        ::bitum_bot::CommandError::UnknownCommand(name.to_string()),
+                    ),
+                }
+            }
+        }
+    })
+}
+
+fn parse_command(variant: &Ident, attrs: &[Attribute], fields: &Fields) -> syn::Result<Command> {
+    let mut name = to_snake_case(&variant.to_string());
+    let mut permissions = Vec::new();
+    let mut description = Vec::new();
+
+    for attr in attrs {
+        if attr.path().is_ident("doc") {
+            if let Meta::NameValue(meta) = &attr.meta {
+                if let Expr::Lit(ExprLit {
+                    lit: Lit::Str(line),
+                    ..
+                }) = &meta.value
+                {
+                    description.push(line.value().trim().to_string());
+                }
+            }
+        } else if attr.path().is_ident("command") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    name = value.value();
+
+                    if name.is_empty() || name.contains(char::is_whitespace) {
+                        return Err(meta.error("имя команды не может быть пустым или с пробелами"));
+                    }
+
+                    Ok(())
+                } else if meta.path.is_ident("requires") {
+                    meta.parse_nested_meta(|permission| {
+                        let permission_name = permission
+                            .path
+                            .get_ident()
+                            .map(Ident::to_string)
+                            .unwrap_or_default();
+
+                        if !PERMISSIONS.contains(&permission_name.as_str()) {
+                            return Err(permission.error(format!(
+                                "неизвестное право, доступны: {}",
+                                PERMISSIONS.join(", ")
+                            )));
+                        }
+
+                        permissions.push(permission_name);
+                        Ok(())
+                    })
+                } else {
+                    Err(meta.error("ожидалось rename = \"...\" или requires(...)"))
+                }
+            })?;
+        }
+    }
+
+    let args = parse_args(fields)?;
+
+    Ok(Command {
+        variant: variant.clone(),
+        name,
+        description: description
+            .into_iter()
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" "),
+        permissions,
+        fields: fields.clone(),
+        args,
+    })
+}
+
+fn parse_args(fields: &Fields) -> syn::Result<Vec<Arg>> {
+    let mut args = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let mut is_rest = false;
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("command") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rest") {
+                        is_rest = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("у аргумента есть только атрибут rest"))
+                    }
+                })?;
+            }
+        }
+
+        let is_optional = is_option(&field.ty);
+        let kind = match (is_rest, is_optional) {
+            (false, false) => ArgKind::Required,
+            (false, true) => ArgKind::Optional,
+            (true, false) => ArgKind::Rest,
+            (true, true) => ArgKind::OptionalRest,
+        };
+
+        let previous = args.last().map(|arg: &Arg| arg.kind);
+        if matches!(previous, Some(ArgKind::Rest | ArgKind::OptionalRest)) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "после аргумента с rest других аргументов быть не может",
+            ));
+        }
+        if previous == Some(ArgKind::Optional) && !is_optional {
+            return Err(syn::Error::new_spanned(
+                field,
+                "обязательный аргумент не может идти после необязательного",
+            ));
+        }
+
+        let name = field
+            .ident
+            .as_ref()
+            .map(Ident::to_string)
+            .unwrap_or_else(|| format!("arg{}", index + 1));
+
+        args.push(Arg { name, kind });
+    }
+
+    Ok(args)
+}
+
+/// Тип записан как `Option<T>`
+fn is_option(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.path.segments.last().is_some_and(|segment| {
+        segment.ident == "Option"
+            && matches!(&segment.arguments, PathArguments::AngleBracketed(arguments)
+                if matches!(arguments.args.first(), Some(GenericArgument::Type(_))))
+    })
+}
+
+/// `AddMember` -> `add_member`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+
+    for (index, ch) in chars.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+
+        // Аббревиатура остаётся одним словом: `HTTPGet` -> `http_get`
+        let starts_word = ch.is_uppercase()
+            && previous.is_some_and(|previous| {
+                !previous.is_uppercase() || next.is_some_and(|next| next.is_lowercase())
+            });
+
+        if starts_word {
+            result.push('_');
+        }
+        result.extend(ch.to_lowercase());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_snake_case;
+
+    #[test]
+    fn snake_case_keeps_acronyms_together() {
+        assert_eq!(to_snake_case("Echo"), "echo");
+        assert_eq!(to_snake_case("KickMember"), "kick_member");
+        assert_eq!(to_snake_case("HTTPGet"), "http_get");
+        assert_eq!(to_snake_case("GetHTTP"), "get_http");
+        assert_eq!(to_snake_case("ID"), "id");
+        assert_eq!(to_snake_case("Base64Decode"), "base64_decode");
+    }
+}
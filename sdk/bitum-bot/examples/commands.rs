@@ -0,0 +1,80 @@
+//! Бот с командами. Справка по командам - `/help`.
+//!
+//! ```sh
+//! BITUM_API_URL=http://localhost:5000/api BITUM_BOT_TOKEN=... cargo run --example commands
+//! ```
+
+use bitum_bot::{
+    Bot, BotCommands, ChatMember, ChatMessage, CommandHandler, CommandRouter, EventHandler,
+    HandlerResult,
+};
+
+#[derive(BotCommands)]
+enum Command {
+    /// Повторить текст
+    Echo {
+        #[command(rest)]
+        text: String,
+    },
+    /// Сложить два числа
+    Sum(i64, i64),
+    /// Добавить пользователя в чат
+    #[command(requires(can_add_members))]
+    Invite { user_id: i32 },
+}
+
+struct Commands;
+
+impl EventHandler for Commands {
+    async fn on_member_added(&self, bot: &Bot, member: ChatMember) -> HandlerResult {
+        if member.user.id == bot.user().id {
+            bot.send_message(member.chat.id, Command::help()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CommandHandler<Command> for Commands {
+    async fn on_command(
+        &self,
+        bot: &Bot,
+        message: &ChatMessage,
+        command: Command,
+    ) -> HandlerResult {
+        let chat_id = message.chat.id;
+
+        match command {
+            Command::Echo { text } => {
+                bot.send_message(chat_id, text).await?;
+            }
+            Command::Sum(a, b) => {
+                bot.send_message(chat_id, format!("{} + {} = {}", a, b, a + b))
+                    .await?;
+            }
+            Command::Invite { user_id } => {
+                let member = bot.client().add_chat_member(chat_id, user_id).await?;
+                bot.send_message(chat_id, format!("{} теперь в чате", member.user.username))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let base_url = std::env::var("BITUM_API_URL")?;
+    let token = std::env::var("BITUM_BOT_TOKEN")?;
+
+    let bot = Bot::connect(base_url, token).await?;
+    println!("Запущен бот {}", bot.user().username);
+
+    bot.run_until(CommandRouter::new(Commands), async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await?;
+
+    Ok(())
+}
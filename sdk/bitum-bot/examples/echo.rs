@@ -0,0 +1,37 @@
+//! Бот, который повторяет каждое сообщение в чате.
+//!
+//! ```sh
+//! BITUM_API_URL=http://localhost:5000/api BITUM_BOT_TOKEN=... cargo run --example echo
+//! ```
+
+use bitum_bot::{Bot, ChatMessage, EventHandler, HandlerResult};
+
+struct Echo;
+
+impl EventHandler for Echo {
+    async fn on_new_message(&self, bot: &Bot, message: ChatMessage) -> HandlerResult {
+        if message.sender.id == bot.user().id {
+            return Ok(());
+        }
+
+        bot.send_message(message.chat.id, message.content).await?;
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let base_url = std::env::var("BITUM_API_URL")?;
+    let token = std::env::var("BITUM_BOT_TOKEN")?;
+
+    let bot = Bot::connect(base_url, token).await?;
+    println!("Запущен бот {}", bot.user().username);
+
+    bot.run_until(Echo, async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+    .await?;
+
+    Ok(())
+}
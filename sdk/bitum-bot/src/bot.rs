@@ -0,0 +1,541 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    future::Future,
+    time::Duration,
+};
+
+use log::{debug, warn};
+
+use crate::{
+    client::Client,
+    error::{Error, HandlerResult, Result},
+    handler::{dispatch, EventHandler},
+    structs::{
+        ChatMessage, Event, ReadEventsRequest, ReadEventsResponseData, SendMessageRequest, User,
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct BotConfig {
+    /// Пауза между запросами `GET /events`, когда новых событий нет
+    pub poll_interval: Duration,
+    /// Пауза после первой ошибки. После каждой следующей подряд она удваивается
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Сколько событий отмечать прочитанными одним запросом `DELETE /events`
+    pub ack_batch_size: usize,
+    /// Сколько раз обрабатывать событие, прежде чем пропустить его
+    pub max_handler_attempts: u32,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            ack_batch_size: 256,
+            max_handler_attempts: 5,
+        }
+    }
+}
+
+/// Запросы, нужные циклу событий. Отдельно от `Client`, чтобы цикл можно было проверить без сервера
+trait EventsApi {
+    fn get_events(&self) -> impl Future<Output = Result<Vec<Event>>> + Send;
+
+    fn read_events(
+        &self,
+        read_events_request: &ReadEventsRequest,
+    ) -> impl Future<Output = Result<ReadEventsResponseData>> + Send;
+}
+
+impl EventsApi for Client {
+    fn get_events(&self) -> impl Future<Output = Result<Vec<Event>>> + Send {
+        Client::get_events(self)
+    }
+
+    fn read_events(
+        &self,
+        read_events_request: &ReadEventsRequest,
+    ) -> impl Future<Output = Result<ReadEventsResponseData>> + Send {
+        Client::read_events(self, read_events_request)
+    }
+}
+
+/// Пауза между неудачными попытками: удваивается до `max_backoff`
+struct Backoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl Backoff {
+    fn new(config: &BotConfig) -> Self {
+        Self {
+            initial: config.initial_backoff,
+            max: config.max_backoff,
+            current: config.initial_backoff,
+        }
+    }
+
+    /// Сколько ждать сейчас. Следующая пауза будет вдвое дольше
+    fn next(&mut self) -> Duration {
+        let delay = self.current.min(self.max);
+        self.current = (delay * 2).min(self.max);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.current = self.initial;
+    }
+}
+
+/// Что происходит с событиями между `GET /events` и `DELETE /events`
+#[derive(Default)]
+struct Delivery {
+    /// Обработаны, но сервер ещё не подтвердил, что они прочитаны
+    unacked: BTreeSet<i32>,
+    /// Сколько раз обработчик уже упал на событии
+    failed_attempts: HashMap<i32, u32>,
+}
+
+#[derive(Debug, PartialEq)]
+struct PollOutcome {
+    received: usize,
+    failed: usize,
+}
+
+impl Delivery {
+    /// Один проход: получить события, обработать новые и отметить обработанные прочитанными
+    async fn poll<A, F, Fut>(
+        &mut self,
+        api: &A,
+        config: &BotConfig,
+        mut dispatch: F,
+    ) -> Result<PollOutcome>
+    where
+        A: EventsApi,
+        F: FnMut(Event) -> Fut,
+        Fut: Future<Output = HandlerResult>,
+    {
+        // Если в прошлый раз не получилось отметить события прочитанными, сначала пробуем ещё раз
+        self.ack(api, config.ack_batch_size).await?;
+
+        let mut events = api.get_events().await?;
+        events.sort_by_key(|event| event.id);
+
+        let event_ids: HashSet<i32> = events.iter().map(|event| event.id).collect();
+        self.failed_attempts
+            .retain(|event_id, _| event_ids.contains(event_id));
+
+        let mut outcome = PollOutcome {
+            received: events.len(),
+            failed: 0,
+        };
+
+        for event in events {
+            let event_id = event.id;
+
+            if self.unacked.contains(&event_id) {
+                continue;
+            }
+
+            match dispatch(event).await {
+                Ok(()) => {
+                    self.failed_attempts.remove(&event_id);
+                    self.unacked.insert(event_id);
+                }
+                Err(error) => {
+                    let attempts = self.failed_attempts.entry(event_id).or_default();
+                    *attempts += 1;
+
+                    if *attempts >= config.max_handler_attempts {
+                        warn!(
+                            "Событие {} не обработано за {} попыток, пропускаем: {}",
+                            event_id, attempts, error
+                        );
+                        self.failed_attempts.remove(&event_id);
+                        self.unacked.insert(event_id);
+                    } else {
+                        warn!("Не удалось обработать событие {}: {}", event_id, error);
+                        outcome.failed += 1;
+                    }
+                }
+            }
+        }
+
+        self.ack(api, config.ack_batch_size).await?;
+
+        Ok(outcome)
+    }
+
+    async fn ack<A: EventsApi>(&mut self, api: &A, batch_size: usize) -> Result<()> {
+        let event_ids: Vec<i32> = self.unacked.iter().copied().collect();
+
+        for ids in event_ids.chunks(batch_size.max(1)) {
+            let response = api
+                .read_events(&ReadEventsRequest { ids: ids.to_vec() })
+                .await?;
+
+            debug!("Прочитаны события {:?}", response.read_event_ids);
+
+            // Сервер не возвращает уже прочитанные события, они тоже больше не придут
+            for event_id in ids {
+                self.unacked.remove(event_id);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Bot {
+    client: Client,
+    user: User,
+    config: BotConfig,
+}
+
+impl Bot {
+    /// Проверяет токен и узнаёт, какому боту он принадлежит
+    pub async fn connect(base_url: impl Into<String>, token: impl Into<String>) -> Result<Self> {
+        let client = Client::new(base_url, token);
+        let user = client.get_current_user_info().await?;
+
+        if !user.is_bot {
+            return Err(Error::NotABot(user.username));
+        }
+
+        Ok(Self {
+            client,
+            user,
+            config: BotConfig::default(),
+        })
+    }
+
+    pub fn with_config(mut self, config: BotConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Пользователь, от имени которого работает бот
+    pub fn user(&self) -> &User {
+        &self.user
+    }
+
+    pub async fn send_message(
+        &self,
+        chat_id: i32,
+        content: impl Into<String>,
+    ) -> Result<ChatMessage> {
+        let send_message_request = SendMessageRequest {
+            content: content.into(),
+            files: Vec::new(),
+        };
+
+        self.client
+            .send_message(chat_id, &send_message_request)
+            .await
+    }
+
+    /// Запрашивает `GET /events` раз в `poll_interval` и передаёт события обработчику,
+    /// пока токен действителен. Сервер отвечает сразу, даже если событий нет, поэтому
+    /// новое сообщение доходит до бота с задержкой до `poll_interval`.
+    ///
+    /// Событие отмечается прочитанным только после того, как обработчик его принял,
+    /// поэтому после сбоя или перезапуска одно и то же событие может прийти дважды
+    pub async fn run<H: EventHandler>(&self, handler: H) -> Result<()> {
+        let mut delivery = Delivery::default();
+        let mut backoff = Backoff::new(&self.config);
+
+        loop {
+            let poll = delivery.poll(&self.client, &self.config, |event| {
+                dispatch(&handler, self, event)
+            });
+
+            let failed = match poll.await {
+                Ok(outcome) if outcome.failed == 0 => {
+                    backoff.reset();
+
+                    // Если события были, сразу спрашиваем, не пришли ли новые
+                    if outcome.received == 0 {
+                        tokio::time::sleep(self.config.poll_interval).await;
+                    }
+
+                    false
+                }
+                Ok(_) => true,
+                Err(error) if error.is_unauthorized() => return Err(error),
+                Err(error) => {
+                    warn!("Не удалось получить события: {}", error);
+                    true
+                }
+            };
+
+            if failed {
+                tokio::time::sleep(backoff.next()).await;
+            }
+        }
+    }
+
+    /// То же, что `run`, но останавливается, когда завершится `shutdown`
+    pub async fn run_until<H, F>(&self, handler: H, shutdown: F) -> Result<()>
+    where
+        H: EventHandler,
+        F: Future<Output = ()>,
+    {
+        tokio::select! {
+            result = self.run(handler) => result,
+            _ = shutdown => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::structs::{Chat, EventType};
+
+    /// Сервер в памяти: события лежат, пока их не прочитают
+    #[derive(Default)]
+    struct MockApi {
+        pending: Mutex<BTreeSet<i32>>,
+        read_requests: Mutex<Vec<Vec<i32>>>,
+        failing_reads: Mutex<u32>,
+    }
+
+    impl MockApi {
+        fn with_events(ids: impl IntoIterator<Item = i32>) -> Self {
+            Self {
+                pending: Mutex::new(ids.into_iter().collect()),
+                ..Default::default()
+            }
+        }
+
+        fn fail_next_reads(&self, count: u32) {
+            *self.failing_reads.lock().unwrap() = count;
+        }
+
+        fn pending(&self) -> Vec<i32> {
+            self.pending.lock().unwrap().iter().copied().collect()
+        }
+
+        fn read_requests(&self) -> Vec<Vec<i32>> {
+            self.read_requests.lock().unwrap().clone()
+        }
+    }
+
+    impl EventsApi for MockApi {
+        async fn get_events(&self) -> Result<Vec<Event>> {
+            // В обратном порядке, чтобы проверить сортировку
+            Ok(self.pending().into_iter().rev().map(event).collect())
+        }
+
+        async fn read_events(
+            &self,
+            read_events_request: &ReadEventsRequest,
+        ) -> Result<ReadEventsResponseData> {
+            let mut failing_reads = self.failing_reads.lock().unwrap();
+
+            if *failing_reads > 0 {
+                *failing_reads -= 1;
+
+                return Err(Error::Api {
+                    code: 500,
+                    message: "Internal Server Error".to_string(),
+                });
+            }
+
+            let mut pending = self.pending.lock().unwrap();
+            let read_event_ids = read_events_request
+                .ids
+                .iter()
+                .copied()
+                .filter(|id| pending.remove(id))
+                .collect();
+
+            self.read_requests
+                .lock()
+                .unwrap()
+                .push(read_events_request.ids.clone());
+
+            Ok(ReadEventsResponseData { read_event_ids })
+        }
+    }
+
+    fn event(id: i32) -> Event {
+        let user = User::default();
+
+        Event {
+            id,
+            user: user.clone(),
+            payload: EventType::MemberKicked {
+                user: user.clone(),
+                chat: Chat {
+                    id: 1,
+                    name: "chat".to_string(),
+                    owner: user,
+                    icon: None,
+                    created_at: 0.0,
+                },
+            },
+        }
+    }
+
+    fn config() -> BotConfig {
+        BotConfig {
+            ack_batch_size: 2,
+            max_handler_attempts: 3,
+            ..Default::default()
+        }
+    }
+
+    /// Обработчик, который падает на событиях из `failing` и записывает порядок вызовов
+    fn handler<'a>(
+        handled: &'a Mutex<Vec<i32>>,
+        failing: &'a [i32],
+    ) -> impl FnMut(Event) -> std::future::Ready<HandlerResult> + 'a {
+        move |event| {
+            handled.lock().unwrap().push(event.id);
+
+            std::future::ready(if failing.contains(&event.id) {
+                Err("обработчик упал".into())
+            } else {
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn acks_handled_events_in_batches() {
+        let api = MockApi::with_events(1..=5);
+        let handled = Mutex::new(Vec::new());
+        let mut delivery = Delivery::default();
+
+        let outcome = delivery
+            .poll(&api, &config(), handler(&handled, &[]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            PollOutcome {
+                received: 5,
+                failed: 0
+            }
+        );
+        assert_eq!(*handled.lock().unwrap(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(api.read_requests(), vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert!(api.pending().is_empty());
+        assert!(delivery.unacked.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_event_is_not_acked_and_retried() {
+        let api = MockApi::with_events([1, 2]);
+        let handled = Mutex::new(Vec::new());
+        let mut delivery = Delivery::default();
+
+        let outcome = delivery
+            .poll(&api, &config(), handler(&handled, &[1]))
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.failed, 1);
+        assert_eq!(api.pending(), vec![1]);
+
+        delivery
+            .poll(&api, &config(), handler(&handled, &[]))
+            .await
+            .unwrap();
+
+        assert_eq!(*handled.lock().unwrap(), vec![1, 2, 1]);
+        assert!(api.pending().is_empty());
+        assert!(delivery.failed_attempts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn event_is_skipped_after_max_attempts() {
+        let api = MockApi::with_events([1]);
+        let handled = Mutex::new(Vec::new());
+        let mut delivery = Delivery::default();
+        let config = config();
+
+        for _ in 1..config.max_handler_attempts {
+            let outcome = delivery
+                .poll(&api, &config, handler(&handled, &[1]))
+                .await
+                .unwrap();
+
+            assert_eq!(outcome.failed, 1);
+            assert_eq!(api.pending(), vec![1]);
+        }
+
+        let outcome = delivery
+            .poll(&api, &config, handler(&handled, &[1]))
+            .await
+            .unwrap();
+
+        // Последняя попытка не считается ошибкой: событие пропущено и прочитано
+        assert_eq!(outcome.failed, 0);
+        assert!(api.pending().is_empty());
+        assert!(delivery.failed_attempts.is_empty());
+        assert_eq!(
+            handled.lock().unwrap().len(),
+            config.max_handler_attempts as usize
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_ack_is_retried_without_handling_again() {
+        let api = MockApi::with_events([1, 2]);
+        let handled = Mutex::new(Vec::new());
+        let mut delivery = Delivery::default();
+
+        api.fail_next_reads(1);
+        assert!(delivery
+            .poll(&api, &config(), handler(&handled, &[]))
+            .await
+            .is_err());
+        assert_eq!(delivery.unacked, BTreeSet::from([1, 2]));
+
+        // Ещё одна ошибка при повторе: событие так и лежит на сервере, но второй раз не обрабатывается
+        api.fail_next_reads(1);
+        assert!(delivery
+            .poll(&api, &config(), handler(&handled, &[]))
+            .await
+            .is_err());
+
+        let outcome = delivery
+            .poll(&api, &config(), handler(&handled, &[]))
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.received, 0);
+        assert_eq!(*handled.lock().unwrap(), vec![1, 2]);
+        assert!(api.pending().is_empty());
+        assert!(delivery.unacked.is_empty());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max_and_resets() {
+        let mut backoff = Backoff::new(&BotConfig {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+            ..Default::default()
+        });
+
+        let delays: Vec<u64> = (0..5).map(|_| backoff.next().as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+
+        backoff.reset();
+        assert_eq!(backoff.next(), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,125 @@
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{Error, Result},
+    structs::*,
+};
+
+/// Запросы к API от имени бота. Токен передаётся в заголовке `Authorization`
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    token: String,
+}
+
+impl Client {
+    /// `base_url` - корень API, например `https://example.com/api`
+    pub fn new(base_url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.token)
+    }
+
+    /// Сервер отвечает `{ok, error, data}` с любым HTTP-статусом, поэтому смотрим только на `ok`
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response: Response<T> = request.send().await?.json().await?;
+
+        if !response.ok {
+            return Err(response
+                .error
+                .map(Error::from)
+                .unwrap_or(Error::MissingData));
+        }
+
+        response.data.ok_or(Error::MissingData)
+    }
+
+    pub async fn get_current_user_info(&self) -> Result<User> {
+        self.send(self.request(Method::GET, "/user")).await
+    }
+
+    pub async fn get_user(&self, user_id: i32) -> Result<User> {
+        self.send(self.request(Method::GET, &format!("/user/{}", user_id)))
+            .await
+    }
+
+    pub async fn get_chats(&self) -> Result<Vec<Chat>> {
+        self.send(self.request(Method::GET, "/chats")).await
+    }
+
+    pub async fn get_chat(&self, chat_id: i32) -> Result<Chat> {
+        self.send(self.request(Method::GET, &format!("/chat/{}", chat_id)))
+            .await
+    }
+
+    pub async fn get_messages(
+        &self,
+        chat_id: i32,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<ChatMessage>> {
+        self.send(
+            self.request(Method::GET, &format!("/chat/{}/messages", chat_id))
+                .query(&[("limit", limit), ("offset", offset)]),
+        )
+        .await
+    }
+
+    pub async fn send_message(
+        &self,
+        chat_id: i32,
+        send_message_request: &SendMessageRequest,
+    ) -> Result<ChatMessage> {
+        self.send(
+            self.request(Method::POST, &format!("/chat/{}/message", chat_id))
+                .json(send_message_request),
+        )
+        .await
+    }
+
+    pub async fn get_chat_members(&self, chat_id: i32) -> Result<Vec<ChatMember>> {
+        self.send(self.request(Method::GET, &format!("/chat/{}/members", chat_id)))
+            .await
+    }
+
+    pub async fn get_chat_member(&self, chat_id: i32, user_id: i32) -> Result<ChatMember> {
+        self.send(self.request(
+            Method::GET,
+            &format!("/chat/{}/member/{}", chat_id, user_id),
+        ))
+        .await
+    }
+
+    pub async fn add_chat_member(&self, chat_id: i32, user_id: i32) -> Result<ChatMember> {
+        self.send(
+            self.request(Method::POST, &format!("/chat/{}/member", chat_id))
+                .json(&AddChatMemberRequest { user_id }),
+        )
+        .await
+    }
+
+    pub async fn get_events(&self) -> Result<Vec<Event>> {
+        self.send(self.request(Method::GET, "/events")).await
+    }
+
+    pub async fn read_events(
+        &self,
+        read_events_request: &ReadEventsRequest,
+    ) -> Result<ReadEventsResponseData> {
+        self.send(
+            self.request(Method::DELETE, "/events")
+                .json(read_events_request),
+        )
+        .await
+    }
+}
@@ -0,0 +1,376 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::structs::ChatMemberPermissions;
+
+/// Команда для справки `/help`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommandDescription {
+    /// Имя без `/`
+    pub name: &'static str,
+    /// Аргументы, например `<user_id> [reason...]`
+    pub usage: &'static str,
+    pub description: &'static str,
+    /// Права в чате, которые нужны отправителю
+    pub permissions: ChatMemberPermissions,
+}
+
+/// Ошибки разбора команды. Текст ошибки отправляется пользователю
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CommandError {
+    #[error("неизвестная команда /{0}")]
+    UnknownCommand(String),
+    #[error("не хватает аргумента {0}")]
+    MissingArgument(&'static str),
+    #[error("неверное значение «{value}» для {name}: {reason}")]
+    InvalidArgument {
+        name: &'static str,
+        value: String,
+        reason: String,
+    },
+    #[error("слишком много аргументов")]
+    TooManyArguments,
+    #[error("не закрыта кавычка")]
+    UnterminatedQuote,
+}
+
+/// Набор команд бота. Обычно выводится через `#[derive(BotCommands)]` для enum:
+///
+/// ```ignore
+/// #[derive(BotCommands)]
+/// enum Command {
+///     /// Повторить текст
+///     Echo {
+///         #[command(rest)]
+///         text: String,
+///     },
+///     /// Выгнать участника
+///     #[command(rename = "kick", requires(can_kick_members))]
+///     KickMember { user_id: i32, reason: Option<String> },
+/// }
+/// ```
+///
+/// * Имя команды - имя варианта в snake_case, его можно заменить через `rename`.
+/// * Описание для `/help` берётся из doc-комментария.
+/// * Аргументы разбираются через `FromStr`. `Option<T>` - необязательный аргумент,
+///   `#[command(rest)]` забирает все оставшиеся слова.
+/// * `requires(...)` - права из `ChatMemberPermissions`, которые нужны отправителю.
+pub trait BotCommands: Sized + Send {
+    fn descriptions() -> &'static [CommandDescription];
+
+    /// Имя команды без `/`
+    fn name(&self) -> &'static str;
+
+    fn parse(name: &str, args: Vec<String>) -> Result<Self, CommandError>;
+
+    fn describe(name: &str) -> Option<&'static CommandDescription> {
+        Self::descriptions()
+            .iter()
+            .find(|description| description.name == name)
+    }
+
+    /// Текст для `/help`
+    fn help() -> String {
+        let mut lines = vec!["Команды:".to_string()];
+
+        for description in Self::descriptions() {
+            let mut line = format!("/{}", description.name);
+
+            if !description.usage.is_empty() {
+                line += &format!(" {}", description.usage);
+            }
+            if !description.description.is_empty() {
+                line += &format!(" — {}", description.description);
+            }
+
+            let permissions = permission_names(&description.permissions);
+            if !permissions.is_empty() {
+                line += &format!(" (нужно право {})", permissions.join(", "));
+            }
+
+            lines.push(line);
+        }
+
+        if Self::describe("help").is_none() {
+            lines.push("/help — список команд".to_string());
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Имя команды и аргументы из текста сообщения. Имя отдаётся, даже если аргументы
+/// разобрать не удалось, чтобы неизвестные команды можно было пропустить целиком.
+///
+/// `None`, если сообщение не начинается с `/`
+pub fn split_command(content: &str) -> Option<(String, Result<Vec<String>, CommandError>)> {
+    let content = content.trim().strip_prefix('/')?;
+    let (name, args) = content
+        .split_once(char::is_whitespace)
+        .unwrap_or((content, ""));
+
+    if name.is_empty() {
+        return None;
+    }
+
+    Some((name.to_string(), split_args(args)))
+}
+
+/// Делит строку на аргументы по пробелам. Текст в двойных кавычках - один аргумент,
+/// внутри кавычек `\"` и `\\` означают сами символы
+pub fn split_args(input: &str) -> Result<Vec<String>, CommandError> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut has_current = false;
+    let mut in_quotes = false;
+    let mut chars = input.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_current = true;
+            }
+            '\\' if in_quotes => match chars.next() {
+                Some(escaped @ ('"' | '\\')) => current.push(escaped),
+                Some(other) => {
+                    current.push('\\');
+                    current.push(other);
+                }
+                None => return Err(CommandError::UnterminatedQuote),
+            },
+            ch if ch.is_whitespace() && !in_quotes => {
+                if has_current {
+                    args.push(std::mem::take(&mut current));
+                    has_current = false;
+                }
+            }
+            ch => {
+                current.push(ch);
+                has_current = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(CommandError::UnterminatedQuote);
+    }
+    if has_current {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+/// Права, которые отмечены в `permissions`, словами
+pub fn permission_names(permissions: &ChatMemberPermissions) -> Vec<&'static str> {
+    [
+        (permissions.can_write, "писать сообщения"),
+        (permissions.can_add_members, "добавлять участников"),
+        (permissions.can_kick_members, "выгонять участников"),
+    ]
+    .into_iter()
+    .filter_map(|(is_set, name)| is_set.then_some(name))
+    .collect()
+}
+
+/// Права из `required`, которых нет в `actual`
+pub fn missing_permissions(
+    required: &ChatMemberPermissions,
+    actual: &ChatMemberPermissions,
+) -> ChatMemberPermissions {
+    ChatMemberPermissions {
+        can_write: required.can_write && !actual.can_write,
+        can_add_members: required.can_add_members && !actual.can_add_members,
+        can_kick_members: required.can_kick_members && !actual.can_kick_members,
+    }
+}
+
+/// Разбор аргументов для кода из `#[derive(BotCommands)]`
+pub struct Args {
+    args: std::vec::IntoIter<String>,
+}
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Self {
+        Self {
+            args: args.into_iter(),
+        }
+    }
+
+    pub fn required<T>(&mut self, name: &'static str) -> Result<T, CommandError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.optional(name)?
+            .ok_or(CommandError::MissingArgument(name))
+    }
+
+    pub fn optional<T>(&mut self, name: &'static str) -> Result<Option<T>, CommandError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.args
+            .next()
+            .map(|value| parse_arg(name, value))
+            .transpose()
+    }
+
+    pub fn rest<T>(&mut self, name: &'static str) -> Result<T, CommandError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.optional_rest(name)?
+            .ok_or(CommandError::MissingArgument(name))
+    }
+
+    pub fn optional_rest<T>(&mut self, name: &'static str) -> Result<Option<T>, CommandError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let rest: Vec<String> = self.args.by_ref().collect();
+
+        if rest.is_empty() {
+            return Ok(None);
+        }
+
+        parse_arg(name, rest.join(" ")).map(Some)
+    }
+
+    pub fn finish(&mut self) -> Result<(), CommandError> {
+        match self.args.next() {
+            Some(_) => Err(CommandError::TooManyArguments),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_arg<T>(name: &'static str, value: String) -> Result<T, CommandError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|error: T::Err| CommandError::InvalidArgument {
+            name,
+            reason: error.to_string(),
+            value,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Args {
+        Args::new(values.iter().map(|value| value.to_string()).collect())
+    }
+
+    #[test]
+    fn split_args_by_whitespace() {
+        assert_eq!(split_args("").unwrap(), Vec::<String>::new());
+        assert_eq!(split_args("  a\tb \n c  ").unwrap(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn split_args_with_quotes() {
+        assert_eq!(split_args(r#"a "b c"  d"#).unwrap(), vec!["a", "b c", "d"]);
+        assert_eq!(split_args(r#""""#).unwrap(), vec![""]);
+        assert_eq!(
+            split_args(r#"pre"fix suf"fix"#).unwrap(),
+            vec!["prefix suffix"]
+        );
+        assert_eq!(split_args(r#"a "b"#), Err(CommandError::UnterminatedQuote));
+    }
+
+    #[test]
+    fn split_args_with_escapes() {
+        assert_eq!(
+            split_args(r#""say \"hi\"" "back\\slash" "\n""#).unwrap(),
+            vec![r#"say "hi""#, r"back\slash", r"\n"]
+        );
+        // Вне кавычек обратная косая черта - обычный символ
+        assert_eq!(split_args(r"C:\dir").unwrap(), vec![r"C:\dir"]);
+        assert_eq!(split_args(r#""end\"#), Err(CommandError::UnterminatedQuote));
+    }
+
+    #[test]
+    fn split_command_keeps_name_when_args_are_broken() {
+        assert_eq!(split_command("hello"), None);
+        assert_eq!(split_command("/"), None);
+        assert_eq!(split_command("/ echo"), None);
+
+        let (name, args) = split_command("  /sum 1 2 ").unwrap();
+        assert_eq!(name, "sum");
+        assert_eq!(args.unwrap(), vec!["1", "2"]);
+
+        let (name, args) = split_command(r#"/echo "open"#).unwrap();
+        assert_eq!(name, "echo");
+        assert_eq!(args, Err(CommandError::UnterminatedQuote));
+
+        let (name, _) = split_command("/usr/bin").unwrap();
+        assert_eq!(name, "usr/bin");
+    }
+
+    #[test]
+    fn args_required_and_optional() {
+        let mut parsed = args(&["1", "x"]);
+        assert_eq!(parsed.required::<i32>("a"), Ok(1));
+        assert_eq!(parsed.optional::<String>("b"), Ok(Some("x".to_string())));
+        assert_eq!(parsed.optional::<i32>("c"), Ok(None));
+        assert_eq!(parsed.finish(), Ok(()));
+
+        assert_eq!(
+            args(&[]).required::<i32>("a"),
+            Err(CommandError::MissingArgument("a"))
+        );
+        assert!(matches!(
+            args(&["x"]).required::<i32>("a"),
+            Err(CommandError::InvalidArgument { name: "a", value, .. }) if value == "x"
+        ));
+    }
+
+    #[test]
+    fn args_rest_joins_remaining_words() {
+        let mut parsed = args(&["1", "b c", "d"]);
+        assert_eq!(parsed.required::<i32>("a"), Ok(1));
+        assert_eq!(parsed.rest::<String>("text"), Ok("b c d".to_string()));
+        assert_eq!(parsed.finish(), Ok(()));
+
+        assert_eq!(
+            args(&[]).rest::<String>("text"),
+            Err(CommandError::MissingArgument("text"))
+        );
+        assert_eq!(args(&[]).optional_rest::<String>("text"), Ok(None));
+    }
+
+    #[test]
+    fn finish_rejects_extra_arguments() {
+        let mut parsed = args(&["1", "2"]);
+        assert_eq!(parsed.required::<i32>("a"), Ok(1));
+        assert_eq!(parsed.finish(), Err(CommandError::TooManyArguments));
+    }
+
+    #[test]
+    fn missing_permissions_only_lists_required() {
+        let required = ChatMemberPermissions {
+            can_write: true,
+            can_add_members: true,
+            can_kick_members: false,
+        };
+        let actual = ChatMemberPermissions {
+            can_write: true,
+            can_add_members: false,
+            can_kick_members: true,
+        };
+
+        assert_eq!(
+            permission_names(&missing_permissions(&required, &actual)),
+            vec!["добавлять участников"]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::structs::ApiError;
+
+const UNAUTHORIZED: i32 = 401;
+const FORBIDDEN: i32 = 403;
+const NOT_FOUND: i32 = 404;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("ошибка запроса: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("сервер вернул ошибку {code}: {message}")]
+    Api { code: i32, message: String },
+    #[error("в ответе сервера нет данных")]
+    MissingData,
+    #[error("{0} не бот, нужен токен бота")]
+    NotABot(String),
+}
+
+impl Error {
+    /// Токен не подходит: повторять запрос бесполезно
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Error::Api { code, .. } if *code == UNAUTHORIZED)
+    }
+
+    /// Сервер отказал в доступе или не нашёл объект, например участника чата
+    pub fn is_access_denied(&self) -> bool {
+        matches!(self, Error::Api { code, .. } if *code == FORBIDDEN || *code == NOT_FOUND)
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(error: ApiError) -> Self {
+        Error::Api {
+            code: error.code,
+            message: error.message,
+        }
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Ошибка обработчика события: событие не будет отмечено прочитанным и придёт ещё раз
+pub type HandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+pub type HandlerResult = Result<(), HandlerError>;
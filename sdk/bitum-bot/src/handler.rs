@@ -0,0 +1,50 @@
+use std::future::Future;
+
+use crate::{
+    bot::Bot,
+    error::HandlerResult,
+    structs::{Chat, ChatMember, ChatMessage, Event, EventType, User},
+};
+
+/// Обработчик событий бота. Реализовывать нужно только нужные методы.
+///
+/// Если метод вернул ошибку, событие не отмечается прочитанным и будет обработано ещё раз
+pub trait EventHandler: Send + Sync {
+    /// Новое сообщение в чате, в том числе отправленное самим ботом
+    fn on_new_message(
+        &self,
+        _bot: &Bot,
+        _message: ChatMessage,
+    ) -> impl Future<Output = HandlerResult> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_member_added(
+        &self,
+        _bot: &Bot,
+        _member: ChatMember,
+    ) -> impl Future<Output = HandlerResult> + Send {
+        async { Ok(()) }
+    }
+
+    fn on_member_kicked(
+        &self,
+        _bot: &Bot,
+        _user: User,
+        _chat: Chat,
+    ) -> impl Future<Output = HandlerResult> + Send {
+        async { Ok(()) }
+    }
+}
+
+pub(crate) async fn dispatch<H: EventHandler>(
+    handler: &H,
+    bot: &Bot,
+    event: Event,
+) -> HandlerResult {
+    match event.payload {
+        EventType::NewMessage(message) => handler.on_new_message(bot, message).await,
+        EventType::MemberAdded(member) => handler.on_member_added(bot, member).await,
+        EventType::MemberKicked { user, chat } => handler.on_member_kicked(bot, user, chat).await,
+    }
+}
@@ -0,0 +1,29 @@
+//! Библиотека для ботов Bitum: клиент API, цикл обработки событий и команды
+//!
+//! Типы запросов и ответов общие с фронтендом, они лежат в крейте `bitum-types`
+
+pub use bitum_types as structs;
+
+mod bot;
+mod client;
+mod commands;
+mod error;
+mod handler;
+mod router;
+
+pub use bitum_bot_derive::BotCommands;
+pub use bot::{Bot, BotConfig};
+pub use client::Client;
+pub use commands::{
+    missing_permissions, permission_names, split_args, split_command, BotCommands,
+    CommandDescription, CommandError,
+};
+pub use error::{Error, HandlerError, HandlerResult, Result};
+pub use handler::EventHandler;
+pub use router::{CommandHandler, CommandRouter};
+pub use structs::*;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::commands::Args;
+}
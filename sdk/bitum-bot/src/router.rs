@@ -0,0 +1,128 @@
+use std::{future::Future, marker::PhantomData};
+
+use crate::{
+    bot::Bot,
+    commands::{missing_permissions, permission_names, split_command, BotCommands, CommandError},
+    error::HandlerResult,
+    handler::EventHandler,
+    structs::{Chat, ChatMember, ChatMessage, User},
+};
+
+/// Обработчик команд. Сообщения без команды и остальные события
+/// передаются в методы `EventHandler`
+pub trait CommandHandler<C: BotCommands>: EventHandler {
+    /// Команда уже разобрана, права отправителя проверены
+    fn on_command(
+        &self,
+        bot: &Bot,
+        message: &ChatMessage,
+        command: C,
+    ) -> impl Future<Output = HandlerResult> + Send;
+}
+
+/// Разбирает команды из сообщений и передаёт их обработчику.
+///
+/// Сообщения самого бота пропускаются. На `/help`, ошибки в аргументах и нехватку прав
+/// бот отвечает сам. Неизвестные команды передаются в `on_new_message` как обычные сообщения
+pub struct CommandRouter<C, H> {
+    handler: H,
+    commands: PhantomData<fn() -> C>,
+}
+
+impl<C: BotCommands, H: CommandHandler<C>> CommandRouter<C, H> {
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            commands: PhantomData,
+        }
+    }
+
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    async fn route(&self, bot: &Bot, message: ChatMessage) -> HandlerResult {
+        let Some((name, args)) = split_command(&message.content) else {
+            return self.handler.on_new_message(bot, message).await;
+        };
+
+        let chat_id = message.chat.id;
+
+        if name == "help" && C::describe("help").is_none() {
+            bot.send_message(chat_id, C::help()).await?;
+            return Ok(());
+        }
+
+        // Чужие команды и текст вроде "/usr/bin" - обычные сообщения, на них бот не отвечает
+        if C::describe(&name).is_none() {
+            return self.handler.on_new_message(bot, message).await;
+        }
+
+        let command = match args.and_then(|args| C::parse(&name, args)) {
+            Ok(command) => command,
+            Err(error) => {
+                bot.send_message(chat_id, usage_error::<C>(&name, error))
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let required = C::describe(command.name())
+            .map(|description| &description.permissions)
+            .filter(|permissions| !permission_names(permissions).is_empty());
+
+        if let Some(required) = required {
+            let missing = match bot
+                .client()
+                .get_chat_member(chat_id, message.sender.id)
+                .await
+            {
+                Ok(member) => permission_names(&missing_permissions(required, &member.permissions)),
+                // Отправителя уже нет в чате: прав нет. Остальные ошибки - повод повторить позже
+                Err(error) if error.is_access_denied() => permission_names(required),
+                Err(error) => return Err(error.into()),
+            };
+
+            if !missing.is_empty() {
+                let text = format!("Для /{} нужно право {}", command.name(), missing.join(", "));
+                bot.send_message(chat_id, text).await?;
+                return Ok(());
+            }
+        }
+
+        self.handler.on_command(bot, &message, command).await
+    }
+}
+
+/// Текст ошибки для пользователя с подсказкой, как правильно вызвать команду
+fn usage_error<C: BotCommands>(name: &str, error: CommandError) -> String {
+    match (&error, C::describe(name)) {
+        (CommandError::UnknownCommand(_), _) | (_, None) => {
+            format!("Ошибка: {}. Список команд: /help", error)
+        }
+        (_, Some(description)) => format!(
+            "Ошибка: {}. Использование: /{} {}",
+            error, description.name, description.usage
+        )
+        .trim_end()
+        .to_string(),
+    }
+}
+
+impl<C: BotCommands, H: CommandHandler<C>> EventHandler for CommandRouter<C, H> {
+    async fn on_new_message(&self, bot: &Bot, message: ChatMessage) -> HandlerResult {
+        if message.sender.id == bot.user().id {
+            return Ok(());
+        }
+
+        self.route(bot, message).await
+    }
+
+    async fn on_member_added(&self, bot: &Bot, member: ChatMember) -> HandlerResult {
+        self.handler.on_member_added(bot, member).await
+    }
+
+    async fn on_member_kicked(&self, bot: &Bot, user: User, chat: Chat) -> HandlerResult {
+        self.handler.on_member_kicked(bot, user, chat).await
+    }
+}
@@ -0,0 +1,87 @@
+use bitum_bot::{BotCommands, CommandError};
+
+#[derive(BotCommands, Debug, PartialEq)]
+enum Command {
+    /// Сложить два числа
+    Sum(i64, i64),
+    /// Выгнать участника
+    #[command(rename = "kick", requires(can_kick_members))]
+    KickMember {
+        user_id: i32,
+        reason: Option<String>,
+    },
+    Echo {
+        #[command(rest)]
+        text: String,
+    },
+    HTTPGet {
+        url: String,
+    },
+}
+
+fn parse(name: &str, args: &[&str]) -> Result<Command, CommandError> {
+    Command::parse(name, args.iter().map(|arg| arg.to_string()).collect())
+}
+
+#[test]
+fn names_and_usage() {
+    let names: Vec<_> = Command::descriptions()
+        .iter()
+        .map(|description| (description.name, description.usage))
+        .collect();
+
+    assert_eq!(
+        names,
+        vec![
+            ("sum", "<arg1> <arg2>"),
+            ("kick", "<user_id> [reason]"),
+            ("echo", "<text...>"),
+            ("http_get", "<url>"),
+        ]
+    );
+    assert!(
+        Command::describe("kick")
+            .unwrap()
+            .permissions
+            .can_kick_members
+    );
+    assert_eq!(
+        Command::describe("sum").unwrap().description,
+        "Сложить два числа"
+    );
+}
+
+#[test]
+fn parses_arguments() {
+    assert_eq!(parse("sum", &["1", "2"]), Ok(Command::Sum(1, 2)));
+    assert_eq!(
+        parse("kick", &["7"]),
+        Ok(Command::KickMember {
+            user_id: 7,
+            reason: None
+        })
+    );
+    assert_eq!(
+        parse("echo", &["a", "b"]),
+        Ok(Command::Echo {
+            text: "a b".to_string()
+        })
+    );
+    assert_eq!(parse("kick", &["7"]).unwrap().name(), "kick");
+}
+
+#[test]
+fn rejects_bad_arguments() {
+    assert_eq!(
+        parse("sum", &["1"]),
+        Err(CommandError::MissingArgument("arg2"))
+    );
+    assert_eq!(
+        parse("sum", &["1", "2", "3"]),
+        Err(CommandError::TooManyArguments)
+    );
+    assert_eq!(
+        parse("nope", &[]),
+        Err(CommandError::UnknownCommand("nope".to_string()))
+    );
+}
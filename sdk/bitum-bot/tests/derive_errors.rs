@@ -0,0 +1,6 @@
+/// Ошибки `#[derive(BotCommands)]`. Ожидаемый вывод компилятора лежит рядом в `ui/*.stderr`,
+/// обновить его можно через `TRYBUILD=overwrite cargo test --test derive_errors`
+#[test]
+fn derive_errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,12 @@
+use bitum_bot::BotCommands;
+
+#[derive(BotCommands)]
+enum Command {
+    Echo {
+        #[command(rest)]
+        text: String,
+        times: u32,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use bitum_bot::BotCommands;
+
+#[derive(BotCommands)]
+enum Command {
+    Echo,
+    #[command(rename = "echo")]
+    Repeat,
+}
+
+fn main() {}
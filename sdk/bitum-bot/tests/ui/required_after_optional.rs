@@ -0,0 +1,8 @@
+use bitum_bot::BotCommands;
+
+#[derive(BotCommands)]
+enum Command {
+    Kick { reason: Option<String>, user_id: i32 },
+}
+
+fn main() {}
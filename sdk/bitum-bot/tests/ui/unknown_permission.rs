@@ -0,0 +1,9 @@
+use bitum_bot::BotCommands;
+
+#[derive(BotCommands)]
+enum Command {
+    #[command(requires(can_ban_members))]
+    Ban { user_id: i32 },
+}
+
+fn main() {}
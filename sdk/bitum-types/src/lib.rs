@@ -1,3 +1,5 @@
+//! Типы запросов и ответов API Bitum. Общие для фронтенда и библиотеки для ботов
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
@@ -7,6 +9,9 @@ pub struct User {
     pub is_bot: bool,
     pub icon: Option<String>,
     pub created_at: f64,
+    /// Создатель бота, у обычных пользователей его нет
+    #[serde(default)]
+    pub creator_id: Option<i32>,
 }
 
 #[derive(Serialize, PartialEq, Deserialize, Debug, Clone)]
@@ -51,6 +56,13 @@ pub enum EventType {
     NewMessage(ChatMessage),
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Event {
+    pub id: i32,
+    pub user: User,
+    pub payload: EventType,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ApiError {
     pub code: i32,
@@ -88,8 +100,67 @@ pub struct NewChatRequest {
     pub icon_file: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PatchChatRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PatchUserRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewBotRequest {
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_file: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PatchBotRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_file: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PasswordResetInfo {
+    /// Сервер умеет сбрасывать пароль
+    pub available: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResetPasswordRequest {
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AddChatMemberRequest {
+    pub user_id: i32,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SendMessageRequest {
     pub content: String,
     pub files: Vec<String>,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReadEventsRequest {
+    pub ids: Vec<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReadEventsResponseData {
+    pub read_event_ids: Vec<i32>,
+}